@@ -11,7 +11,9 @@ use actix_web::{web, App, HttpServer};
 use log::{debug, info};
 
 mod endpoints;
-mod models;
+mod events;
+pub(crate) mod models;
+mod webhooks;
 
 static DEFAULT_SERVER_ADDRESS: &str = "127.0.0.1:15825";
 static EMBEDDED_SERVER_ADDRESS: &str = "127.0.0.1:8080";
@@ -69,9 +71,10 @@ pub fn start(
             let app = App::new()
                 .wrap(Cors::new().finish())
                 .data(context.clone())
-                .service(web::resource("/api/server").to(endpoints::server_info))
-                .service(web::resource("/api/builds").to(endpoints::get_builds))
-                .service(web::resource("/api/builds/view/{id}").to(endpoints::get_builds_for_view));
+                // The versioned API has to be registered first since
+                // the unversioned scope would match its requests as well.
+                .service(web::scope("/api/v1").configure(routes))
+                .service(web::scope("/api").configure(routes));
 
             // Serve static files from the web directory?
             if cfg!(feature = "docker") {
@@ -100,6 +103,22 @@ pub fn start(
     Ok(HttpServerHandle::new(rx.recv()?))
 }
 
+/// Registers the API routes.
+/// These are available both with and without a version prefix.
+fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/server").to(endpoints::server_info))
+        .service(web::resource("/builds").to(endpoints::get_builds))
+        .service(web::resource("/builds/view/{id}").to(endpoints::get_builds_for_view))
+        .service(web::resource("/builds/{id}/history").to(endpoints::get_build_history))
+        .service(web::resource("/stats").to(endpoints::get_stats))
+        .service(web::resource("/events").to(endpoints::get_events))
+        .service(web::resource("/events/view/{id}").to(endpoints::get_events_for_view))
+        .service(web::resource("/ingest").route(web::post().to(endpoints::ingest)))
+        .service(web::resource("/webhooks/github").route(web::post().to(webhooks::github)))
+        .service(web::resource("/webhooks/azure").route(web::post().to(webhooks::azure)))
+        .service(web::resource("/webhooks/gitlab").route(web::post().to(webhooks::gitlab)));
+}
+
 fn get_binding(server_address: &Option<String>) -> String {
     // Get the address to bind to.
     match server_address {
@@ -1,12 +1,22 @@
 use std::sync::Arc;
 
-use actix_web::web;
-use actix_web::HttpResponse;
+use actix_web::web::{self, Bytes};
+use actix_web::{HttpRequest, HttpResponse};
+use futures::{future, stream, StreamExt};
 
+use crate::builds::Build;
+use crate::engine::state::history::statistics::BuildStatistics;
+use crate::engine::state::views::View;
 use crate::engine::state::EngineState;
-use crate::utils::VERSION;
+use crate::filters::{BuildFilter, FilterResult};
+use crate::utils::{API_CAPABILITIES, API_VERSION, API_VERSIONS, VERSION};
 
-use super::models::{BuildViewModel, ServerInfoModel, ViewInfoModel};
+use super::events::EventConverter;
+use super::models::{
+    ApiInfoModel, BuildHistoryViewModel, BuildQueryModel, BuildViewModel, IngestModel,
+    IngestResultModel, PartitionStatisticsViewModel, ServerInfoModel, StatisticsViewModel,
+    ViewInfoModel,
+};
 
 ///////////////////////////////////////////////////////////
 // Server information
@@ -26,6 +36,11 @@ pub async fn server_info(state: web::Data<Arc<EngineState>>) -> HttpResponse {
             .iter()
             .map(ViewInfoModel::from)
             .collect(),
+        api: ApiInfoModel {
+            version: API_VERSION,
+            versions: API_VERSIONS,
+            capabilities: API_CAPABILITIES,
+        },
     };
     let json = serde_json::to_string(&info).unwrap();
     HttpResponse::Ok()
@@ -36,14 +51,17 @@ pub async fn server_info(state: web::Data<Arc<EngineState>>) -> HttpResponse {
 ///////////////////////////////////////////////////////////
 // All builds
 
-pub async fn get_builds(state: web::Data<Arc<EngineState>>) -> HttpResponse {
+pub async fn get_builds(
+    query: web::Query<BuildQueryModel>,
+    state: web::Data<Arc<EngineState>>,
+) -> HttpResponse {
+    let builds = match filter_builds(state.builds.all(), &query) {
+        Ok(builds) => builds,
+        Err(response) => return response,
+    };
+
     // Convert to view models
-    let builds: Vec<BuildViewModel> = state
-        .builds
-        .all()
-        .iter()
-        .map(BuildViewModel::from)
-        .collect();
+    let builds: Vec<BuildViewModel> = builds.iter().map(BuildViewModel::from).collect();
 
     // Serialize to JSON and return.
     let json = serde_json::to_string(&builds).unwrap();
@@ -57,19 +75,176 @@ pub async fn get_builds(state: web::Data<Arc<EngineState>>) -> HttpResponse {
 
 pub async fn get_builds_for_view(
     id: web::Path<String>,
+    query: web::Query<BuildQueryModel>,
     state: web::Data<Arc<EngineState>>,
 ) -> HttpResponse {
+    let builds = state.builds.for_view(&state.views, &id[..]);
+    let builds = match filter_builds(builds, &query) {
+        Ok(builds) => builds,
+        Err(response) => return response,
+    };
+
     // Convert to view models
-    let builds: Vec<BuildViewModel> = state
-        .builds
-        .for_view(&state.views, &id[..])
+    let builds: Vec<BuildViewModel> = builds.iter().map(BuildViewModel::from).collect();
+
+    // Serialize to JSON and return.
+    let json = serde_json::to_string(&builds).unwrap();
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json)
+}
+
+/// Filters builds using the (optional) filter expression in the query string.
+fn filter_builds(builds: Vec<Build>, query: &BuildQueryModel) -> Result<Vec<Build>, HttpResponse> {
+    let filter = match BuildFilter::new(query.q.clone()) {
+        Ok(filter) => filter,
+        Err(e) => return Err(HttpResponse::BadRequest().body(format!("{}", e))),
+    };
+    Ok(builds
+        .into_iter()
+        .filter(|b| match filter.evaluate(b) {
+            FilterResult::Retain => true,
+            FilterResult::Filter | FilterResult::Error(_) => false,
+        })
+        .collect())
+}
+
+///////////////////////////////////////////////////////////
+// Build history
+
+pub async fn get_build_history(
+    id: web::Path<u64>,
+    state: web::Data<Arc<EngineState>>,
+) -> HttpResponse {
+    let partition = match state.history.get_partition(*id) {
+        Some(partition) => partition,
+        None => return HttpResponse::NotFound().finish(),
+    };
+
+    // Get all results for the build's partition.
+    let results = state.history.get_results(partition);
+    let history = BuildHistoryViewModel {
+        statistics: StatisticsViewModel::from(&BuildStatistics::calculate(&results)),
+        builds: results.iter().rev().map(BuildViewModel::from).collect(),
+    };
+
+    // Serialize to JSON and return.
+    let json = serde_json::to_string(&history).unwrap();
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json)
+}
+
+///////////////////////////////////////////////////////////
+// Statistics
+
+pub async fn get_stats(state: web::Data<Arc<EngineState>>) -> HttpResponse {
+    // Calculate statistics for every partition.
+    let stats: Vec<PartitionStatisticsViewModel> = state
+        .history
+        .get_all_results()
         .iter()
-        .map(BuildViewModel::from)
+        .filter_map(|results| {
+            results.last().map(|latest| {
+                PartitionStatisticsViewModel::new(latest, &BuildStatistics::calculate(results))
+            })
+        })
         .collect();
 
     // Serialize to JSON and return.
-    let json = serde_json::to_string(&builds).unwrap();
+    let json = serde_json::to_string(&stats).unwrap();
     HttpResponse::Ok()
         .content_type("application/json")
         .body(json)
 }
+
+///////////////////////////////////////////////////////////
+// Ingest
+
+pub async fn ingest(
+    request: HttpRequest,
+    model: web::Json<IngestModel>,
+    state: web::Data<Arc<EngineState>>,
+) -> HttpResponse {
+    // Make sure that the client is allowed to push builds to the collector.
+    let inbox = match state.ingest.get_inbox(&model.collector[..]) {
+        Some(inbox) => inbox,
+        None => return HttpResponse::Unauthorized().finish(),
+    };
+    let token = request
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .filter(|h| h.starts_with("Bearer "))
+        .map(|h| &h[7..]);
+    if !token.map_or(false, |t| inbox.is_authorized(t)) {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    // Validate all builds before accepting any of them.
+    let mut builds = Vec::new();
+    for (index, build) in model.builds.iter().enumerate() {
+        match build.to_build(&model.collector[..]) {
+            Ok(build) => builds.push(build),
+            Err(e) => {
+                return HttpResponse::BadRequest()
+                    .body(format!("Build at index {} is invalid: {}", index, e))
+            }
+        }
+    }
+
+    let result = IngestResultModel {
+        accepted: builds.len(),
+    };
+    for build in builds {
+        inbox.push(build);
+    }
+
+    // Process the pushed builds right away.
+    state.triggers.trigger(&model.collector[..]);
+
+    // Serialize to JSON and return.
+    let json = serde_json::to_string(&result).unwrap();
+    HttpResponse::Accepted()
+        .content_type("application/json")
+        .body(json)
+}
+
+///////////////////////////////////////////////////////////
+// Events
+
+pub async fn get_events(state: web::Data<Arc<EngineState>>) -> HttpResponse {
+    stream_events(state.get_ref().clone(), None)
+}
+
+///////////////////////////////////////////////////////////
+// Events for view
+
+pub async fn get_events_for_view(
+    id: web::Path<String>,
+    state: web::Data<Arc<EngineState>>,
+) -> HttpResponse {
+    match state.views.get_view(&id[..]) {
+        Some(view) => stream_events(state.get_ref().clone(), Some(view)),
+        None => HttpResponse::NotFound().finish(),
+    }
+}
+
+fn stream_events(state: Arc<EngineState>, view: Option<Arc<View>>) -> HttpResponse {
+    let notifications = state.events.subscribe();
+    let mut converter = EventConverter::new(state, view);
+
+    // Start by sending the current status, then
+    // all events that the subscriber is interested in.
+    let connected = stream::once(future::ready(Some(converter.connected())));
+    let events = connected
+        .chain(notifications.map(move |n| converter.convert(n)))
+        .filter_map(|event| {
+            future::ready(event.map(|e| Ok::<_, actix_web::Error>(Bytes::from(e))))
+        });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .header("Cache-Control", "no-cache")
+        .streaming(events)
+}
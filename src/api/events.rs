@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::builds::{Build, BuildStatus};
+use crate::engine::state::events::EngineNotification;
+use crate::engine::state::views::View;
+use crate::engine::state::EngineState;
+
+use super::models::{BuildViewModel, StatusViewModel};
+
+/// Converts engine notifications to server-sent events for a
+/// single subscriber, optionally limited to the builds in a view.
+pub struct EventConverter {
+    state: Arc<EngineState>,
+    view: Option<Arc<View>>,
+    status: BuildStatus,
+}
+
+impl EventConverter {
+    pub fn new(state: Arc<EngineState>, view: Option<Arc<View>>) -> Self {
+        let status = match &view {
+            Some(view) => state.builds.current_status_for_view(view),
+            None => state.builds.current_status(),
+        };
+        Self {
+            state,
+            view,
+            status,
+        }
+    }
+
+    /// Gets the event that is sent when a subscriber connects.
+    pub fn connected(&self) -> String {
+        format_event("statusChanged", &StatusViewModel::from(&self.status))
+    }
+
+    pub fn convert(&mut self, notification: EngineNotification) -> Option<String> {
+        match notification {
+            EngineNotification::BuildUpdated(build) => {
+                if !self.is_interested_in(&build) {
+                    return None;
+                }
+                let mut event = format_event("buildUpdated", &BuildViewModel::from(&build));
+                if let Some(status) = self.get_view_status_change() {
+                    event.push_str(&status);
+                }
+                Some(event)
+            }
+            EngineNotification::BuildStatusChanged(build) => {
+                if !self.is_interested_in(&build) {
+                    return None;
+                }
+                let mut event = format_event("buildStatusChanged", &BuildViewModel::from(&build));
+                if let Some(status) = self.get_view_status_change() {
+                    event.push_str(&status);
+                }
+                Some(event)
+            }
+            EngineNotification::StatusChanged(status) => {
+                // Views keep track of their own status.
+                if self.view.is_some() {
+                    return None;
+                }
+                self.status = status;
+                Some(format_event(
+                    "statusChanged",
+                    &StatusViewModel::from(&self.status),
+                ))
+            }
+        }
+    }
+
+    fn is_interested_in(&self, build: &Build) -> bool {
+        match &self.view {
+            Some(view) => view.includes(build),
+            None => true,
+        }
+    }
+
+    fn get_view_status_change(&mut self) -> Option<String> {
+        if let Some(view) = &self.view {
+            let status = self.state.builds.current_status_for_view(view);
+            if status != self.status {
+                self.status = status;
+                return Some(format_event(
+                    "statusChanged",
+                    &StatusViewModel::from(&self.status),
+                ));
+            }
+        }
+        None
+    }
+}
+
+fn format_event<T: Serialize>(name: &str, data: &T) -> String {
+    format!(
+        "event: {}\ndata: {}\n\n",
+        name,
+        serde_json::to_string(data).unwrap()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+    use crate::config::ViewConfiguration;
+
+    fn create_state() -> Arc<EngineState> {
+        let state = Arc::new(EngineState::new());
+        state.views.add_views(&[ViewConfiguration {
+            id: "foo".to_owned(),
+            name: "Foo".to_owned(),
+            collectors: Some(vec!["a".to_owned()]),
+            filter: None,
+        }]);
+        state
+    }
+
+    #[test]
+    fn should_convert_build_updated_notification() {
+        // Given
+        let mut converter = EventConverter::new(create_state(), None);
+        let build = BuildBuilder::dummy().build_id("123").unwrap();
+
+        // When
+        let event = converter
+            .convert(EngineNotification::BuildUpdated(build))
+            .unwrap();
+
+        // Then
+        assert!(event.starts_with("event: buildUpdated\ndata: {"));
+        assert!(event.contains("\"buildId\":\"123\""));
+        assert!(event.ends_with("}\n\n"));
+    }
+
+    #[test]
+    fn should_convert_status_changed_notification() {
+        // Given
+        let mut converter = EventConverter::new(create_state(), None);
+
+        // When
+        let event = converter.convert(EngineNotification::StatusChanged(BuildStatus::Failed));
+
+        // Then
+        assert_eq!(
+            "event: statusChanged\ndata: {\"status\":\"Failed\"}\n\n",
+            event.unwrap()
+        );
+    }
+
+    #[test]
+    fn should_ignore_builds_from_collectors_outside_of_view() {
+        // Given
+        let state = create_state();
+        let view = state.views.get_view("foo");
+        let mut converter = EventConverter::new(state, view);
+        let build = BuildBuilder::dummy().collector("b").unwrap();
+
+        // When
+        let event = converter.convert(EngineNotification::BuildUpdated(build));
+
+        // Then
+        assert!(event.is_none());
+    }
+
+    #[test]
+    fn should_send_view_status_when_it_changes() {
+        // Given
+        let state = create_state();
+        let view = state.views.get_view("foo");
+        let mut converter = EventConverter::new(state.clone(), view);
+        let build = BuildBuilder::dummy()
+            .collector("a")
+            .status(BuildStatus::Failed)
+            .unwrap();
+        state.builds.update(&build);
+
+        // When
+        let event = converter
+            .convert(EngineNotification::BuildUpdated(build))
+            .unwrap();
+
+        // Then
+        assert!(event.starts_with("event: buildUpdated\n"));
+        assert!(event.ends_with("event: statusChanged\ndata: {\"status\":\"Failed\"}\n\n"));
+    }
+
+    #[test]
+    fn should_send_view_status_when_build_status_changes() {
+        // Given
+        let state = create_state();
+        let view = state.views.get_view("foo");
+        state.builds.update(
+            &BuildBuilder::dummy()
+                .collector("a")
+                .status(BuildStatus::Running)
+                .unwrap(),
+        );
+        let mut converter = EventConverter::new(state.clone(), view);
+        let build = BuildBuilder::dummy()
+            .collector("a")
+            .status(BuildStatus::Failed)
+            .unwrap();
+        state.builds.update(&build);
+
+        // When
+        let event = converter
+            .convert(EngineNotification::BuildStatusChanged(build))
+            .unwrap();
+
+        // Then
+        assert!(event.starts_with("event: buildStatusChanged\n"));
+        assert!(event.ends_with("event: statusChanged\ndata: {\"status\":\"Failed\"}\n\n"));
+    }
+
+    #[test]
+    fn should_not_forward_overall_status_to_view_subscribers() {
+        // Given
+        let state = create_state();
+        let view = state.views.get_view("foo");
+        let mut converter = EventConverter::new(state, view);
+
+        // When
+        let event = converter.convert(EngineNotification::StatusChanged(BuildStatus::Failed));
+
+        // Then
+        assert!(event.is_none());
+    }
+}
@@ -1,7 +1,8 @@
 use serde::Serialize;
 
-use crate::builds::{Build, BuildStatus};
+use crate::builds::{Build, BuildBuilder, BuildStatus};
 use crate::config::ViewConfiguration;
+use crate::engine::state::history::statistics::BuildStatistics;
 
 ///////////////////////////////////////////////////////////
 // Server information
@@ -12,6 +13,14 @@ pub struct ServerInfoModel<'a> {
     pub version: &'static str,
     pub started: u64,
     pub views: Vec<ViewInfoModel>,
+    pub api: ApiInfoModel,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ApiInfoModel {
+    pub version: u16,
+    pub versions: &'static [u16],
+    pub capabilities: &'static [&'static str],
 }
 
 ///////////////////////////////////////////////////////////
@@ -35,17 +44,28 @@ impl<'a> From<&ViewConfiguration> for ViewInfoModel {
 ///////////////////////////////////////////////////////////
 // Builds
 
-#[derive(Serialize, Clone)]
+#[derive(Deserialize)]
+pub struct BuildQueryModel {
+    /// An optional filter expression.
+    pub q: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct BuildViewModel {
+    #[serde(default)]
     pub id: u64,
     pub provider: String,
     pub collector: String,
     pub project: String,
+    #[serde(rename = "projectId", default)]
+    pub project_id: Option<String>,
     pub build: String,
+    #[serde(rename = "definitionId", default)]
+    pub definition_id: Option<String>,
     pub branch: String,
-    #[serde(rename(serialize = "buildId"))]
+    #[serde(rename = "buildId")]
     pub build_id: String,
-    #[serde(rename(serialize = "buildNumber"))]
+    #[serde(rename = "buildNumber")]
     pub build_number: String,
     pub started: i64,
     pub finished: Option<i64>,
@@ -53,7 +73,7 @@ pub struct BuildViewModel {
     pub status: BuildStatusViewModel,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub enum BuildStatusViewModel {
     Unknown,
     Success,
@@ -71,7 +91,9 @@ impl From<&Build> for BuildViewModel {
             provider: item.provider.clone(),
             collector: item.collector.clone(),
             project: item.project_name.clone(),
+            project_id: Some(item.project_id.clone()),
             build: item.definition_name.clone(),
+            definition_id: Some(item.definition_id.clone()),
             branch: item.branch.clone(),
             build_id: item.build_id.clone(),
             build_number: item.build_number.clone(),
@@ -83,6 +105,44 @@ impl From<&Build> for BuildViewModel {
     }
 }
 
+impl BuildViewModel {
+    /// Converts a build view model (such as the ones returned
+    /// from the builds API) back to a build. View models without
+    /// IDs (such as ones saved by older versions) use the names instead.
+    pub fn to_build(&self) -> Result<Build, String> {
+        BuildBuilder::new()
+            .build_id(self.build_id.clone())
+            .provider(self.provider.clone())
+            .origin(self.collector.clone())
+            .collector(self.collector.clone())
+            .project_id(self.project_id.as_ref().unwrap_or(&self.project).clone())
+            .project_name(self.project.clone())
+            .definition_id(self.definition_id.as_ref().unwrap_or(&self.build).clone())
+            .definition_name(self.build.clone())
+            .build_number(self.build_number.clone())
+            .status(BuildStatus::from(&self.status))
+            .branch(self.branch.clone())
+            .url(self.url.clone())
+            .started_at(self.started)
+            .finished_at(self.finished)
+            .build()
+    }
+}
+
+impl From<&BuildStatusViewModel> for BuildStatus {
+    fn from(item: &BuildStatusViewModel) -> Self {
+        match item {
+            BuildStatusViewModel::Unknown => BuildStatus::Unknown,
+            BuildStatusViewModel::Success => BuildStatus::Success,
+            BuildStatusViewModel::Failed => BuildStatus::Failed,
+            BuildStatusViewModel::Running => BuildStatus::Running,
+            BuildStatusViewModel::Canceled => BuildStatus::Canceled,
+            BuildStatusViewModel::Queued => BuildStatus::Queued,
+            BuildStatusViewModel::Skipped => BuildStatus::Skipped,
+        }
+    }
+}
+
 impl From<&BuildStatus> for BuildStatusViewModel {
     fn from(item: &BuildStatus) -> Self {
         match item {
@@ -96,3 +156,272 @@ impl From<&BuildStatus> for BuildStatusViewModel {
         }
     }
 }
+
+///////////////////////////////////////////////////////////
+// Status
+
+#[derive(Serialize, Clone)]
+pub struct StatusViewModel {
+    pub status: BuildStatusViewModel,
+}
+
+impl From<&BuildStatus> for StatusViewModel {
+    fn from(item: &BuildStatus) -> Self {
+        StatusViewModel {
+            status: BuildStatusViewModel::from(item),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Statistics
+
+#[derive(Serialize, Clone)]
+pub struct StatisticsViewModel {
+    pub builds: usize,
+    #[serde(rename(serialize = "successRate"))]
+    pub success_rate: Option<f64>,
+    #[serde(rename(serialize = "meanDuration"))]
+    pub mean_duration: Option<i64>,
+    #[serde(rename(serialize = "p95Duration"))]
+    pub p95_duration: Option<i64>,
+    #[serde(rename(serialize = "meanTimeToRecovery"))]
+    pub mean_time_to_recovery: Option<i64>,
+}
+
+impl From<&BuildStatistics> for StatisticsViewModel {
+    fn from(item: &BuildStatistics) -> Self {
+        StatisticsViewModel {
+            builds: item.builds,
+            success_rate: item.success_rate,
+            mean_duration: item.mean_duration,
+            p95_duration: item.p95_duration,
+            mean_time_to_recovery: item.mean_time_to_recovery,
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct PartitionStatisticsViewModel {
+    pub provider: String,
+    pub collector: String,
+    pub project: String,
+    pub build: String,
+    pub branch: String,
+    pub latest: BuildViewModel,
+    pub statistics: StatisticsViewModel,
+}
+
+impl PartitionStatisticsViewModel {
+    pub fn new(latest: &Build, statistics: &BuildStatistics) -> Self {
+        PartitionStatisticsViewModel {
+            provider: latest.provider.clone(),
+            collector: latest.collector.clone(),
+            project: latest.project_name.clone(),
+            build: latest.definition_name.clone(),
+            branch: latest.branch.clone(),
+            latest: BuildViewModel::from(latest),
+            statistics: StatisticsViewModel::from(statistics),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////
+// History
+
+#[derive(Serialize, Clone)]
+pub struct BuildHistoryViewModel {
+    pub statistics: StatisticsViewModel,
+    pub builds: Vec<BuildViewModel>,
+}
+
+///////////////////////////////////////////////////////////
+// Ingest
+
+#[derive(Deserialize)]
+pub struct IngestModel {
+    pub collector: String,
+    pub builds: Vec<IngestBuildModel>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct IngestBuildModel {
+    #[serde(rename = "buildId")]
+    pub build_id: Option<String>,
+    pub origin: Option<String>,
+    #[serde(rename = "projectId")]
+    pub project_id: Option<String>,
+    #[serde(rename = "projectName")]
+    pub project_name: Option<String>,
+    #[serde(rename = "definitionId")]
+    pub definition_id: Option<String>,
+    #[serde(rename = "definitionName")]
+    pub definition_name: Option<String>,
+    #[serde(rename = "buildNumber")]
+    pub build_number: Option<String>,
+    pub status: Option<BuildStatus>,
+    pub branch: Option<String>,
+    pub url: Option<String>,
+    pub started: Option<i64>,
+    pub finished: Option<i64>,
+}
+
+impl IngestBuildModel {
+    /// Converts the pushed build into a build that belongs to the
+    /// specified collector. Names default to their corresponding IDs.
+    /// The provider is always the push collector's, otherwise the
+    /// build would never be removed once it has expired.
+    pub fn to_build(&self, collector: &str) -> Result<Build, String> {
+        let mut builder = BuildBuilder::new()
+            .collector(collector)
+            .provider("Push")
+            .origin(self.origin.as_deref().unwrap_or(collector))
+            .finished_at(self.finished);
+        if let Some(build_id) = &self.build_id {
+            builder = builder.build_id(build_id);
+        }
+        if let Some(project_id) = &self.project_id {
+            builder = builder
+                .project_id(project_id)
+                .project_name(self.project_name.as_ref().unwrap_or(project_id));
+        }
+        if let Some(definition_id) = &self.definition_id {
+            builder = builder
+                .definition_id(definition_id)
+                .definition_name(self.definition_name.as_ref().unwrap_or(definition_id));
+        }
+        if let Some(build_number) = &self.build_number {
+            builder = builder.build_number(build_number);
+        }
+        if let Some(status) = &self.status {
+            builder = builder.status(status.clone());
+        }
+        if let Some(branch) = &self.branch {
+            builder = builder.branch(branch);
+        }
+        if let Some(url) = &self.url {
+            builder = builder.url(url);
+        }
+        if let Some(started) = self.started {
+            builder = builder.started_at(started);
+        }
+        builder.build()
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct IngestResultModel {
+    pub accepted: usize,
+}
+
+///////////////////////////////////////////////////////////
+// Webhooks
+
+#[derive(Serialize, Clone)]
+pub struct WebhookResultModel {
+    pub triggered: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_model() -> IngestBuildModel {
+        IngestBuildModel {
+            build_id: Some("123".to_owned()),
+            project_id: Some("duck".to_owned()),
+            definition_id: Some("ci".to_owned()),
+            build_number: Some("42".to_owned()),
+            status: Some(BuildStatus::Success),
+            branch: Some("master".to_owned()),
+            url: Some("https://example.com/builds/123".to_owned()),
+            started: Some(1588580832),
+            finished: Some(1588581237),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_convert_build_view_model_back_to_build() {
+        // Given
+        let json = serde_json::to_string(&BuildViewModel::from(
+            &BuildBuilder::dummy().build().unwrap(),
+        ))
+        .unwrap();
+        let model: BuildViewModel = serde_json::from_str(&json).unwrap();
+        // When
+        let build = model.to_build().unwrap();
+        // Then
+        assert_eq!("foo", build.build_id);
+        assert_eq!("TeamCity", build.provider);
+        assert_eq!("collector", build.collector);
+        assert_eq!("project_id", build.project_id);
+        assert_eq!("project_name", build.project_name);
+        assert_eq!("definition_id", build.definition_id);
+        assert_eq!("definition_name", build.definition_name);
+        assert_eq!("build_number", build.build_number);
+        assert_eq!(BuildStatus::Success, build.status);
+        assert_eq!(1578819921, build.started_at);
+        assert_eq!(Some(1578820921), build.finished_at);
+    }
+
+    #[test]
+    fn should_use_names_as_ids_if_build_view_model_has_no_ids() {
+        // Given
+        let model: BuildViewModel = serde_json::from_str(
+            r#"{
+                "provider": "TeamCity",
+                "collector": "collector",
+                "project": "Duck",
+                "build": "CI",
+                "branch": "master",
+                "buildId": "42",
+                "buildNumber": "42",
+                "started": 1578819921,
+                "finished": null,
+                "url": "https://example.com",
+                "status": "Running"
+            }"#,
+        )
+        .unwrap();
+
+        // When
+        let build = model.to_build().unwrap();
+
+        // Then
+        assert_eq!("Duck", build.project_id);
+        assert_eq!("CI", build.definition_id);
+    }
+
+    #[test]
+    fn should_convert_pushed_build() {
+        // Given
+        let model = create_model();
+        // When
+        let build = model.to_build("push").unwrap();
+        // Then
+        assert_eq!("123", build.build_id);
+        assert_eq!("Push", build.provider);
+        assert_eq!("push", build.collector);
+        assert_eq!("push", build.origin);
+        assert_eq!("duck", build.project_id);
+        assert_eq!("duck", build.project_name);
+        assert_eq!("ci", build.definition_id);
+        assert_eq!("ci", build.definition_name);
+        assert_eq!(BuildStatus::Success, build.status);
+        assert_eq!(Some(1588581237), build.finished_at);
+    }
+
+    #[test]
+    fn should_return_error_if_pushed_build_is_incomplete() {
+        // Given
+        let model = IngestBuildModel {
+            branch: None,
+            ..create_model()
+        };
+        // When
+        let result = model.to_build("push");
+        // Then
+        assert_eq!(Err("Branch is missing".to_owned()), result);
+    }
+}
@@ -0,0 +1,428 @@
+use std::sync::Arc;
+
+use actix_web::web::{self, Bytes};
+use actix_web::{HttpRequest, HttpResponse};
+use hmac::{Hmac, Mac};
+use log::debug;
+use sha2::Sha256;
+use url::Url;
+
+use crate::engine::state::webhooks::{WebhookSource, WebhookTarget};
+use crate::engine::state::EngineState;
+use crate::utils::secret;
+
+use super::models::WebhookResultModel;
+
+///////////////////////////////////////////////////////////
+// GitHub
+
+pub async fn github(
+    request: HttpRequest,
+    body: Bytes,
+    state: web::Data<Arc<EngineState>>,
+) -> HttpResponse {
+    let result = match_github(
+        &state.webhooks.get_targets(),
+        get_header(&request, "X-GitHub-Event"),
+        get_header(&request, "X-Hub-Signature-256"),
+        &body,
+    );
+    trigger(&state, result)
+}
+
+/// Gets the collectors that should be triggered by a GitHub webhook.
+/// Returns `None` if the webhook could not be verified.
+fn match_github(
+    targets: &[WebhookTarget],
+    event: Option<&str>,
+    signature: Option<&str>,
+    body: &[u8],
+) -> Option<Vec<String>> {
+    let payload: GitHubPayload = serde_json::from_slice(body).ok()?;
+    let repository = payload.repository?.full_name;
+    let signature = signature?;
+
+    let collectors: Vec<String> = targets
+        .iter()
+        .filter(|t| match &t.source {
+            WebhookSource::GitHub {
+                owner,
+                repository: name,
+            } => format!("{}/{}", owner, name).eq_ignore_ascii_case(&repository),
+            _ => false,
+        })
+        .filter(|t| verify_signature(&t.secret, signature, body))
+        .map(|t| t.collector.clone())
+        .collect();
+
+    if collectors.is_empty() {
+        return None;
+    }
+
+    // Only workflow runs are interesting.
+    if event != Some("workflow_run") {
+        debug!("Ignoring GitHub event {:?}", event);
+        return Some(vec![]);
+    }
+
+    Some(collectors)
+}
+
+/// Verifies the `sha256=<hex>` signature of a GitHub webhook.
+fn verify_signature(secret: &str, signature: &str, body: &[u8]) -> bool {
+    if !signature.starts_with("sha256=") {
+        return false;
+    }
+    let signature = match hex::decode(&signature[7..]) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let mut mac = match Hmac::<Sha256>::new_varkey(secret.as_bytes()) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.input(body);
+    mac.verify(&signature).is_ok()
+}
+
+#[derive(Deserialize)]
+struct GitHubPayload {
+    repository: Option<GitHubRepository>,
+}
+
+#[derive(Deserialize)]
+struct GitHubRepository {
+    full_name: String,
+}
+
+///////////////////////////////////////////////////////////
+// Azure DevOps
+
+pub async fn azure(
+    request: HttpRequest,
+    body: Bytes,
+    state: web::Data<Arc<EngineState>>,
+) -> HttpResponse {
+    let result = match_azure(
+        &state.webhooks.get_targets(),
+        get_header(&request, "Authorization"),
+        &body,
+    );
+    trigger(&state, result)
+}
+
+/// Gets the collectors that should be triggered by an Azure DevOps service hook.
+/// Service hooks are verified using the password of the basic authentication.
+/// Returns `None` if the service hook could not be verified.
+fn match_azure(
+    targets: &[WebhookTarget],
+    authorization: Option<&str>,
+    body: &[u8],
+) -> Option<Vec<String>> {
+    let payload: AzurePayload = serde_json::from_slice(body).ok()?;
+    let organization = get_azure_organization(&payload.resource_containers.account.base_url)?;
+    let password = get_basic_auth_password(authorization?)?;
+
+    let collectors: Vec<String> = targets
+        .iter()
+        .filter(|t| match &t.source {
+            WebhookSource::AzureDevOps { organization: name } => {
+                name.eq_ignore_ascii_case(&organization)
+            }
+            _ => false,
+        })
+        .filter(|t| secret::is_match(&t.secret, &password))
+        .map(|t| t.collector.clone())
+        .collect();
+
+    if collectors.is_empty() {
+        return None;
+    }
+
+    debug!("Received Azure DevOps event '{}'", payload.event_type);
+    Some(collectors)
+}
+
+/// Gets the organization (or collection) from an Azure DevOps base URL.
+fn get_azure_organization(base_url: &str) -> Option<String> {
+    let url = Url::parse(base_url).ok()?;
+    let host = url.host_str()?;
+    if host.ends_with(".visualstudio.com") {
+        return host.split('.').next().map(|s| s.to_owned());
+    }
+    let mut segments = url.path_segments()?.filter(|s| !s.is_empty());
+    if host == "dev.azure.com" {
+        return segments.next().map(|s| s.to_owned());
+    }
+    // Azure DevOps Server: the last segment is the collection.
+    segments.last().map(|s| s.to_owned())
+}
+
+fn get_basic_auth_password(authorization: &str) -> Option<String> {
+    if !authorization.starts_with("Basic ") {
+        return None;
+    }
+    let decoded = base64::decode(&authorization[6..]).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    decoded.splitn(2, ':').nth(1).map(|p| p.to_owned())
+}
+
+#[derive(Deserialize)]
+struct AzurePayload {
+    #[serde(rename = "eventType")]
+    event_type: String,
+    #[serde(rename = "resourceContainers")]
+    resource_containers: AzureResourceContainers,
+}
+
+#[derive(Deserialize)]
+struct AzureResourceContainers {
+    #[serde(alias = "collection")]
+    account: AzureResourceContainer,
+}
+
+#[derive(Deserialize)]
+struct AzureResourceContainer {
+    #[serde(rename = "baseUrl")]
+    base_url: String,
+}
+
+///////////////////////////////////////////////////////////
+// GitLab
+
+pub async fn gitlab(
+    request: HttpRequest,
+    body: Bytes,
+    state: web::Data<Arc<EngineState>>,
+) -> HttpResponse {
+    let result = match_gitlab(
+        &state.webhooks.get_targets(),
+        get_header(&request, "X-Gitlab-Token"),
+        &body,
+    );
+    trigger(&state, result)
+}
+
+/// Gets the collectors that should be triggered by a GitLab webhook.
+/// Returns `None` if the webhook could not be verified.
+fn match_gitlab(
+    targets: &[WebhookTarget],
+    token: Option<&str>,
+    body: &[u8],
+) -> Option<Vec<String>> {
+    let payload: GitLabPayload = serde_json::from_slice(body).ok()?;
+    let token = token?;
+    let project_id = payload.project.id.to_string();
+
+    let collectors: Vec<String> = targets
+        .iter()
+        .filter(|t| match &t.source {
+            WebhookSource::GitLab { projects } => projects.iter().any(|p| {
+                *p == project_id || p.eq_ignore_ascii_case(&payload.project.path_with_namespace)
+            }),
+            _ => false,
+        })
+        .filter(|t| secret::is_match(&t.secret, token))
+        .map(|t| t.collector.clone())
+        .collect();
+
+    if collectors.is_empty() {
+        return None;
+    }
+
+    // Only pipeline events are interesting.
+    if payload.object_kind != "pipeline" {
+        debug!("Ignoring GitLab event '{}'", payload.object_kind);
+        return Some(vec![]);
+    }
+
+    Some(collectors)
+}
+
+#[derive(Deserialize)]
+struct GitLabPayload {
+    object_kind: String,
+    project: GitLabProject,
+}
+
+#[derive(Deserialize)]
+struct GitLabProject {
+    id: u64,
+    path_with_namespace: String,
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+
+fn get_header<'a>(request: &'a HttpRequest, name: &str) -> Option<&'a str> {
+    request.headers().get(name).and_then(|h| h.to_str().ok())
+}
+
+fn trigger(state: &EngineState, collectors: Option<Vec<String>>) -> HttpResponse {
+    let collectors = match collectors {
+        Some(collectors) => collectors,
+        None => return HttpResponse::Unauthorized().finish(),
+    };
+
+    let result = WebhookResultModel {
+        triggered: collectors
+            .into_iter()
+            .filter(|c| state.triggers.trigger(c))
+            .collect(),
+    };
+
+    // Serialize to JSON and return.
+    let json = serde_json::to_string(&result).unwrap();
+    HttpResponse::Accepted()
+        .content_type("application/json")
+        .body(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn create_targets() -> Vec<WebhookTarget> {
+        vec![
+            WebhookTarget {
+                collector: "github".to_owned(),
+                secret: "SECRET".to_owned(),
+                source: WebhookSource::GitHub {
+                    owner: "duckhq".to_owned(),
+                    repository: "duck".to_owned(),
+                },
+            },
+            WebhookTarget {
+                collector: "azure".to_owned(),
+                secret: "SECRET".to_owned(),
+                source: WebhookSource::AzureDevOps {
+                    organization: "cake-build".to_owned(),
+                },
+            },
+            WebhookTarget {
+                collector: "gitlab".to_owned(),
+                secret: "SECRET".to_owned(),
+                source: WebhookSource::GitLab {
+                    projects: vec!["duckhq/duck".to_owned()],
+                },
+            },
+        ]
+    }
+
+    fn sign(secret: &str, body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_varkey(secret.as_bytes()).unwrap();
+        mac.input(body.as_bytes());
+        format!("sha256={}", hex::encode(mac.result().code()))
+    }
+
+    static GITHUB_PAYLOAD: &str =
+        r#"{ "action": "completed", "repository": { "full_name": "DuckHQ/duck" } }"#;
+    static AZURE_PAYLOAD: &str = r#"{
+        "eventType": "build.complete",
+        "resourceContainers": { "account": { "baseUrl": "https://dev.azure.com/cake-build/" } }
+    }"#;
+    static GITLAB_PAYLOAD: &str = r#"{
+        "object_kind": "pipeline",
+        "project": { "id": 123, "path_with_namespace": "duckhq/duck" }
+    }"#;
+
+    #[test]
+    fn should_trigger_github_collector_for_verified_workflow_run() {
+        // Given
+        let signature = sign("SECRET", GITHUB_PAYLOAD);
+        // When
+        let result = match_github(
+            &create_targets(),
+            Some("workflow_run"),
+            Some(&signature),
+            GITHUB_PAYLOAD.as_bytes(),
+        );
+        // Then
+        assert_eq!(Some(vec!["github".to_owned()]), result);
+    }
+
+    #[test]
+    fn should_not_trigger_github_collector_for_invalid_signature() {
+        // Given
+        let signature = sign("OTHER", GITHUB_PAYLOAD);
+        // When
+        let result = match_github(
+            &create_targets(),
+            Some("workflow_run"),
+            Some(&signature),
+            GITHUB_PAYLOAD.as_bytes(),
+        );
+        // Then
+        assert_eq!(None, result);
+    }
+
+    #[test]
+    fn should_not_trigger_github_collector_for_other_events() {
+        // Given
+        let signature = sign("SECRET", GITHUB_PAYLOAD);
+        // When
+        let result = match_github(
+            &create_targets(),
+            Some("ping"),
+            Some(&signature),
+            GITHUB_PAYLOAD.as_bytes(),
+        );
+        // Then
+        assert_eq!(Some(vec![]), result);
+    }
+
+    #[test]
+    fn should_trigger_azure_collector_for_verified_service_hook() {
+        // Given
+        let authorization = format!("Basic {}", base64::encode("duck:SECRET"));
+        // When
+        let result = match_azure(
+            &create_targets(),
+            Some(&authorization),
+            AZURE_PAYLOAD.as_bytes(),
+        );
+        // Then
+        assert_eq!(Some(vec!["azure".to_owned()]), result);
+    }
+
+    #[test]
+    fn should_not_trigger_azure_collector_for_invalid_password() {
+        // Given
+        let authorization = format!("Basic {}", base64::encode("duck:OTHER"));
+        // When
+        let result = match_azure(
+            &create_targets(),
+            Some(&authorization),
+            AZURE_PAYLOAD.as_bytes(),
+        );
+        // Then
+        assert_eq!(None, result);
+    }
+
+    #[test]
+    fn should_trigger_gitlab_collector_for_verified_pipeline_event() {
+        // Given, When
+        let result = match_gitlab(&create_targets(), Some("SECRET"), GITLAB_PAYLOAD.as_bytes());
+        // Then
+        assert_eq!(Some(vec!["gitlab".to_owned()]), result);
+    }
+
+    #[test]
+    fn should_not_trigger_gitlab_collector_for_invalid_token() {
+        // Given, When
+        let result = match_gitlab(&create_targets(), Some("OTHER"), GITLAB_PAYLOAD.as_bytes());
+        // Then
+        assert_eq!(None, result);
+    }
+
+    #[test_case("https://dev.azure.com/cake-build/", "cake-build" ; "Azure DevOps Services")]
+    #[test_case("https://cake-build.visualstudio.com/", "cake-build" ; "Visual Studio Team Services")]
+    #[test_case("https://tfs.example.com/tfs/DefaultCollection/", "DefaultCollection" ; "Azure DevOps Server")]
+    fn should_get_organization_from_azure_base_url(base_url: &str, expected: &str) {
+        // Given, When
+        let organization = get_azure_organization(base_url);
+        // Then
+        assert_eq!(Some(expected.to_owned()), organization);
+    }
+}
@@ -127,7 +127,7 @@ impl BuildBuilder {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BuildStatus {
     Unknown,
     Success,
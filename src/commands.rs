@@ -1,3 +1,4 @@
+pub mod query;
 pub mod schema;
 pub mod start;
 pub mod validate;
@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+
+use duck::{DuckResult, QuerySource};
+use structopt::StructOpt;
+
+use crate::commands::{DEFAULT_CONFIG, ENV_CONFIG};
+
+///////////////////////////////////////////////////////////
+// Arguments
+
+#[derive(StructOpt, Debug)]
+pub struct Arguments {
+    /// The filter expression to evaluate
+    pub expression: String,
+    /// The configuration file whose collectors should be used to get builds
+    #[structopt(
+        short,
+        long,
+        parse(from_os_str),
+        default_value = DEFAULT_CONFIG,
+        env = ENV_CONFIG
+    )]
+    pub config: PathBuf,
+    /// A JSON file with builds, such as the response from the builds API.
+    /// Used instead of the configuration file if specified.
+    #[structopt(short, long, parse(from_os_str))]
+    pub builds: Option<PathBuf>,
+    /// Only show builds that match the expression
+    #[structopt(short, long)]
+    pub matching: bool,
+}
+
+impl Arguments {
+    fn get_source(&self) -> QuerySource {
+        match &self.builds {
+            Some(builds) => QuerySource::File(builds.clone()),
+            None => QuerySource::Configuration(self.config.clone()),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Command
+
+pub fn execute(args: Arguments) -> DuckResult<()> {
+    let results = duck::query(&args.expression[..], args.get_source())?;
+
+    let matches = results.iter().filter(|r| r.matched).count();
+    for result in results.iter().filter(|r| r.matched || !args.matching) {
+        println!(
+            "{} {}",
+            if result.matched {
+                "MATCH   "
+            } else {
+                "NO MATCH"
+            },
+            result.build
+        );
+        for reason in result.reasons.iter() {
+            println!("         {}", reason);
+        }
+    }
+
+    println!();
+    println!("{} of {} builds matched", matches, results.len());
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn should_use_default_configuration_file_if_no_source_is_specified() {
+        // Given
+        let args = Arguments::from_iter(&["query", "branch == 'master'"]);
+        // When
+        let source = args.get_source();
+        // Then
+        match source {
+            QuerySource::Configuration(path) => {
+                assert_eq!(DEFAULT_CONFIG, path.to_str().unwrap())
+            }
+            QuerySource::File(_) => panic!("Expected configuration"),
+        }
+    }
+
+    #[test]
+    pub fn should_use_builds_file_if_specified() {
+        // Given
+        let args = Arguments::from_iter(&["query", "true", "--builds", "builds.json"]);
+        // When
+        let source = args.get_source();
+        // Then
+        match source {
+            QuerySource::File(path) => assert_eq!("builds.json", path.to_str().unwrap()),
+            QuerySource::Configuration(_) => panic!("Expected builds file"),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 
@@ -24,7 +26,8 @@ pub trait ConfigurationLoader: Sync + Send + Clone {
 #[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
 pub struct Configuration {
     /// # Update interval
-    /// The update interval in seconds
+    /// The update interval in seconds. Must be at least 1.
+    /// Collectors can override this with their own `interval`.
     #[serde(default = "default_interval")]
     pub interval: u16,
     /// # Views
@@ -38,6 +41,10 @@ pub struct Configuration {
     /// # Observers
     #[serde(default)]
     pub observers: Option<Vec<ObserverConfiguration>>,
+    /// # Storage
+    /// Where the build history is stored
+    #[serde(default)]
+    pub storage: Option<StorageConfiguration>,
 }
 
 impl Configuration {
@@ -98,8 +105,14 @@ pub struct ViewConfiguration {
     /// the name of the view
     pub name: String,
     /// # Included collectors
-    /// The collectors included in this view
-    pub collectors: Vec<String>,
+    /// The collectors included in this view.
+    /// If omitted, builds from all collectors are included.
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # An optional filter expression
+    /// Only builds matching the filter are included in the view.
+    #[serde(default)]
+    pub filter: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
@@ -108,6 +121,10 @@ pub enum CollectorConfiguration {
     /// Gets builds from TeamCity
     #[serde(rename = "teamcity")]
     TeamCity(TeamCityConfiguration),
+    /// # Jenkins collector
+    /// Gets builds from Jenkins
+    #[serde(rename = "jenkins")]
+    Jenkins(JenkinsConfiguration),
     /// # Azure DevOps collector
     /// Gets builds from Azure DevOps
     #[serde(rename = "azure")]
@@ -116,6 +133,18 @@ pub enum CollectorConfiguration {
     /// Gets builds from GitHub Actions
     #[serde(rename = "github")]
     GitHub(GitHubConfiguration),
+    /// # Bitbucket collector
+    /// Gets pipelines from Bitbucket Cloud or builds from Bitbucket Server
+    #[serde(rename = "bitbucket")]
+    Bitbucket(BitbucketConfiguration),
+    /// # CircleCI collector
+    /// Gets workflows from CircleCI
+    #[serde(rename = "circleci")]
+    CircleCi(CircleCiConfiguration),
+    /// # GitLab collector
+    /// Gets pipelines from GitLab CI
+    #[serde(rename = "gitlab")]
+    GitLab(GitLabConfiguration),
     /// # Octopus Deploy collector
     /// Gets deployments from Octopus Deploy
     #[serde(rename = "octopus")]
@@ -128,6 +157,10 @@ pub enum CollectorConfiguration {
     /// Gets builds from another Duck instance
     #[serde(rename = "duck")]
     Duck(DuckConfiguration),
+    /// # Push collector
+    /// Receives builds that are pushed to Duck's ingest endpoint
+    #[serde(rename = "push")]
+    Push(PushConfiguration),
     /// # Debug collector
     /// Gets builds from a Duck debugger instance
     #[serde(rename = "debugger")]
@@ -138,23 +171,50 @@ impl CollectorConfiguration {
     pub fn get_id(&self) -> &str {
         match self {
             CollectorConfiguration::TeamCity(c) => &c.id,
+            CollectorConfiguration::Jenkins(c) => &c.id,
             CollectorConfiguration::Azure(c) => &c.id,
             CollectorConfiguration::GitHub(c) => &c.id,
+            CollectorConfiguration::Bitbucket(c) => &c.id,
+            CollectorConfiguration::CircleCi(c) => &c.id,
+            CollectorConfiguration::GitLab(c) => &c.id,
             CollectorConfiguration::OctopusDeploy(c) => &c.id,
             CollectorConfiguration::AppVeyor(c) => &c.id,
             CollectorConfiguration::Duck(c) => &c.id,
+            CollectorConfiguration::Push(c) => &c.id,
             CollectorConfiguration::Debugger(c) => &c.id,
         }
     }
 
+    pub fn get_interval(&self) -> Option<u16> {
+        match self {
+            CollectorConfiguration::TeamCity(c) => c.interval,
+            CollectorConfiguration::Jenkins(c) => c.interval,
+            CollectorConfiguration::Azure(c) => c.interval,
+            CollectorConfiguration::GitHub(c) => c.interval,
+            CollectorConfiguration::Bitbucket(c) => c.interval,
+            CollectorConfiguration::CircleCi(c) => c.interval,
+            CollectorConfiguration::GitLab(c) => c.interval,
+            CollectorConfiguration::OctopusDeploy(c) => c.interval,
+            CollectorConfiguration::AppVeyor(c) => c.interval,
+            CollectorConfiguration::Duck(c) => c.interval,
+            CollectorConfiguration::Push(c) => c.interval,
+            CollectorConfiguration::Debugger(c) => c.interval,
+        }
+    }
+
     pub fn is_enabled(&self) -> bool {
         if let Some(enabled) = match self {
             CollectorConfiguration::TeamCity(c) => c.enabled,
+            CollectorConfiguration::Jenkins(c) => c.enabled,
             CollectorConfiguration::Azure(c) => c.enabled,
             CollectorConfiguration::GitHub(c) => c.enabled,
+            CollectorConfiguration::Bitbucket(c) => c.enabled,
+            CollectorConfiguration::CircleCi(c) => c.enabled,
+            CollectorConfiguration::GitLab(c) => c.enabled,
             CollectorConfiguration::OctopusDeploy(c) => c.enabled,
             CollectorConfiguration::AppVeyor(c) => c.enabled,
             CollectorConfiguration::Duck(c) => c.enabled,
+            CollectorConfiguration::Push(c) => c.enabled,
             CollectorConfiguration::Debugger(c) => c.enabled,
         } {
             return enabled;
@@ -167,11 +227,16 @@ impl Validate for CollectorConfiguration {
     fn validate(&self) -> DuckResult<()> {
         match self {
             CollectorConfiguration::TeamCity(c) => c.validate(),
+            CollectorConfiguration::Jenkins(c) => c.validate(),
             CollectorConfiguration::Azure(c) => c.validate(),
             CollectorConfiguration::GitHub(c) => c.validate(),
+            CollectorConfiguration::Bitbucket(c) => c.validate(),
+            CollectorConfiguration::CircleCi(c) => c.validate(),
+            CollectorConfiguration::GitLab(c) => c.validate(),
             CollectorConfiguration::OctopusDeploy(c) => c.validate(),
             CollectorConfiguration::AppVeyor(c) => c.validate(),
             CollectorConfiguration::Duck(c) => c.validate(),
+            CollectorConfiguration::Push(c) => c.validate(),
             CollectorConfiguration::Debugger(c) => c.validate(),
         }
     }
@@ -188,6 +253,12 @@ pub enum ObserverConfiguration {
     /// # Mattermost observer
     #[serde(rename = "mattermost")]
     Mattermost(MattermostConfiguration),
+    /// # Microsoft Teams observer
+    #[serde(rename = "teams")]
+    Teams(TeamsConfiguration),
+    /// # Webhook observer
+    #[serde(rename = "webhook")]
+    Webhook(WebhookConfiguration),
 }
 
 impl ObserverConfiguration {
@@ -196,6 +267,8 @@ impl ObserverConfiguration {
             ObserverConfiguration::Hue(c) => &c.id,
             ObserverConfiguration::Slack(c) => &c.id,
             ObserverConfiguration::Mattermost(c) => &c.id,
+            ObserverConfiguration::Teams(c) => &c.id,
+            ObserverConfiguration::Webhook(c) => &c.id,
         }
     }
 
@@ -204,17 +277,31 @@ impl ObserverConfiguration {
             ObserverConfiguration::Hue(c) => c.enabled,
             ObserverConfiguration::Slack(c) => c.enabled,
             ObserverConfiguration::Mattermost(c) => c.enabled,
+            ObserverConfiguration::Teams(c) => c.enabled,
+            ObserverConfiguration::Webhook(c) => c.enabled,
         } {
             return enabled;
         }
         return true;
     }
 
+    pub fn get_filter(&self) -> Option<String> {
+        match self {
+            ObserverConfiguration::Hue(c) => c.filter.clone(),
+            ObserverConfiguration::Slack(c) => c.filter.clone(),
+            ObserverConfiguration::Mattermost(c) => c.filter.clone(),
+            ObserverConfiguration::Teams(c) => c.filter.clone(),
+            ObserverConfiguration::Webhook(c) => c.filter.clone(),
+        }
+    }
+
     pub fn get_collector_references(&self) -> Option<Vec<String>> {
         match self {
             ObserverConfiguration::Hue(c) => c.collectors.clone(),
             ObserverConfiguration::Slack(c) => c.collectors.clone(),
             ObserverConfiguration::Mattermost(c) => c.collectors.clone(),
+            ObserverConfiguration::Teams(c) => c.collectors.clone(),
+            ObserverConfiguration::Webhook(c) => c.collectors.clone(),
         }
     }
 }
@@ -225,6 +312,8 @@ impl Validate for ObserverConfiguration {
             ObserverConfiguration::Hue(c) => c.validate(),
             ObserverConfiguration::Slack(c) => c.validate(),
             ObserverConfiguration::Mattermost(c) => c.validate(),
+            ObserverConfiguration::Teams(c) => c.validate(),
+            ObserverConfiguration::Webhook(c) => c.validate(),
         }
     }
 }
@@ -239,12 +328,23 @@ pub struct AppVeyorConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
     /// # The TeamCity credentials
     pub credentials: AppVeyorCredentials,
     /// # The AppVeyor account
     pub account: String,
     /// # The AppVeyor project
-    pub project: String,
+    /// If not specified, all projects in the account will be collected.
+    #[serde(default)]
+    pub project: Option<String>,
+    /// # The branches to include
+    /// If specified, the latest build for each branch will be collected
+    /// instead of the build history.
+    #[serde(default)]
+    pub branches: Option<Vec<String>>,
     /// # The number of builds to retrieve
     #[serde(default)]
     pub count: Option<u16>,
@@ -275,6 +375,10 @@ pub struct TeamCityConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
     /// # The TeamCity server URL
     #[serde(rename = "serverUrl")]
     pub server_url: String,
@@ -282,6 +386,10 @@ pub struct TeamCityConfiguration {
     pub credentials: TeamCityAuth,
     /// # The TeamCity builds definitions to include
     pub builds: Vec<String>,
+    /// # The branches to include per build definition
+    /// Build definitions without an entry will include all branches.
+    #[serde(default)]
+    pub branches: Option<HashMap<String, Vec<String>>>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
@@ -299,6 +407,52 @@ pub enum TeamCityAuth {
         /// # The password to use
         password: String,
     },
+    /// # Access token
+    /// Authenticate using an access token
+    #[serde(rename = "token")]
+    Token(String),
+}
+
+///////////////////////////////////////////////////////////
+// Jenkins
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct JenkinsConfiguration {
+    /// # The Jenkins collector ID
+    pub id: String,
+    /// # Determines whether or not this collector is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
+    /// # The Jenkins server URL
+    #[serde(rename = "serverUrl")]
+    pub server_url: String,
+    /// # The Jenkins credentials
+    pub credentials: JenkinsCredentials,
+    /// # The Jenkins jobs to include
+    /// Jobs inside folders are separated by a forward slash (e.g. folder/job).
+    /// Folders and multibranch jobs will include all of their jobs.
+    pub jobs: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub enum JenkinsCredentials {
+    /// # Anonymous
+    /// Use anonymous authentication
+    #[serde(rename = "anonymous")]
+    Anonymous,
+    /// # Basic authentication
+    /// Authenticate using a username and an API token
+    #[serde(rename = "basic")]
+    Basic {
+        /// # The username to use
+        username: String,
+        /// # The API token to use
+        token: String,
+    },
 }
 
 ///////////////////////////////////////////////////////////
@@ -311,6 +465,10 @@ pub struct AzureDevOpsConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
     /// # The Azure DevOps server URL.
     /// Only required if Duck should collect builds
     /// from a self-hosted instance of Azure DevOps Server.
@@ -323,9 +481,26 @@ pub struct AzureDevOpsConfiguration {
     /// # The Azure DevOps credentials
     pub credentials: AzureDevOpsCredentials,
     /// # The branches to include
+    /// Required if any build definitions have been specified.
+    #[serde(default)]
     pub branches: Vec<String>,
     /// # The build definitions to include
+    #[serde(default)]
     pub definitions: Vec<String>,
+    /// # The classic release definitions to include
+    /// Every stage of a release definition will be collected.
+    #[serde(default)]
+    pub releases: Option<Vec<String>>,
+    /// # The YAML environments to include
+    /// Environments can be specified by either ID or name.
+    #[serde(default)]
+    pub environments: Option<Vec<String>>,
+    /// # The webhook secret
+    /// If specified, Azure DevOps service hooks that authenticate using
+    /// basic authentication with this password trigger an immediate update.
+    #[serde(rename = "webhookSecret")]
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
@@ -350,6 +525,16 @@ pub struct GitHubConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
+    /// # The GitHub API URL
+    /// Only required if Duck should collect builds from GitHub Enterprise Server,
+    /// in which case it is usually https://[hostname]/api/v3.
+    #[serde(rename = "apiUrl")]
+    #[serde(default)]
+    pub api_url: Option<String>,
     /// # The GitHub owner
     pub owner: String,
     /// # The GitHub repository
@@ -357,7 +542,47 @@ pub struct GitHubConfiguration {
     /// # The GitHub credentials
     pub credentials: GitHubCredentials,
     /// # The GitHub Actions workflow
-    pub workflow: String,
+    /// Use `workflows` to collect builds from more than one workflow.
+    #[serde(default)]
+    pub workflow: Option<String>,
+    /// # The GitHub Actions workflows
+    /// If neither this nor `workflow` is specified,
+    /// builds from all workflows will be collected.
+    #[serde(default)]
+    pub workflows: Option<Vec<String>>,
+    /// # The branches to include
+    /// If not specified, builds from all branches will be collected.
+    #[serde(default)]
+    pub branches: Option<Vec<String>>,
+    /// # The branches to exclude
+    #[serde(rename = "excludedBranches")]
+    #[serde(default)]
+    pub excluded_branches: Option<Vec<String>>,
+    /// # The webhook secret
+    /// If specified, signed GitHub workflow_run webhooks
+    /// trigger an immediate update.
+    #[serde(rename = "webhookSecret")]
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+}
+
+impl GitHubConfiguration {
+    /// Gets all configured workflows.
+    /// An empty result means that all workflows should be collected.
+    pub fn get_workflows(&self) -> Vec<String> {
+        let mut result = Vec::new();
+        if let Some(workflow) = &self.workflow {
+            result.push(workflow.clone());
+        }
+        if let Some(workflows) = &self.workflows {
+            for workflow in workflows.iter() {
+                if !result.contains(workflow) {
+                    result.push(workflow.clone());
+                }
+            }
+        }
+        result
+    }
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
@@ -371,6 +596,155 @@ pub enum GitHubCredentials {
         /// # The password to use
         password: String,
     },
+    /// # Token
+    /// Authenticate using a personal access token
+    #[serde(rename = "token")]
+    Token(String),
+    /// # GitHub App
+    /// Authenticate as a GitHub App installation
+    #[serde(rename = "app")]
+    App {
+        /// # The GitHub App ID
+        #[serde(rename = "appId")]
+        app_id: String,
+        /// # The GitHub App installation ID
+        #[serde(rename = "installationId")]
+        installation_id: String,
+        /// # The path to the GitHub App private key
+        /// The private key must be PEM encoded.
+        #[serde(rename = "privateKeyPath")]
+        private_key_path: String,
+    },
+}
+
+///////////////////////////////////////////////////////////
+// Bitbucket
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct BitbucketConfiguration {
+    /// # The Bitbucket collector ID
+    pub id: String,
+    /// # Determines whether or not this collector is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
+    /// # The Bitbucket Server URL
+    /// Only required if Duck should collect builds from Bitbucket Server.
+    /// If omitted, pipelines are collected from Bitbucket Cloud.
+    #[serde(rename = "serverUrl", default)]
+    pub server_url: Option<String>,
+    /// # The Bitbucket credentials
+    pub credentials: BitbucketCredentials,
+    /// # The repositories to include
+    /// Either workspace/repository (Bitbucket Cloud) or project/repository (Bitbucket Server)
+    pub repositories: Vec<String>,
+    /// # The branches to include
+    pub branches: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub enum BitbucketCredentials {
+    /// # Anonymous
+    /// Use anonymous authentication
+    #[serde(rename = "anonymous")]
+    Anonymous,
+    /// # App password
+    /// Authenticate using a username and an app password
+    #[serde(rename = "appPassword")]
+    AppPassword {
+        /// # The username to use
+        username: String,
+        /// # The app password to use
+        password: String,
+    },
+    /// # Bearer token
+    /// Authenticate using an OAuth bearer token or a personal access token
+    #[serde(rename = "bearer")]
+    Bearer(String),
+}
+
+///////////////////////////////////////////////////////////
+// CircleCI
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CircleCiConfiguration {
+    /// # The CircleCI collector ID
+    pub id: String,
+    /// # Determines whether or not this collector is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
+    /// # The CircleCI credentials
+    pub credentials: CircleCiCredentials,
+    /// # The CircleCI project slug
+    /// In the format vcs-type/organization/repository (e.g. gh/duckhq/duck)
+    pub project: String,
+    /// # The branches to include
+    pub branches: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub enum CircleCiCredentials {
+    /// # Anonymous
+    /// Use anonymous authentication (public projects only)
+    #[serde(rename = "anonymous")]
+    Anonymous,
+    /// # API token
+    /// Authenticate using a personal API token
+    #[serde(rename = "token")]
+    Token(String),
+}
+
+///////////////////////////////////////////////////////////
+// GitLab CI
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct GitLabConfiguration {
+    /// # The GitLab collector ID
+    pub id: String,
+    /// # Determines whether or not this collector is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
+    /// # The GitLab server URL.
+    /// Only required if Duck should collect pipelines
+    /// from a self-hosted instance of GitLab.
+    #[serde(rename = "serverUrl")]
+    pub server_url: Option<String>,
+    /// # The GitLab credentials
+    pub credentials: GitLabCredentials,
+    /// # The GitLab projects to include
+    /// Either a numeric project ID or a full project path (namespace/project)
+    pub projects: Vec<String>,
+    /// # The branches to include
+    pub branches: Vec<String>,
+    /// # The webhook secret
+    /// If specified, GitLab pipeline events with this
+    /// secret token trigger an immediate update.
+    #[serde(rename = "webhookSecret")]
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub enum GitLabCredentials {
+    /// # Anonymous
+    /// Use anonymous authentication
+    #[serde(rename = "anonymous")]
+    Anonymous,
+    /// # Access token
+    /// Authenticate using a personal or project access token
+    #[serde(rename = "token")]
+    AccessToken(String),
 }
 
 ///////////////////////////////////////////////////////////
@@ -383,6 +757,10 @@ pub struct OctopusDeployConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
     /// # The Octopus Deploy server URL
     #[serde(rename = "serverUrl")]
     pub server_url: String,
@@ -394,11 +772,23 @@ pub struct OctopusDeployConfiguration {
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub struct OctopusDeployProject {
-    /// # The Octopus Deploy project ID
-    #[serde(rename = "projectId")]
+    /// # The Octopus Deploy project
+    /// The project can be specified by ID, name or slug.
+    #[serde(rename = "projectId", alias = "project")]
     pub project_id: String,
-    /// # The Octopus Deploy environment IDs within the project
+    /// # The Octopus Deploy environments within the project
+    /// Environments can be specified by ID or name.
     pub environments: Vec<String>,
+    /// # The Octopus Deploy tenants to include
+    /// Tenants can be specified by ID or name.
+    /// If not specified, deployments for all tenants will be collected.
+    #[serde(default)]
+    pub tenants: Option<Vec<String>>,
+    /// # The Octopus Deploy channels to include
+    /// Channels can be specified by ID or name.
+    /// If not specified, deployments for all channels will be collected.
+    #[serde(default)]
+    pub channels: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
@@ -419,6 +809,10 @@ pub struct DebuggerConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
     /// # The Duck debugger URL
     #[serde(rename = "serverUrl")]
     pub server_url: String,
@@ -434,6 +828,10 @@ pub struct DuckConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Update interval
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
     /// # The Duck server URL
     #[serde(rename = "serverUrl")]
     pub server_url: String,
@@ -442,6 +840,39 @@ pub struct DuckConfiguration {
     pub view: Option<String>,
 }
 
+///////////////////////////////////////////////////////////
+// Push
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct PushConfiguration {
+    /// # The push collector ID
+    pub id: String,
+    /// # Determines whether or not this collector is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # Update interval
+    /// The interval in seconds that pushed builds are processed.
+    /// Overrides the global update interval.
+    #[serde(default)]
+    pub interval: Option<u16>,
+    /// # The token that clients must provide when pushing builds
+    pub token: String,
+    /// # The expiry in seconds for pushed builds
+    /// Builds that have not been updated within this time are removed.
+    /// Defaults to 24 hours.
+    #[serde(default)]
+    pub expiry: Option<u32>,
+}
+
+impl PushConfiguration {
+    pub fn get_expiry(&self) -> u32 {
+        match self.expiry {
+            None => 86400,
+            Some(expiry) => std::cmp::max(1, expiry),
+        }
+    }
+}
+
 ///////////////////////////////////////////////////////////
 // Hue
 
@@ -487,6 +918,14 @@ pub struct SlackConfiguration {
     /// # The Slack channel to send messages to
     #[serde(default)]
     pub channel: Option<String>,
+    /// # The message template
+    /// Placeholders such as {{project}}, {{definition}}, {{branch}}, {{status}},
+    /// {{previousStatus}}, {{duration}} and {{icon}} are replaced with build values
+    #[serde(default)]
+    pub template: Option<String>,
+    /// # Icons to use for different build statuses
+    #[serde(default)]
+    pub icons: Option<StatusIcons>,
     /// # An optional filter expression
     pub filter: Option<String>,
 }
@@ -516,6 +955,14 @@ pub struct MattermostConfiguration {
     pub channel: Option<String>,
     /// # The Mattermost credentials
     pub credentials: MattermostCredentials,
+    /// # The message template
+    /// Placeholders such as {{project}}, {{definition}}, {{branch}}, {{status}},
+    /// {{previousStatus}}, {{duration}} and {{icon}} are replaced with build values
+    #[serde(default)]
+    pub template: Option<String>,
+    /// # Icons to use for different build statuses
+    #[serde(default)]
+    pub icons: Option<StatusIcons>,
     /// # An optional filter expression
     pub filter: Option<String>,
 }
@@ -528,6 +975,123 @@ pub enum MattermostCredentials {
     Webhook { url: String },
 }
 
+///////////////////////////////////////////////////////////
+// Status icons
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct StatusIcons {
+    /// # The icon for successful builds
+    #[serde(default)]
+    pub success: Option<String>,
+    /// # The icon for failed builds
+    #[serde(default)]
+    pub failed: Option<String>,
+    /// # The icon for running builds
+    #[serde(default)]
+    pub running: Option<String>,
+    /// # The icon for canceled builds
+    #[serde(default)]
+    pub canceled: Option<String>,
+    /// # The icon for queued builds
+    #[serde(default)]
+    pub queued: Option<String>,
+    /// # The icon for skipped builds
+    #[serde(default)]
+    pub skipped: Option<String>,
+}
+
+///////////////////////////////////////////////////////////
+// Microsoft Teams
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct TeamsConfiguration {
+    /// # The Microsoft Teams observer ID
+    pub id: String,
+    /// # Determines whether or not this observer is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # The collectors to include events from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The Microsoft Teams credentials
+    pub credentials: TeamsCredentials,
+    /// # An optional filter expression
+    pub filter: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub enum TeamsCredentials {
+    /// # Webhook
+    /// Send messages directly to an incoming webhook
+    #[serde(rename = "webhook")]
+    Webhook { url: String },
+}
+
+///////////////////////////////////////////////////////////
+// Webhook
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct WebhookConfiguration {
+    /// # The webhook observer ID
+    pub id: String,
+    /// # Determines whether or not this observer is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # The collectors to include events from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The URL to send events to
+    pub url: String,
+    /// # Additional HTTP headers to send
+    #[serde(default)]
+    pub headers: Option<HashMap<String, String>>,
+    /// # The secret used to sign the payload
+    /// The HMAC-SHA256 signature of the payload is sent in the X-Duck-Signature header
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// # The JSON payload to send
+    /// Placeholders such as {{event}}, {{status}}, {{project}} and {{branch}} are
+    /// replaced in all string values. If omitted, a default payload is sent.
+    #[serde(default)]
+    pub payload: Option<serde_json::Value>,
+    /// # An optional filter expression
+    pub filter: Option<String>,
+}
+
+///////////////////////////////////////////////////////////
+// Storage
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub enum StorageConfiguration {
+    /// # File storage
+    /// Stores the build history in an append-only file
+    #[serde(rename = "file")]
+    File {
+        /// # The path to the history file
+        path: String,
+        /// # Retention
+        /// The number of builds to keep per build definition.
+        /// Defaults to 100.
+        #[serde(default)]
+        retention: Option<u16>,
+    },
+}
+
+impl StorageConfiguration {
+    pub fn get_retention(&self) -> usize {
+        match self {
+            StorageConfiguration::File { retention, .. } => match retention {
+                None => DEFAULT_HISTORY_RETENTION,
+                Some(retention) => std::cmp::max(1, *retention as usize),
+            },
+        }
+    }
+}
+
+/// The number of builds per build definition
+/// that are kept in the history by default.
+pub const DEFAULT_HISTORY_RETENTION: usize = 100;
+
 ///////////////////////////////////////////////////////////
 // Default values
 
@@ -22,6 +22,19 @@ mod tests {
                     "builds": [ "${TEAMCITY_BUILD}_1", "${TEAMCITY_BUILD}_2" ]
                 }
             },
+            {
+                "jenkins": {
+                    "id": "${JENKINS_ID}",
+                    "serverUrl": "https://${JENKINS_HOST}",
+                    "credentials": {
+                        "basic": {
+                            "username": "${JENKINS_USERNAME}",
+                            "token": "${JENKINS_TOKEN}"
+                        }
+                    },
+                    "jobs": [ "${JENKINS_FOLDER}/${JENKINS_JOB}" ]
+                }
+            },
             {
                 "azure": {
                     "id": "${AZURE_ID}",
@@ -37,9 +50,11 @@ mod tests {
             {
                 "github": {
                     "id": "${GITHUB_ID}",
+                    "apiUrl": "https://${GITHUB_HOST}/api/v3",
                     "owner": "${GITHUB_OWNER}",
                     "repository": "${GITHUB_REPOSITORY}",
                     "workflow": "${GITHUB_WORKFLOW}",
+                    "branches": [ "${GITHUB_BRANCH}" ],
                     "credentials": {
                         "basic": {
                             "username": "${GITHUB_USERNAME}",
@@ -48,6 +63,41 @@ mod tests {
                     }
                 }
             },
+            {
+                "bitbucket": {
+                    "id": "${BITBUCKET_ID}",
+                    "serverUrl": "https://${BITBUCKET_HOST}",
+                    "credentials": {
+                        "appPassword": {
+                            "username": "${BITBUCKET_USERNAME}",
+                            "password": "${BITBUCKET_PASSWORD}"
+                        }
+                    },
+                    "repositories": [ "${BITBUCKET_REPOSITORY}" ],
+                    "branches": [ "${BITBUCKET_BRANCH}" ]
+                }
+            },
+            {
+                "circleci": {
+                    "id": "${CIRCLECI_ID}",
+                    "credentials": {
+                        "token": "${CIRCLECI_TOKEN}"
+                    },
+                    "project": "${CIRCLECI_PROJECT}",
+                    "branches": [ "${CIRCLECI_BRANCH}" ]
+                }
+            },
+            {
+                "gitlab": {
+                    "id": "${GITLAB_ID}",
+                    "serverUrl": "https://${GITLAB_HOST}",
+                    "credentials": {
+                        "token": "${GITLAB_TOKEN}"
+                    },
+                    "projects": [ "${GITLAB_PROJECT}" ],
+                    "branches": [ "${GITLAB_BRANCH}" ]
+                }
+            },
             {
                 "octopus": {
                     "id": "${OCTOPUS_ID}",
@@ -117,6 +167,26 @@ mod tests {
                         }
                     }
                 }
+            },
+            {
+                "teams": {
+                    "id": "${TEAMS_ID}",
+                    "credentials": {
+                        "webhook": {
+                            "url": "${TEAMS_WEBHOOK_URL}"
+                        }
+                    }
+                }
+            },
+            {
+                "webhook": {
+                    "id": "${WEBHOOK_ID}",
+                    "url": "${WEBHOOK_URL}",
+                    "headers": {
+                        "Authorization": "Bearer ${WEBHOOK_TOKEN}"
+                    },
+                    "secret": "${WEBHOOK_SECRET}"
+                }
             }
         ]
     }
@@ -147,6 +217,12 @@ mod tests {
         variables.add("TEAMCITY_BUILD", "MYBUILD");
         variables.add("TEAMCITY_USERNAME", "patrik");
         variables.add("TEAMCITY_PASSWORD", "hunter1!");
+        variables.add("JENKINS_ID", "jenkins");
+        variables.add("JENKINS_HOST", "jenkins.example.com");
+        variables.add("JENKINS_USERNAME", "patrik");
+        variables.add("JENKINS_TOKEN", "SECRET-JENKINS-TOKEN");
+        variables.add("JENKINS_FOLDER", "MyFolder");
+        variables.add("JENKINS_JOB", "MyJob");
         variables.add("AZURE_ID", "azure");
         variables.add("AZURE_ORG", "MyOrganization");
         variables.add("AZURE_PROJECT", "MyProject");
@@ -154,11 +230,28 @@ mod tests {
         variables.add("AZURE_BRANCH", "MyBranch");
         variables.add("AZURE_DEF", "MyDefinition");
         variables.add("GITHUB_ID", "github");
+        variables.add("GITHUB_HOST", "github.example.com");
         variables.add("GITHUB_OWNER", "spectresystems");
         variables.add("GITHUB_REPOSITORY", "duck");
         variables.add("GITHUB_WORKFLOW", "workflow.yml");
+        variables.add("GITHUB_BRANCH", "master");
         variables.add("GITHUB_USERNAME", "patrik");
         variables.add("GITHUB_PASSWORD", "hunter1!");
+        variables.add("BITBUCKET_ID", "bitbucket");
+        variables.add("BITBUCKET_HOST", "bitbucket.example.com");
+        variables.add("BITBUCKET_USERNAME", "patrik");
+        variables.add("BITBUCKET_PASSWORD", "SECRET-BITBUCKET-PASSWORD");
+        variables.add("BITBUCKET_REPOSITORY", "duckhq/duck");
+        variables.add("BITBUCKET_BRANCH", "master");
+        variables.add("CIRCLECI_ID", "circleci");
+        variables.add("CIRCLECI_TOKEN", "SECRET-CIRCLECI-TOKEN");
+        variables.add("CIRCLECI_PROJECT", "gh/duckhq/duck");
+        variables.add("CIRCLECI_BRANCH", "master");
+        variables.add("GITLAB_ID", "gitlab");
+        variables.add("GITLAB_HOST", "gitlab.example.com");
+        variables.add("GITLAB_TOKEN", "SECRET-GITLAB-TOKEN");
+        variables.add("GITLAB_PROJECT", "duckhq/duck");
+        variables.add("GITLAB_BRANCH", "master");
         variables.add("OCTOPUS_ID", "octopus");
         variables.add("OCTOPUS_HOST", "localhost");
         variables.add("OCTOPUS_PORT", "9000");
@@ -182,6 +275,12 @@ mod tests {
         variables.add("MATTERMOST_ID", "mattermost");
         variables.add("MATTERMOST_CHANNEL", "some-channel");
         variables.add("MATTERMOST_WEBHOOK_URL", "https://example.com/mattermost");
+        variables.add("TEAMS_ID", "teams");
+        variables.add("TEAMS_WEBHOOK_URL", "https://example.com/teams");
+        variables.add("WEBHOOK_ID", "webhook");
+        variables.add("WEBHOOK_URL", "https://example.com/webhook");
+        variables.add("WEBHOOK_TOKEN", "SECRET-WEBHOOK-TOKEN");
+        variables.add("WEBHOOK_SECRET", "SECRET-WEBHOOK-SECRET");
         return variables;
     }
 
@@ -202,6 +301,22 @@ mod tests {
         assert_eq!("hunter1!", password);
     }
 
+    #[test]
+    fn should_expand_jenkins_configuration() {
+        // Given, When
+        let config = read_config!(CONFIGURATION);
+
+        // Then
+        let jenkins = find_config!(config.collectors, CollectorConfiguration::Jenkins);
+        let (username, token) = jenkins.get_basic_auth();
+
+        assert_eq!("jenkins", jenkins.id);
+        assert_eq!("https://jenkins.example.com", jenkins.server_url);
+        assert_eq!("MyFolder/MyJob", jenkins.jobs[0]);
+        assert_eq!("patrik", username);
+        assert_eq!("SECRET-JENKINS-TOKEN", token);
+    }
+
     #[test]
     fn should_expand_azure_configuration() {
         // Given, When
@@ -230,14 +345,71 @@ mod tests {
         let (username, password) = github.get_credentials();
 
         assert_eq!("github", github.id);
+        assert_eq!(
+            "https://github.example.com/api/v3",
+            github.api_url.as_ref().unwrap()
+        );
         assert_eq!("spectresystems", github.owner);
         assert_eq!("duck", github.repository);
-        assert_eq!("workflow.yml", github.workflow);
-        assert_eq!("workflow.yml", github.workflow);
+        assert_eq!("workflow.yml", github.workflow.as_ref().unwrap());
+        assert_eq!("master", github.branches.as_ref().unwrap()[0]);
         assert_eq!("patrik", username);
         assert_eq!("hunter1!", password);
     }
 
+    #[test]
+    fn should_expand_bitbucket_configuration() {
+        // Given, When
+        let config = read_config!(CONFIGURATION);
+
+        // Then
+        let bitbucket = find_config!(config.collectors, CollectorConfiguration::Bitbucket);
+
+        assert_eq!("bitbucket", bitbucket.id);
+        assert_eq!(
+            "https://bitbucket.example.com",
+            bitbucket.server_url.as_ref().unwrap()
+        );
+        assert_eq!("duckhq/duck", bitbucket.repositories[0]);
+        assert_eq!("master", bitbucket.branches[0]);
+        assert_eq!(
+            ("patrik", "SECRET-BITBUCKET-PASSWORD"),
+            bitbucket.get_app_password()
+        );
+    }
+
+    #[test]
+    fn should_expand_circleci_configuration() {
+        // Given, When
+        let config = read_config!(CONFIGURATION);
+
+        // Then
+        let circleci = find_config!(config.collectors, CollectorConfiguration::CircleCi);
+
+        assert_eq!("circleci", circleci.id);
+        assert_eq!("gh/duckhq/duck", circleci.project);
+        assert_eq!("master", circleci.branches[0]);
+        assert_eq!("SECRET-CIRCLECI-TOKEN", circleci.get_token());
+    }
+
+    #[test]
+    fn should_expand_gitlab_configuration() {
+        // Given, When
+        let config = read_config!(CONFIGURATION);
+
+        // Then
+        let gitlab = find_config!(config.collectors, CollectorConfiguration::GitLab);
+
+        assert_eq!("gitlab", gitlab.id);
+        assert_eq!(
+            "https://gitlab.example.com",
+            gitlab.server_url.as_ref().unwrap()
+        );
+        assert_eq!("duckhq/duck", gitlab.projects[0]);
+        assert_eq!("master", gitlab.branches[0]);
+        assert_eq!("SECRET-GITLAB-TOKEN", gitlab.get_access_token());
+    }
+
     #[test]
     fn should_expand_octopus_configuration() {
         // Given, When
@@ -264,7 +436,7 @@ mod tests {
 
         assert_eq!("appveyor", appveyor.id);
         assert_eq!("patriksvensson", appveyor.account);
-        assert_eq!("spectre-commandline", appveyor.project);
+        assert_eq!(Some("spectre-commandline".to_owned()), appveyor.project);
         assert_eq!("SECRET-APPVEYOR-TOKEN", appveyor.get_bearer_token());
         assert_eq!(4, appveyor.get_count());
     }
@@ -327,6 +499,37 @@ mod tests {
             mattermost.get_webhook_url()
         );
     }
+
+    #[test]
+    fn should_expand_teams_configuration() {
+        // Given, When
+        let config = read_config!(CONFIGURATION);
+
+        // Then
+        let observers = config.observers.as_ref().unwrap();
+        let teams = find_config!(observers, ObserverConfiguration::Teams);
+
+        assert_eq!("teams", teams.id);
+        assert_eq!("https://example.com/teams", teams.get_webhook_url());
+    }
+
+    #[test]
+    fn should_expand_webhook_configuration() {
+        // Given, When
+        let config = read_config!(CONFIGURATION);
+
+        // Then
+        let observers = config.observers.as_ref().unwrap();
+        let webhook = find_config!(observers, ObserverConfiguration::Webhook);
+
+        assert_eq!("webhook", webhook.id);
+        assert_eq!("https://example.com/webhook", webhook.url);
+        assert_eq!(
+            "Bearer SECRET-WEBHOOK-TOKEN",
+            webhook.headers.as_ref().unwrap()["Authorization"]
+        );
+        assert_eq!("SECRET-WEBHOOK-SECRET", webhook.secret.as_ref().unwrap());
+    }
 }
 
 #[cfg(test)]
@@ -338,6 +541,18 @@ mod utilities {
             match &self.credentials {
                 TeamCityAuth::Guest => panic!("TeamCity configuration has guest credentials"),
                 TeamCityAuth::BasicAuth { username, password } => (username, password),
+                TeamCityAuth::Token(_) => panic!("TeamCity configuration has token credentials"),
+            }
+        }
+    }
+
+    impl JenkinsConfiguration {
+        pub fn get_basic_auth(&self) -> (&str, &str) {
+            match &self.credentials {
+                JenkinsCredentials::Anonymous => {
+                    panic!("Jenkins configuration have anonymous credentials")
+                }
+                JenkinsCredentials::Basic { username, token } => (username, token),
             }
         }
     }
@@ -357,6 +572,38 @@ mod utilities {
         pub fn get_credentials(&self) -> (&str, &str) {
             match &self.credentials {
                 GitHubCredentials::Basic { username, password } => (username, password),
+                _ => panic!("GitHub configuration does not have basic credentials"),
+            }
+        }
+    }
+
+    impl BitbucketConfiguration {
+        pub fn get_app_password(&self) -> (&str, &str) {
+            match &self.credentials {
+                BitbucketCredentials::AppPassword { username, password } => (username, password),
+                _ => panic!("Bitbucket configuration does not have app password credentials"),
+            }
+        }
+    }
+
+    impl CircleCiConfiguration {
+        pub fn get_token(&self) -> &str {
+            match &self.credentials {
+                CircleCiCredentials::Anonymous => {
+                    panic!("CircleCI configuration have anonymous credentials")
+                }
+                CircleCiCredentials::Token(token) => token,
+            }
+        }
+    }
+
+    impl GitLabConfiguration {
+        pub fn get_access_token(&self) -> &str {
+            match &self.credentials {
+                GitLabCredentials::Anonymous => {
+                    panic!("GitLab configuration have anonymous credentials")
+                }
+                GitLabCredentials::AccessToken(token) => token,
             }
         }
     }
@@ -392,4 +639,12 @@ mod utilities {
             }
         }
     }
+
+    impl TeamsConfiguration {
+        pub fn get_webhook_url(&self) -> &str {
+            match &self.credentials {
+                TeamsCredentials::Webhook { url } => url,
+            }
+        }
+    }
 }
@@ -3,7 +3,8 @@ use std::collections::{HashMap, HashSet};
 
 use log::warn;
 
-use super::{Configuration, Validate};
+use super::{Configuration, StorageConfiguration, Validate};
+use crate::filters::BuildFilter;
 use crate::DuckResult;
 
 impl Validate for Configuration {
@@ -12,9 +13,12 @@ impl Validate for Configuration {
             warn!("No collectors have been specified");
         }
 
+        validate_intervals(&self)?;
         validate_views(&self)?;
         validate_ids(&self)?;
         validate_collector_references(&self)?;
+        validate_observer_filters(&self)?;
+        validate_storage(&self)?;
 
         // Validate collectors
         for collector in self.collectors.iter() {
@@ -32,6 +36,23 @@ impl Validate for Configuration {
     }
 }
 
+fn validate_intervals(configuration: &Configuration) -> DuckResult<()> {
+    // An interval of zero would make collectors poll without pause.
+    if configuration.interval == 0 {
+        return Err(format_err!("The update interval must be at least 1 second"));
+    }
+    for collector in configuration.collectors.iter() {
+        if collector.get_interval() == Some(0) {
+            return Err(format_err!(
+                "The update interval for collector '{}' must be at least 1 second",
+                collector.get_id()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 fn validate_views(configuration: &Configuration) -> DuckResult<()> {
     let valid_id_pattern = Regex::new(r"^[a-zA-Z0-9_]+$")?;
     if let Some(views) = &configuration.views {
@@ -46,15 +67,24 @@ fn validate_views(configuration: &Configuration) -> DuckResult<()> {
                     view.id
                 ));
             }
-            for view_collector in view.collectors.iter() {
-                if !configuration.collector_exist(&view_collector) {
-                    return Err(format_err!(
-                        "The view '{}' depends on collector '{}' which does not exist",
-                        view.id,
-                        view_collector
-                    ));
+            if let Some(collectors) = &view.collectors {
+                for view_collector in collectors.iter() {
+                    if !configuration.collector_exist(&view_collector) {
+                        return Err(format_err!(
+                            "The view '{}' depends on collector '{}' which does not exist",
+                            view.id,
+                            view_collector
+                        ));
+                    }
                 }
             }
+            if let Err(e) = BuildFilter::new(view.filter.clone()) {
+                return Err(format_err!(
+                    "The view '{}' has an invalid filter: {}",
+                    view.id,
+                    e
+                ));
+            }
             known_ids.insert(view.id.clone());
         }
     };
@@ -119,6 +149,36 @@ fn validate_collector_references(configuration: &Configuration) -> DuckResult<()
     Ok(())
 }
 
+fn validate_observer_filters(configuration: &Configuration) -> DuckResult<()> {
+    if let Some(observers) = &configuration.observers {
+        for observer in observers.iter() {
+            if let Err(e) = BuildFilter::new(observer.get_filter()) {
+                return Err(format_err!(
+                    "The observer '{}' has an invalid filter: {}",
+                    observer.get_id(),
+                    e
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_storage(configuration: &Configuration) -> DuckResult<()> {
+    if let Some(storage) = &configuration.storage {
+        match storage {
+            StorageConfiguration::File { path, .. } => {
+                if path.trim().is_empty() {
+                    return Err(format_err!("The storage file path is empty"));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 ///////////////////////////////////////////////////////////
 // Tests
 
@@ -200,6 +260,52 @@ mod tests {
         config.validate().unwrap();
     }
 
+    #[test]
+    #[should_panic(expected = "has an invalid filter")]
+    fn should_return_error_if_a_view_has_an_invalid_filter() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [ ],
+                "views": [
+                    {
+                        "id": "foo",
+                        "name": "Foo",
+                        "filter": "branch == 3"
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown property 'brnch'. Did you mean 'branch'?")]
+    fn should_return_error_if_an_observer_has_an_invalid_filter() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "webhook": {
+                            "id": "foo",
+                            "url": "https://example.com",
+                            "filter": "brnch == 'master'"
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
+
     #[test]
     #[should_panic(expected = "Found duplicate id \\'foo\\' in configuration")]
     fn should_return_error_if_two_collectors_have_the_same_id() {
@@ -302,4 +408,66 @@ mod tests {
         .unwrap();
         config.validate().unwrap();
     }
+
+    #[test]
+    #[should_panic(expected = "The update interval must be at least 1 second")]
+    fn should_return_error_if_interval_is_zero() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "interval": 0,
+                "collectors": [ ]
+            }
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn should_return_error_if_collector_interval_is_zero() {
+        let result = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "teamcity": {
+                            "id": "teamcity",
+                            "interval": 0,
+                            "serverUrl": "http://localhost:8111",
+                            "credentials": "guest",
+                            "builds": [ "Foo" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        );
+        assert_eq!(
+            "The update interval for collector 'teamcity' must be at least 1 second",
+            format!("{}", result.err().unwrap())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The storage file path is empty")]
+    fn should_return_error_if_storage_file_path_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [ ],
+                "storage": {
+                    "file": {
+                        "path": ""
+                    }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
 }
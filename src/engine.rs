@@ -55,7 +55,7 @@ pub enum EngineEvent {
     /// The build was updated.
     BuildUpdated(Box<Build>),
     /// Absolute status for a build changed from Success->Failure or vice versa.
-    AbsoluteBuildStatusChanged(Box<Build>),
+    AbsoluteBuildStatusChanged(Box<Build>, BuildStatus),
     /// Duck is shutting down.
     ShuttingDown,
 }
@@ -70,7 +70,7 @@ impl EngineEvent {
     }
     fn is_build_status_changed(&self) -> bool {
         match self {
-            EngineEvent::AbsoluteBuildStatusChanged(_) => true,
+            EngineEvent::AbsoluteBuildStatusChanged(_, _) => true,
             _ => false,
         }
     }
@@ -194,28 +194,21 @@ fn run_accumulator(
 
     trace!("Waiting for a configuration to be loaded");
     loop {
-        match accumulator::check_for_updated_configuration(&mut context) {
-            Err(_) => (),
-            Ok(result) => match result {
-                accumulator::ConfigurationResult::Unchanged => (),
-                accumulator::ConfigurationResult::Updated => break,
-            },
-        };
-        if handle.wait(Duration::from_millis(500)).unwrap() {
-            break;
+        // Restart all collectors if the configuration changed.
+        // Every collector runs in its own thread on its own schedule.
+        if let Err(e) = accumulator::check_for_updated_configuration(&mut context) {
+            error!("{}", e);
         }
-    }
 
-    while !handle.check().unwrap() {
-        accumulator::accumulate(&mut context);
-
-        // Wait for a little while
-        if handle.wait(std::time::Duration::from_secs(15)).unwrap() {
+        if handle.wait(Duration::from_millis(500)).unwrap() {
             debug!("The accumulator was instructed to stop");
             break;
         }
     }
 
+    trace!("Stopping collectors...");
+    accumulator::stop_workers(&mut context);
+
     debug!("Sending shutdown message");
     match sender.send(EngineEvent::ShuttingDown) {
         Result::Ok(_) => (),
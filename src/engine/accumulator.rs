@@ -1,9 +1,11 @@
-use std::collections::HashSet;
-use std::sync::mpsc::{Receiver, Sender};
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
 use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
 
 use log::{debug, error, trace};
-use waithandle::WaitHandleListener;
+use waithandle::{WaitHandleListener, WaitHandleSignaler};
 
 use crate::builds::Build;
 use crate::engine::{EngineEvent, EngineState};
@@ -19,7 +21,7 @@ pub struct Context {
     engine_receiver: Receiver<EngineThreadMessage>,
     state: Arc<EngineState>,
     sender: Sender<EngineEvent>,
-    collectors: Vec<Box<dyn Collector>>,
+    workers: Vec<Worker>,
 }
 
 impl Context {
@@ -34,93 +36,58 @@ impl Context {
             engine_receiver,
             state,
             sender: accumulator_sender,
-            collectors: vec![],
+            workers: vec![],
         }
     }
 }
 
-#[allow(clippy::borrowed_box)]
-pub fn accumulate(context: &mut Context) {
-    if let Err(e) = check_for_updated_configuration(context) {
-        error!("{}", e);
-        return;
-    }
-
-    for collector in context.collectors.iter() {
-        let mut build_hashes = std::collections::HashSet::<u64>::new();
-        if let Err(e) = collector.collect(context.listener.clone(), &mut |build: Build| {
-            build_hashes.insert(build.id);
-            match context.state.builds.update(&build) {
-                BuildUpdateResult::Added | BuildUpdateResult::BuildUpdated => {
-                    // The build was updated
-                    match context
-                        .sender
-                        .send(EngineEvent::BuildUpdated(Box::new(build)))
-                    {
-                        Result::Ok(_) => (),
-                        Result::Err(e) => error!("Failed to send build update event. {}", e),
-                    }
-                }
-                BuildUpdateResult::AbsoluteBuildStatusChanged => {
-                    // The build's status was changed (success->failed or failed->success)
-                    match context
-                        .sender
-                        .send(EngineEvent::AbsoluteBuildStatusChanged(Box::new(build)))
-                    {
-                        Result::Ok(_) => (),
-                        Result::Err(e) => error!("Failed to send build status event. {}", e),
-                    }
-                }
-                _ => {}
-            };
-        }) {
-            // Log the error but continue as normal since
-            // we don't want to retain the builds that we could
-            // not collect information about
-            error!(
-                "An error occured while collecting builds from '{}': {}",
-                collector.info().id,
-                e
-            );
-        };
-
-        // Retain builds that were updated
-        context
-            .state
-            .builds
-            .retain_builds(&collector.info(), build_hashes);
-    }
-}
-
-pub enum ConfigurationResult {
-    Unchanged,
-    Updated,
+/// A worker thread that collects builds from
+/// a single collector on its own schedule.
+struct Worker {
+    id: String,
+    signaler: WaitHandleSignaler,
+    trigger: Sender<()>,
+    handle: JoinHandle<()>,
 }
 
-pub fn check_for_updated_configuration(context: &mut Context) -> DuckResult<ConfigurationResult> {
+pub fn check_for_updated_configuration(context: &mut Context) -> DuckResult<()> {
     if let Some(config) =
         super::try_get_updated_configuration(&context.listener, &context.engine_receiver)
     {
         trace!("Applying new configuration...");
         match providers::create_collectors(&config) {
             Ok(collectors) => {
-                context.collectors.clear();
+                // Stop all running collectors before
+                // starting the new ones.
+                stop_workers(context);
+
+                // Remove state for unloaded collectors.
+                let mut collector_ids = HashSet::<String>::new();
+                for collector in collectors.iter() {
+                    collector_ids.insert(collector.info().id.clone());
+                }
+                context.state.builds.retain(&collector_ids);
+
+                // Register inboxes for collectors that builds are pushed to.
+                let mut inboxes = HashMap::new();
+                for collector in collectors.iter() {
+                    if let Some(inbox) = collector.inbox() {
+                        inboxes.insert(collector.info().id.clone(), inbox);
+                    }
+                }
+                context.state.ingest.set_inboxes(inboxes);
+
+                let interval = Duration::from_secs(config.interval.into());
                 for collector in collectors {
                     debug!(
                         "Loaded {} collector: {}",
                         collector.info().provider,
                         collector.info().id
                     );
-                    context.collectors.push(collector);
+                    start_worker(context, collector, interval)?;
                 }
 
-                // Remove state for unloaded collectors.
-                let mut collector_ids = HashSet::<String>::new();
-                for collector in context.collectors.iter() {
-                    collector_ids.insert(collector.info().id.clone());
-                }
-                context.state.builds.retain(&collector_ids);
-                return Ok(ConfigurationResult::Updated);
+                return Ok(());
             }
             Err(err) => {
                 return Err(format_err!(
@@ -130,7 +97,145 @@ pub fn check_for_updated_configuration(context: &mut Context) -> DuckResult<Conf
             }
         }
     }
-    Ok(ConfigurationResult::Unchanged)
+    Ok(())
+}
+
+fn start_worker(
+    context: &mut Context,
+    collector: Box<dyn Collector>,
+    interval: Duration,
+) -> DuckResult<()> {
+    let id = collector.info().id.clone();
+    let interval = match collector.info().interval {
+        Some(interval) => Duration::from_secs(interval.into()),
+        None => interval,
+    };
+
+    let (signaler, listener) = waithandle::new();
+    let (trigger, triggered) = channel::<()>();
+    let handle = std::thread::Builder::new()
+        .name(format!("collector-{}", id))
+        .spawn({
+            let state = context.state.clone();
+            let sender = context.sender.clone();
+            move || run_worker(collector, interval, listener, triggered, state, sender)
+        })?;
+
+    // Allow other parts of Duck to trigger collection.
+    context.state.triggers.add(&id, trigger.clone());
+
+    context.workers.push(Worker {
+        id,
+        signaler,
+        trigger,
+        handle,
+    });
+
+    Ok(())
+}
+
+/// Stops all running workers and waits for them to finish.
+pub fn stop_workers(context: &mut Context) {
+    context.state.triggers.clear();
+    for worker in context.workers.iter() {
+        if let Err(e) = worker.signaler.signal() {
+            error!("Could not signal collector '{}' to stop: {}", worker.id, e);
+        }
+        // Wake up the worker if it's waiting for its next update.
+        let _ = worker.trigger.send(());
+    }
+    for worker in context.workers.drain(..) {
+        if worker.handle.join().is_err() {
+            error!("The collector '{}' stopped unexpectedly", worker.id);
+        }
+        trace!("The collector '{}' stopped", worker.id);
+    }
+}
+
+fn run_worker(
+    collector: Box<dyn Collector>,
+    interval: Duration,
+    listener: WaitHandleListener,
+    triggered: Receiver<()>,
+    state: Arc<EngineState>,
+    sender: Sender<EngineEvent>,
+) {
+    debug!(
+        "Collecting builds from '{}' every {} seconds",
+        collector.info().id,
+        interval.as_secs()
+    );
+    while !listener.check().unwrap() {
+        collect(collector.as_ref(), &listener, &state, &sender);
+
+        // Wait until it's time to collect builds again,
+        // or until we're told to collect builds right away.
+        match triggered.recv_timeout(interval) {
+            Ok(_) => {
+                // Several triggers might have arrived at
+                // once, but we only need to collect once.
+                while triggered.try_recv().is_ok() {}
+                trace!("Collection for '{}' was triggered", collector.info().id);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
+
+pub fn collect(
+    collector: &dyn Collector,
+    listener: &WaitHandleListener,
+    state: &EngineState,
+    sender: &Sender<EngineEvent>,
+) {
+    let mut build_hashes = std::collections::HashSet::<u64>::new();
+    if let Err(e) = collector.collect(listener.clone(), &mut |build: Build| {
+        build_hashes.insert(build.id);
+        let result = state.builds.update(&build);
+        if result != BuildUpdateResult::Unchanged {
+            // Record the state transition.
+            state.history.record(&build);
+        }
+        match result {
+            BuildUpdateResult::Added | BuildUpdateResult::BuildUpdated => {
+                // The build was updated
+                match sender.send(EngineEvent::BuildUpdated(Box::new(build))) {
+                    Result::Ok(_) => (),
+                    Result::Err(e) => error!("Failed to send build update event. {}", e),
+                }
+            }
+            BuildUpdateResult::AbsoluteBuildStatusChanged(previous) => {
+                // The build's status was changed (success->failed or failed->success)
+                match sender.send(EngineEvent::AbsoluteBuildStatusChanged(
+                    Box::new(build),
+                    previous,
+                )) {
+                    Result::Ok(_) => (),
+                    Result::Err(e) => error!("Failed to send build status event. {}", e),
+                }
+            }
+            _ => {}
+        };
+    }) {
+        // Log the error but continue as normal since
+        // we don't want to retain the builds that we could
+        // not collect information about
+        error!(
+            "An error occured while collecting builds from '{}': {}",
+            collector.info().id,
+            e
+        );
+    };
+
+    // Were we interrupted? Then we don't know
+    // which builds that should be retained.
+    if listener.check().unwrap() {
+        return;
+    }
+
+    // Retain builds that were updated
+    state.builds.retain_builds(&collector.info(), build_hashes);
 }
 
 #[cfg(test)]
@@ -139,12 +244,15 @@ mod tests {
     use crate::builds::{BuildBuilder, BuildStatus};
     use crate::providers::collectors::CollectorInfo;
     use crate::DuckResult;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::mpsc::channel;
     use test_case::test_case;
 
     pub struct DummyCollector {
         pub build: Build,
         pub info: CollectorInfo,
+        pub collected: Arc<AtomicUsize>,
+        pub notifier: Option<Sender<()>>,
     }
 
     impl DummyCollector {
@@ -155,9 +263,24 @@ mod tests {
                     id: "dummy".to_owned(),
                     enabled: true,
                     provider: "GitHub".to_owned(),
+                    interval: None,
                 },
+                collected: Arc::new(AtomicUsize::new(0)),
+                notifier: None,
             }
         }
+
+        pub fn with_interval(mut self, interval: u16) -> Self {
+            self.info.interval = Some(interval);
+            self
+        }
+
+        /// Returns a receiver that gets notified every time builds are collected.
+        pub fn with_notifier(mut self) -> (Self, Receiver<()>) {
+            let (sender, receiver) = channel::<()>();
+            self.notifier = Some(sender);
+            (self, receiver)
+        }
     }
 
     impl Collector for DummyCollector {
@@ -169,32 +292,70 @@ mod tests {
             _: WaitHandleListener,
             callback: &mut dyn FnMut(Build),
         ) -> DuckResult<()> {
+            self.collected.fetch_add(1, Ordering::SeqCst);
             callback(self.build.clone());
+            if let Some(notifier) = &self.notifier {
+                let _ = notifier.send(());
+            }
             return Ok(());
         }
     }
 
-    #[test]
-    fn should_send_build_updated_event_if_build_is_new() {
-        // Given
+    /// A collector that never returns until it's told to stop.
+    pub struct HangingCollector {
+        pub info: CollectorInfo,
+    }
+
+    impl HangingCollector {
+        pub fn new() -> Self {
+            HangingCollector {
+                info: CollectorInfo {
+                    id: "hanging".to_owned(),
+                    enabled: true,
+                    provider: "Octopus".to_owned(),
+                    interval: None,
+                },
+            }
+        }
+    }
+
+    impl Collector for HangingCollector {
+        fn info(&self) -> &crate::providers::collectors::CollectorInfo {
+            &self.info
+        }
+        fn collect(
+            &self,
+            listener: WaitHandleListener,
+            _: &mut dyn FnMut(Build),
+        ) -> DuckResult<()> {
+            listener.wait(Duration::from_secs(60)).unwrap();
+            Err(format_err!("The request timed out"))
+        }
+    }
+
+    fn create_context() -> (Context, Receiver<EngineEvent>) {
         let (sender, receiver) = channel::<EngineEvent>();
         let (_, engine_receiver) = channel::<EngineThreadMessage>();
         let (_, listener) = waithandle::new();
-
-        let mut context = Context {
+        let context = Context::new(
             listener,
-            sender,
+            Arc::new(EngineState::new()),
             engine_receiver,
-            state: Arc::new(EngineState::new()),
-            collectors: Vec::new(),
-        };
+            sender,
+        );
+        (context, receiver)
+    }
 
-        let new_build = DummyCollector::new(BuildBuilder::dummy().build().unwrap());
-        let collector = Box::new(new_build) as Box<dyn Collector>;
-        context.collectors.push(collector);
+    #[test]
+    fn should_send_build_updated_event_if_build_is_new() {
+        // Given
+        let (sender, receiver) = channel::<EngineEvent>();
+        let (_, listener) = waithandle::new();
+        let state = EngineState::new();
+        let collector = DummyCollector::new(BuildBuilder::dummy().build().unwrap());
 
         // When
-        accumulate(&mut context);
+        collect(&collector, &listener, &state, &sender);
 
         // Then
         assert!(receiver.try_recv().unwrap().is_build_updated());
@@ -205,26 +366,17 @@ mod tests {
     fn should_not_send_build_updated_event_if_build_is_known() {
         // Given
         let (sender, receiver) = channel::<EngineEvent>();
-        let (_, engine_receiver) = channel::<EngineThreadMessage>();
         let (_, listener) = waithandle::new();
-
-        let mut context = Context {
-            listener,
-            sender,
-            engine_receiver,
-            state: Arc::new(EngineState::new()),
-            collectors: Vec::new(),
-        };
+        let state = EngineState::new();
 
         let current_build = BuildBuilder::dummy().build().unwrap();
-        context.state.builds.update(&current_build);
+        state.builds.update(&current_build);
 
         let new_build = BuildBuilder::dummy().build().unwrap();
-        let collector = Box::new(DummyCollector::new(new_build)) as Box<dyn Collector>;
-        context.collectors.push(collector);
+        let collector = DummyCollector::new(new_build);
 
         // When
-        accumulate(&mut context);
+        collect(&collector, &listener, &state, &sender);
 
         // Then
         receiver
@@ -258,26 +410,17 @@ mod tests {
     ) {
         // Given
         let (sender, receiver) = channel::<EngineEvent>();
-        let (_, engine_receiver) = channel::<EngineThreadMessage>();
         let (_, listener) = waithandle::new();
-
-        let mut context = Context {
-            listener,
-            sender,
-            engine_receiver,
-            state: Arc::new(EngineState::new()),
-            collectors: Vec::new(),
-        };
+        let state = EngineState::new();
 
         let current_build = BuildBuilder::dummy().status(from).build().unwrap();
-        context.state.builds.update(&current_build);
+        state.builds.update(&current_build);
 
         let new_build = BuildBuilder::dummy().status(to).build().unwrap();
-        let collector = Box::new(DummyCollector::new(new_build)) as Box<dyn Collector>;
-        context.collectors.push(collector);
+        let collector = DummyCollector::new(new_build);
 
         // When
-        accumulate(&mut context);
+        collect(&collector, &listener, &state, &sender);
 
         // Then
         assert!(receiver.try_recv().unwrap().is_build_updated());
@@ -297,28 +440,96 @@ mod tests {
     ) {
         // Given
         let (sender, receiver) = channel::<EngineEvent>();
-        let (_, engine_receiver) = channel::<EngineThreadMessage>();
         let (_, listener) = waithandle::new();
-
-        let mut context = Context {
-            listener,
-            sender,
-            engine_receiver,
-            state: Arc::new(EngineState::new()),
-            collectors: Vec::new(),
-        };
+        let state = EngineState::new();
 
         let current_build = BuildBuilder::dummy().status(from).build().unwrap();
-        context.state.builds.update(&current_build);
+        state.builds.update(&current_build);
 
         let new_build = BuildBuilder::dummy().status(to).build().unwrap();
-        let collector = Box::new(DummyCollector::new(new_build)) as Box<dyn Collector>;
-        context.collectors.push(collector);
+        let collector = DummyCollector::new(new_build);
 
         // When
-        accumulate(&mut context);
+        collect(&collector, &listener, &state, &sender);
 
         // Then
         assert!(receiver.try_recv().unwrap().is_build_status_changed());
     }
+
+    #[test]
+    fn should_not_be_blocked_by_hanging_collector() {
+        // Given
+        let (mut context, receiver) = create_context();
+        let collector = DummyCollector::new(BuildBuilder::dummy().build().unwrap());
+        let interval = Duration::from_secs(15);
+
+        // When
+        start_worker(&mut context, Box::new(HangingCollector::new()), interval).unwrap();
+        start_worker(&mut context, Box::new(collector), interval).unwrap();
+
+        // Then
+        let event = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(event.is_build_updated());
+        stop_workers(&mut context);
+    }
+
+    #[test]
+    fn should_collect_builds_again_when_interval_has_elapsed() {
+        // Given
+        let (mut context, _receiver) = create_context();
+        let (collector, collected) =
+            DummyCollector::new(BuildBuilder::dummy().build().unwrap()).with_notifier();
+
+        // When
+        start_worker(&mut context, Box::new(collector), Duration::from_millis(10)).unwrap();
+        let first = collected.recv_timeout(Duration::from_secs(5));
+        let second = collected.recv_timeout(Duration::from_secs(5));
+        stop_workers(&mut context);
+
+        // Then
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+    }
+
+    #[test]
+    fn should_use_collector_interval_if_specified() {
+        // Given
+        let (mut context, _receiver) = create_context();
+        let (collector, collected) = DummyCollector::new(BuildBuilder::dummy().build().unwrap())
+            .with_interval(60)
+            .with_notifier();
+        let count = collector.collected.clone();
+
+        // When
+        start_worker(&mut context, Box::new(collector), Duration::from_millis(10)).unwrap();
+        collected.recv_timeout(Duration::from_secs(5)).unwrap();
+        let second = collected.recv_timeout(Duration::from_millis(100));
+        stop_workers(&mut context);
+
+        // Then
+        assert_eq!(Err(RecvTimeoutError::Timeout), second);
+        assert_eq!(1, count.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn should_collect_builds_right_away_when_triggered() {
+        // Given
+        let (mut context, _receiver) = create_context();
+        let (collector, collected) = DummyCollector::new(BuildBuilder::dummy().build().unwrap())
+            .with_interval(60)
+            .with_notifier();
+        let count = collector.collected.clone();
+        start_worker(&mut context, Box::new(collector), Duration::from_millis(10)).unwrap();
+        collected.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        // When
+        let triggered = context.state.triggers.trigger("dummy");
+        let second = collected.recv_timeout(Duration::from_secs(5));
+        stop_workers(&mut context);
+
+        // Then
+        assert!(triggered);
+        assert!(second.is_ok());
+        assert_eq!(2, count.load(Ordering::SeqCst));
+    }
 }
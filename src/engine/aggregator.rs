@@ -6,6 +6,7 @@ use log::{debug, error, trace};
 use waithandle::WaitHandleListener;
 
 use crate::builds::{Build, BuildStatus};
+use crate::engine::state::events::EngineNotification;
 use crate::engine::{EngineEvent, EngineState, EngineThreadMessage};
 use crate::filters::FilterResult;
 use crate::providers::observers::*;
@@ -49,21 +50,25 @@ pub fn aggregate(context: &mut Context) -> AggregateResult {
 
         match command {
             EngineEvent::BuildUpdated(build) => {
-                build_updated(context, build);
+                let notification = EngineNotification::BuildUpdated((*build).clone());
+                build_updated(context, build, notification);
             }
-            EngineEvent::AbsoluteBuildStatusChanged(build) => {
-                // Send the BuildUpdated event to all observers.
-                propagate_to_observers(&context.observers, &mut || {
-                    Observation::BuildUpdated(&build)
-                });
+            EngineEvent::AbsoluteBuildStatusChanged(build, previous) => {
+                // The build was updated as well, which might have changed
+                // the overall build status. Subscribers are only told about
+                // the status change so they don't apply the update twice.
+                let notification = EngineNotification::BuildStatusChanged((*build).clone());
+                build_updated(context, build.clone(), notification);
                 // Send the BuildStatusChanged event to all observers.
                 propagate_to_observers(&context.observers, &mut || {
-                    Observation::BuildStatusChanged(&build)
+                    Observation::BuildStatusChanged(&build, previous.clone())
                 });
             }
             EngineEvent::ShuttingDown => {
                 // Send the ShuttingDown event to all observers.
                 propagate_to_observers(&context.observers, &mut || Observation::ShuttingDown);
+                // Disconnect all subscribers.
+                context.state.events.close();
                 return AggregateResult::Stopped;
             }
         }
@@ -83,11 +88,31 @@ fn check_for_updated_configuration(context: &mut Context) -> DuckResult<()> {
             debug!("Loaded observer: {}", observer.info().id);
             context.observers.push(observer);
         }
+
+        // Start from the current state (which might have been restored
+        // from history) so we only notify observers about actual changes.
+        if context.status == BuildStatus::Unknown {
+            context.status = context.state.builds.current_status();
+        }
+        for observer in context.observers.iter() {
+            if let Some(collectors) = &observer.info().collectors {
+                let status = context
+                    .state
+                    .builds
+                    .current_status_for_collectors(collectors);
+                if status.is_absolute() {
+                    context
+                        .observer_status
+                        .entry(observer.info().id.clone())
+                        .or_insert(status);
+                }
+            }
+        }
     }
     Ok(())
 }
 
-fn build_updated(context: &mut Context, build: Box<Build>) {
+fn build_updated(context: &mut Context, build: Box<Build>, notification: EngineNotification) {
     // Did the build status change?
     let status = context.state.builds.current_status();
     let overall_status_changed = if context.status != status {
@@ -97,6 +122,15 @@ fn build_updated(context: &mut Context, build: Box<Build>) {
         false
     };
 
+    // Notify subscribers.
+    context.state.events.publish(notification);
+    if overall_status_changed {
+        context
+            .state
+            .events
+            .publish(EngineNotification::StatusChanged(context.status.clone()));
+    }
+
     // Did the overall build status change for any observers?
     for observer in context.observers.iter() {
         // Is the build be filtered out by the observer?
@@ -177,7 +211,7 @@ fn propagate_to_observers<'a>(
                     continue;
                 }
             }
-            Observation::BuildStatusChanged(build) => {
+            Observation::BuildStatusChanged(build, _) => {
                 if should_filter(observer, build) {
                     continue;
                 }
@@ -217,3 +251,100 @@ fn should_filter(observer: &Box<dyn Observer>, build: &Build) -> bool {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+    use std::sync::mpsc::{channel, Sender};
+
+    fn create_context() -> (Context, Sender<EngineEvent>) {
+        let (_, engine_receiver) = channel::<EngineThreadMessage>();
+        let (sender, accumulator_receiver) = channel::<EngineEvent>();
+        let (_, listener) = waithandle::new();
+        let context = Context {
+            state: Arc::new(EngineState::new()),
+            listener,
+            engine_receiver,
+            accumulator_receiver,
+            observers: Vec::new(),
+            observer_status: HashMap::new(),
+            status: BuildStatus::Unknown,
+        };
+        (context, sender)
+    }
+
+    #[test]
+    fn should_publish_status_changed_when_absolute_build_status_changes() {
+        // Given
+        let (mut context, sender) = create_context();
+        let running = BuildBuilder::dummy().status(BuildStatus::Running).unwrap();
+        context.state.builds.update(&running);
+        sender
+            .send(EngineEvent::BuildUpdated(Box::new(running)))
+            .unwrap();
+        aggregate(&mut context);
+        let mut subscriber = context.state.events.subscribe();
+
+        // When
+        let failed = BuildBuilder::dummy().status(BuildStatus::Failed).unwrap();
+        context.state.builds.update(&failed);
+        sender
+            .send(EngineEvent::AbsoluteBuildStatusChanged(
+                Box::new(failed),
+                BuildStatus::Running,
+            ))
+            .unwrap();
+        aggregate(&mut context);
+
+        // Then
+        let mut statuses = Vec::new();
+        while let Ok(Some(notification)) = subscriber.try_next() {
+            if let EngineNotification::StatusChanged(status) = notification {
+                statuses.push(status);
+            }
+        }
+        assert_eq!(vec![BuildStatus::Failed], statuses);
+        assert_eq!(BuildStatus::Failed, context.status);
+    }
+
+    #[test]
+    fn should_only_publish_build_status_changed_when_absolute_build_status_changes() {
+        // Given
+        let (mut context, sender) = create_context();
+        let running = BuildBuilder::dummy().status(BuildStatus::Running).unwrap();
+        context.state.builds.update(&running);
+        sender
+            .send(EngineEvent::BuildUpdated(Box::new(running)))
+            .unwrap();
+        aggregate(&mut context);
+        let mut subscriber = context.state.events.subscribe();
+
+        // When
+        let failed = BuildBuilder::dummy().status(BuildStatus::Failed).unwrap();
+        context.state.builds.update(&failed);
+        sender
+            .send(EngineEvent::AbsoluteBuildStatusChanged(
+                Box::new(failed),
+                BuildStatus::Running,
+            ))
+            .unwrap();
+        aggregate(&mut context);
+
+        // Then
+        let mut events = Vec::new();
+        while let Ok(Some(notification)) = subscriber.try_next() {
+            events.push(match notification {
+                EngineNotification::BuildUpdated(_) => "BuildUpdated".to_owned(),
+                EngineNotification::BuildStatusChanged(b) => {
+                    format!("BuildStatusChanged({})", b.status)
+                }
+                EngineNotification::StatusChanged(s) => format!("StatusChanged({})", s),
+            });
+        }
+        assert_eq!(
+            vec!["BuildStatusChanged(Failed)", "StatusChanged(Failed)"],
+            events
+        );
+    }
+}
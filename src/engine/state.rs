@@ -1,21 +1,36 @@
 use std::time::SystemTime;
 
-use log::debug;
+use log::{debug, error, info};
 
 use crate::config::Configuration;
 use crate::engine::state::builds::BuildRepository;
+use crate::engine::state::events::EventRepository;
+use crate::engine::state::history::HistoryRepository;
+use crate::engine::state::ingest::IngestRepository;
+use crate::engine::state::triggers::TriggerRepository;
 use crate::engine::state::ui::UiRepository;
 use crate::engine::state::views::ViewRepository;
+use crate::engine::state::webhooks::WebhookRepository;
 
 pub mod builds;
+pub mod events;
+pub mod history;
+pub mod ingest;
+pub mod triggers;
 pub mod ui;
 pub mod views;
+pub mod webhooks;
 
 pub struct EngineState {
     pub started: SystemTime,
     pub builds: BuildRepository,
+    pub events: EventRepository,
+    pub history: HistoryRepository,
+    pub ingest: IngestRepository,
+    pub triggers: TriggerRepository,
     pub ui: UiRepository,
     pub views: ViewRepository,
+    pub webhooks: WebhookRepository,
 }
 
 impl EngineState {
@@ -23,8 +38,13 @@ impl EngineState {
         return EngineState {
             started: SystemTime::now(),
             builds: BuildRepository::new(),
+            events: EventRepository::new(),
+            history: HistoryRepository::new(),
+            ingest: IngestRepository::new(),
+            triggers: TriggerRepository::new(),
             ui: UiRepository::new(),
             views: ViewRepository::new(),
+            webhooks: WebhookRepository::new(),
         };
     }
 
@@ -34,5 +54,25 @@ impl EngineState {
         if let Some(views) = &config.views {
             self.views.add_views(views);
         }
+        self.webhooks.configure(config);
+
+        // Restore the latest known builds from the history
+        // so we don't report status changes that already happened.
+        let collectors = config
+            .collectors
+            .iter()
+            .map(|c| c.get_id().to_owned())
+            .collect();
+        match self.history.configure(&config.storage, &collectors) {
+            Ok(builds) => {
+                if !builds.is_empty() {
+                    info!("Restored {} builds from history", builds.len());
+                }
+                for build in builds.iter() {
+                    self.builds.update(build);
+                }
+            }
+            Err(e) => error!("Could not load build history: {}", e),
+        }
     }
 }
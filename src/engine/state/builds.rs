@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use std::sync::Mutex;
 
 use crate::builds::{Build, BuildStatus};
-use crate::engine::state::views::ViewRepository;
+use crate::engine::state::views::{View, ViewRepository};
 use crate::providers::collectors::CollectorInfo;
 
 pub struct BuildRepository {
@@ -15,7 +15,8 @@ pub struct BuildRepository {
 pub enum BuildUpdateResult {
     Added,
     BuildUpdated,
-    AbsoluteBuildStatusChanged,
+    /// The absolute build status changed from the provided status.
+    AbsoluteBuildStatusChanged(BuildStatus),
     Unchanged,
 }
 
@@ -32,19 +33,15 @@ impl BuildRepository {
     }
 
     pub fn for_view(&self, views: &ViewRepository, id: &str) -> Vec<Build> {
-        let builds = self.builds.lock().unwrap();
-
-        if let Some(collectors) = views.get_collectors(id) {
-            let mut result = Vec::<Build>::new();
-            for build in builds.iter() {
-                if collectors.contains(&build.collector) {
-                    result.push(build.clone());
-                }
-            }
-            return result;
+        match views.get_view(id) {
+            // Evaluate the view filter outside of the lock.
+            Some(view) => self
+                .all()
+                .into_iter()
+                .filter(|b| view.includes(b))
+                .collect(),
+            None => vec![],
         }
-
-        return vec![];
     }
 
     #[allow(clippy::block_in_if_condition_stmt)] // Clippy does not like what fmt does...
@@ -75,7 +72,7 @@ impl BuildRepository {
             statuses.insert(build.partition, build.status.clone());
         } else if let Some(val) = statuses.get_mut(&build.partition) {
             if build.status.is_absolute() && *val != build.status {
-                result = BuildUpdateResult::AbsoluteBuildStatusChanged;
+                result = BuildUpdateResult::AbsoluteBuildStatusChanged(val.clone());
                 *val = build.status.clone();
             }
         }
@@ -120,42 +117,49 @@ impl BuildRepository {
     }
 
     pub fn current_status(&self) -> BuildStatus {
-        let results = self.builds.lock().unwrap();
-        if results.len() == 0 {
-            return BuildStatus::Unknown;
-        }
-        if results.iter().any(|b| b.status == BuildStatus::Running) {
-            return BuildStatus::Running;
-        } else if results.iter().any(|b| b.status == BuildStatus::Failed) {
-            return BuildStatus::Failed;
-        }
-        BuildStatus::Success
+        get_status(self.builds.lock().unwrap().iter())
     }
 
     pub fn current_status_for_collectors(&self, collectors: &HashSet<String>) -> BuildStatus {
-        let results = self.builds.lock().unwrap();
-        if !results.iter().any(|b| collectors.contains(&b.collector)) {
-            return BuildStatus::Unknown;
-        }
-        if results
-            .iter()
-            .any(|b| collectors.contains(&b.collector) && b.status == BuildStatus::Running)
-        {
-            return BuildStatus::Running;
-        } else if results
-            .iter()
-            .any(|b| collectors.contains(&b.collector) && b.status == BuildStatus::Failed)
-        {
-            return BuildStatus::Failed;
+        get_status(
+            self.builds
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|b| collectors.contains(&b.collector)),
+        )
+    }
+
+    pub fn current_status_for_view(&self, view: &View) -> BuildStatus {
+        // Evaluate the view filter outside of the lock.
+        let builds = self.all();
+        get_status(builds.iter().filter(|b| view.includes(b)))
+    }
+}
+
+/// Gets the combined status of the provided builds
+/// while only looking at each build once.
+fn get_status<'a, I: Iterator<Item = &'a Build>>(builds: I) -> BuildStatus {
+    let mut result = BuildStatus::Unknown;
+    for build in builds {
+        match build.status {
+            BuildStatus::Running => return BuildStatus::Running,
+            BuildStatus::Failed => result = BuildStatus::Failed,
+            _ => {
+                if result == BuildStatus::Unknown {
+                    result = BuildStatus::Success;
+                }
+            }
         }
-        BuildStatus::Success
     }
+    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::builds::BuildBuilder;
+    use crate::config::ViewConfiguration;
 
     #[test]
     fn should_have_successful_as_current_state_if_there_are_no_builds() {
@@ -290,4 +294,51 @@ mod tests {
 
         assert!(state.current_status_for_collectors(&collectors) == BuildStatus::Failed);
     }
+
+    #[test]
+    fn should_return_correct_state_for_view() {
+        let state = BuildRepository::new();
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project1")
+                .branch("master")
+                .status(BuildStatus::Running)
+                .unwrap(),
+        );
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project2")
+                .branch("develop")
+                .status(BuildStatus::Failed)
+                .unwrap(),
+        );
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project3")
+                .branch("develop")
+                .status(BuildStatus::Success)
+                .unwrap(),
+        );
+
+        let view = View::new(&ViewConfiguration {
+            id: "foo".to_owned(),
+            name: "Foo".to_owned(),
+            collectors: None,
+            filter: Some("branch == 'develop'".to_owned()),
+        })
+        .unwrap();
+
+        assert!(state.current_status_for_view(&view) == BuildStatus::Failed);
+    }
+
+    #[test]
+    fn should_return_previous_status_when_absolute_build_status_changes() {
+        let state = BuildRepository::new();
+        state.update(&BuildBuilder::dummy().status(BuildStatus::Success).unwrap());
+        state.update(&BuildBuilder::dummy().status(BuildStatus::Running).unwrap());
+
+        let result = state.update(&BuildBuilder::dummy().status(BuildStatus::Failed).unwrap());
+
+        assert!(result == BuildUpdateResult::AbsoluteBuildStatusChanged(BuildStatus::Success));
+    }
 }
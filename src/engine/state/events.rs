@@ -0,0 +1,101 @@
+use std::sync::Mutex;
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+
+use crate::builds::{Build, BuildStatus};
+
+/// Events that are published to subscribers
+/// such as the event stream in the HTTP API.
+#[derive(Clone, Debug)]
+pub enum EngineNotification {
+    /// The build was updated.
+    BuildUpdated(Build),
+    /// Absolute status for a build changed from Success->Failure or vice versa.
+    /// Published instead of `BuildUpdated` for the same update.
+    BuildStatusChanged(Build),
+    /// The overall status changed.
+    StatusChanged(BuildStatus),
+}
+
+pub struct EventRepository {
+    subscribers: Mutex<Vec<UnboundedSender<EngineNotification>>>,
+}
+
+impl EventRepository {
+    pub fn new() -> Self {
+        Self {
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn subscribe(&self) -> UnboundedReceiver<EngineNotification> {
+        let (sender, receiver) = unbounded();
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.push(sender);
+        receiver
+    }
+
+    pub fn publish(&self, notification: EngineNotification) {
+        // Send the notification to all subscribers and
+        // get rid of the ones that have disconnected.
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(|s| s.unbounded_send(notification.clone()).is_ok());
+    }
+
+    /// Disconnects all subscribers.
+    pub fn close(&self) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+
+    #[test]
+    fn should_publish_notification_to_all_subscribers() {
+        // Given
+        let repository = EventRepository::new();
+        let mut first = repository.subscribe();
+        let mut second = repository.subscribe();
+
+        // When
+        repository.publish(EngineNotification::StatusChanged(BuildStatus::Failed));
+
+        // Then
+        assert!(first.try_next().unwrap().is_some());
+        assert!(second.try_next().unwrap().is_some());
+    }
+
+    #[test]
+    fn should_remove_disconnected_subscribers() {
+        // Given
+        let repository = EventRepository::new();
+        let mut subscriber = repository.subscribe();
+        drop(repository.subscribe());
+
+        // When
+        repository.publish(EngineNotification::BuildUpdated(
+            BuildBuilder::dummy().unwrap(),
+        ));
+
+        // Then
+        assert_eq!(1, repository.subscribers.lock().unwrap().len());
+        assert!(subscriber.try_next().unwrap().is_some());
+    }
+
+    #[test]
+    fn should_end_subscriptions_when_closed() {
+        // Given
+        let repository = EventRepository::new();
+        let mut subscriber = repository.subscribe();
+
+        // When
+        repository.close();
+
+        // Then
+        assert!(subscriber.try_next().unwrap().is_none());
+    }
+}
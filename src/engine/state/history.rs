@@ -0,0 +1,673 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use log::{debug, error, warn};
+
+use crate::builds::{Build, BuildBuilder, BuildStatus};
+use crate::config::{StorageConfiguration, DEFAULT_HISTORY_RETENTION};
+use crate::DuckResult;
+
+pub mod statistics;
+
+/// A recorded build state transition.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub recorded_at: i64,
+    pub build: Build,
+}
+
+/// Keeps track of build state transitions for the most recent builds
+/// and optionally persists them to an append-only file.
+pub struct HistoryRepository {
+    partitions: Mutex<Partitions>,
+    store: Mutex<Option<Arc<Mutex<FileStore>>>>,
+    retention: AtomicUsize,
+}
+
+impl HistoryRepository {
+    pub fn new() -> Self {
+        Self {
+            partitions: Mutex::new(Partitions::default()),
+            store: Mutex::new(None),
+            retention: AtomicUsize::new(DEFAULT_HISTORY_RETENTION),
+        }
+    }
+
+    /// Configures the storage and returns the latest known builds
+    /// if history was loaded from the storage for the first time.
+    /// History for collectors that no longer exist is removed.
+    pub fn configure(
+        &self,
+        storage: &Option<StorageConfiguration>,
+        collectors: &HashSet<String>,
+    ) -> DuckResult<Vec<Build>> {
+        let mut store = self.store.lock().unwrap();
+        let retention = match storage {
+            None => DEFAULT_HISTORY_RETENTION,
+            Some(storage) => storage.get_retention(),
+        };
+        self.retention.store(retention, Ordering::SeqCst);
+
+        let path = match storage {
+            None => {
+                *store = None;
+                self.partitions.lock().unwrap().retain(collectors);
+                return Ok(vec![]);
+            }
+            Some(StorageConfiguration::File { path, .. }) => PathBuf::from(path),
+        };
+
+        // Already using this file?
+        if let Some(current) = store.as_ref() {
+            let mut current = current.lock().unwrap();
+            if current.path == path {
+                let mut partitions = self.partitions.lock().unwrap();
+                if partitions.retain(collectors) {
+                    current.compact(&partitions.all())?;
+                }
+                return Ok(vec![]);
+            }
+        }
+
+        let mut file = FileStore::new(path);
+        let mut partitions = self.partitions.lock().unwrap();
+        let restored = if partitions.is_empty() {
+            // Load the history from the file.
+            let loaded = file.load()?;
+            debug!("Loaded {} history entries", loaded.len());
+            for entry in loaded.iter() {
+                partitions.push(entry.clone());
+            }
+
+            // Get rid of builds that are no longer retained.
+            partitions.retain(collectors);
+            partitions.prune_all(retention);
+            if partitions.len() < loaded.len() {
+                debug!(
+                    "Removed {} expired history entries",
+                    loaded.len() - partitions.len()
+                );
+                file.compact(&partitions.all())?;
+            }
+
+            partitions.get_latest_builds()
+        } else {
+            partitions.retain(collectors);
+            vec![]
+        };
+
+        *store = Some(Arc::new(Mutex::new(file)));
+        Ok(restored)
+    }
+
+    pub fn record(&self, build: &Build) {
+        let entry = HistoryEntry {
+            recorded_at: chrono::Utc::now().timestamp(),
+            build: build.clone(),
+        };
+
+        // Update the history in memory first, so that
+        // readers never have to wait for the file.
+        let retained = {
+            let mut partitions = self.partitions.lock().unwrap();
+            let partition = entry.build.partition;
+            partitions.push(entry.clone());
+            partitions.prune(partition, self.retention.load(Ordering::SeqCst));
+            partitions.len()
+        };
+
+        let store = match self.store.lock().unwrap().as_ref() {
+            Some(store) => store.clone(),
+            None => return,
+        };
+        let mut store = store.lock().unwrap();
+        if let Err(e) = store.append(&entry) {
+            error!("Could not write build history: {}", e);
+        }
+
+        // Rewrite the file once it contains more
+        // expired entries than retained ones.
+        if store.appended > retained {
+            let entries = self.partitions.lock().unwrap().all();
+            if let Err(e) = store.compact(&entries) {
+                error!("Could not compact build history: {}", e);
+            }
+        }
+    }
+
+    pub fn all(&self) -> Vec<HistoryEntry> {
+        self.partitions.lock().unwrap().all()
+    }
+
+    /// Gets the partition that a build belongs to.
+    pub fn get_partition(&self, id: u64) -> Option<u64> {
+        let partitions = self.partitions.lock().unwrap();
+        partitions.builds.get(&id).copied()
+    }
+
+    /// Gets the last known state of every build in a
+    /// partition, ordered by when the builds were started.
+    pub fn get_results(&self, partition: u64) -> Vec<Build> {
+        let entries = match self.partitions.lock().unwrap().entries.get(&partition) {
+            Some(entries) => entries.clone(),
+            None => return vec![],
+        };
+        get_results(&entries)
+    }
+
+    /// Gets the results for all partitions.
+    pub fn get_all_results(&self) -> Vec<Vec<Build>> {
+        let partitions: Vec<Vec<HistoryEntry>> = {
+            let partitions = self.partitions.lock().unwrap();
+            partitions
+                .order
+                .iter()
+                .map(|p| partitions.entries[p].clone())
+                .collect()
+        };
+        partitions.iter().map(|e| get_results(e)).collect()
+    }
+}
+
+/// Recorded entries grouped by partition.
+#[derive(Default)]
+struct Partitions {
+    /// The partitions in the order they were first recorded.
+    order: Vec<u64>,
+    /// The entries for each partition in the order they were recorded.
+    entries: HashMap<u64, Vec<HistoryEntry>>,
+    /// The partition that each recorded build belongs to.
+    builds: HashMap<u64, u64>,
+}
+
+impl Partitions {
+    fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Gets the total number of entries.
+    fn len(&self) -> usize {
+        self.entries.values().map(|e| e.len()).sum()
+    }
+
+    fn all(&self) -> Vec<HistoryEntry> {
+        self.order
+            .iter()
+            .flat_map(|p| self.entries[p].iter().cloned())
+            .collect()
+    }
+
+    fn push(&mut self, entry: HistoryEntry) {
+        let partition = entry.build.partition;
+        self.builds.insert(entry.build.id, partition);
+        match self.entries.get_mut(&partition) {
+            Some(entries) => entries.push(entry),
+            None => {
+                self.order.push(partition);
+                self.entries.insert(partition, vec![entry]);
+            }
+        }
+    }
+
+    /// Removes the entries for the oldest builds in a partition
+    /// so that no more than `retention` builds are kept.
+    fn prune(&mut self, partition: u64, retention: usize) {
+        let entries = match self.entries.get_mut(&partition) {
+            Some(entries) => entries,
+            None => return,
+        };
+
+        let mut retained = HashSet::<u64>::new();
+        for entry in entries.iter().rev() {
+            if retained.len() == retention {
+                break;
+            }
+            retained.insert(entry.build.id);
+        }
+
+        let builds = &mut self.builds;
+        entries.retain(|e| {
+            if retained.contains(&e.build.id) {
+                return true;
+            }
+            builds.remove(&e.build.id);
+            false
+        });
+    }
+
+    /// Removes all entries that don't belong to any of the provided
+    /// collectors, together with any partitions left empty.
+    /// Returns whether or not anything was removed.
+    fn retain(&mut self, collectors: &HashSet<String>) -> bool {
+        let count = self.len();
+        let builds = &mut self.builds;
+        for entries in self.entries.values_mut() {
+            entries.retain(|e| {
+                if collectors.contains(&e.build.collector) {
+                    return true;
+                }
+                builds.remove(&e.build.id);
+                false
+            });
+        }
+        self.entries.retain(|_, entries| !entries.is_empty());
+        let entries = &self.entries;
+        self.order.retain(|p| entries.contains_key(p));
+        self.len() < count
+    }
+
+    fn prune_all(&mut self, retention: usize) {
+        for partition in self.order.clone() {
+            self.prune(partition, retention);
+        }
+    }
+
+    /// Gets the last recorded build for every partition.
+    fn get_latest_builds(&self) -> Vec<Build> {
+        self.order
+            .iter()
+            .filter_map(|p| self.entries[p].last())
+            .map(|e| e.build.clone())
+            .collect()
+    }
+}
+
+fn get_results(entries: &[HistoryEntry]) -> Vec<Build> {
+    // The same build is recorded every time it changes,
+    // so only keep the last recorded state of it.
+    let mut indices = HashMap::<u64, usize>::new();
+    let mut result = Vec::<Build>::new();
+    for entry in entries {
+        match indices.get(&entry.build.id) {
+            Some(index) => result[*index] = entry.build.clone(),
+            None => {
+                indices.insert(entry.build.id, result.len());
+                result.push(entry.build.clone());
+            }
+        }
+    }
+    result.sort_by_key(|b| b.started_at);
+    result
+}
+
+///////////////////////////////////////////////////////////
+// File storage
+
+struct FileStore {
+    path: PathBuf,
+    /// The number of entries appended since the file was last written.
+    appended: usize,
+}
+
+impl FileStore {
+    fn new(path: PathBuf) -> Self {
+        Self { path, appended: 0 }
+    }
+
+    fn load(&self) -> DuckResult<Vec<HistoryEntry>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(vec![]);
+        }
+
+        let mut result = Vec::new();
+        let reader = BufReader::new(File::open(&self.path)?);
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            // A line might be corrupt if Duck was terminated
+            // while writing to the file, so just skip it.
+            match serde_json::from_str::<HistoryRecord>(&line).map(|r| r.into_entry()) {
+                Ok(Ok(entry)) => result.push(entry),
+                Ok(Err(e)) => warn!("Skipping history entry on line {}: {}", index + 1, e),
+                Err(e) => warn!("Skipping history entry on line {}: {}", index + 1, e),
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn append(&mut self, entry: &HistoryEntry) -> DuckResult<()> {
+        let mut line = serde_json::to_string(&HistoryRecord::from(entry))?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        self.appended += 1;
+        Ok(())
+    }
+
+    /// Replaces the content of the file with the provided entries.
+    fn compact(&mut self, entries: &[HistoryEntry]) -> DuckResult<()> {
+        let mut content = String::new();
+        for entry in entries.iter() {
+            content.push_str(&serde_json::to_string(&HistoryRecord::from(entry))?);
+            content.push('\n');
+        }
+
+        // Write to a temporary file first so we don't
+        // lose the history if Duck is terminated.
+        let temp = PathBuf::from(format!("{}.tmp", self.path.display()));
+        std::fs::write(&temp, content)?;
+        std::fs::rename(&temp, &self.path)?;
+        self.appended = 0;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HistoryRecord {
+    recorded_at: i64,
+    origin: String,
+    build_id: String,
+    provider: String,
+    collector: String,
+    project_id: String,
+    project_name: String,
+    definition_id: String,
+    definition_name: String,
+    build_number: String,
+    status: BuildStatus,
+    branch: String,
+    url: String,
+    started_at: i64,
+    finished_at: Option<i64>,
+}
+
+impl From<&HistoryEntry> for HistoryRecord {
+    fn from(entry: &HistoryEntry) -> Self {
+        let build = &entry.build;
+        HistoryRecord {
+            recorded_at: entry.recorded_at,
+            origin: build.origin.clone(),
+            build_id: build.build_id.clone(),
+            provider: build.provider.clone(),
+            collector: build.collector.clone(),
+            project_id: build.project_id.clone(),
+            project_name: build.project_name.clone(),
+            definition_id: build.definition_id.clone(),
+            definition_name: build.definition_name.clone(),
+            build_number: build.build_number.clone(),
+            status: build.status.clone(),
+            branch: build.branch.clone(),
+            url: build.url.clone(),
+            started_at: build.started_at,
+            finished_at: build.finished_at,
+        }
+    }
+}
+
+impl HistoryRecord {
+    fn into_entry(self) -> DuckResult<HistoryEntry> {
+        let build = BuildBuilder::new()
+            .origin(self.origin)
+            .build_id(self.build_id)
+            .provider(self.provider)
+            .collector(self.collector)
+            .project_id(self.project_id)
+            .project_name(self.project_name)
+            .definition_id(self.definition_id)
+            .definition_name(self.definition_name)
+            .build_number(self.build_number)
+            .status(self.status)
+            .branch(self.branch)
+            .url(self.url)
+            .started_at(self.started_at)
+            .finished_at(self.finished_at)
+            .build()
+            .map_err(|e| format_err!("{}", e))?;
+
+        Ok(HistoryEntry {
+            recorded_at: self.recorded_at,
+            build,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "duck-history-{}-{}.jsonl",
+            name,
+            std::process::id()
+        ));
+        if path.exists() {
+            std::fs::remove_file(&path).unwrap();
+        }
+        path
+    }
+
+    fn get_storage(path: &PathBuf) -> Option<StorageConfiguration> {
+        Some(StorageConfiguration::File {
+            path: path.to_str().unwrap().to_owned(),
+            retention: None,
+        })
+    }
+
+    fn get_storage_with_retention(path: &PathBuf, retention: u16) -> Option<StorageConfiguration> {
+        Some(StorageConfiguration::File {
+            path: path.to_str().unwrap().to_owned(),
+            retention: Some(retention),
+        })
+    }
+
+    fn get_collectors() -> HashSet<String> {
+        let mut collectors = HashSet::new();
+        collectors.insert("collector".to_owned());
+        collectors
+    }
+
+    fn count_lines(path: &PathBuf) -> usize {
+        std::fs::read_to_string(path).unwrap().lines().count()
+    }
+
+    #[test]
+    fn should_record_history_without_storage() {
+        // Given
+        let repository = HistoryRepository::new();
+
+        // When
+        repository.record(&BuildBuilder::dummy().unwrap());
+        repository.record(&BuildBuilder::dummy().status(BuildStatus::Failed).unwrap());
+
+        // Then
+        let entries = repository.all();
+        assert_eq!(2, entries.len());
+        assert_eq!(BuildStatus::Failed, entries[1].build.status);
+    }
+
+    #[test]
+    fn should_restore_latest_builds_from_file() {
+        // Given
+        let path = get_temp_path("restore");
+        let repository = HistoryRepository::new();
+        repository
+            .configure(&get_storage(&path), &get_collectors())
+            .unwrap();
+        repository.record(&BuildBuilder::dummy().status(BuildStatus::Running).unwrap());
+        repository.record(&BuildBuilder::dummy().status(BuildStatus::Failed).unwrap());
+        repository.record(&BuildBuilder::dummy().branch("develop").unwrap());
+
+        // When
+        let restored = HistoryRepository::new();
+        let builds = restored
+            .configure(&get_storage(&path), &get_collectors())
+            .unwrap();
+
+        // Then
+        assert_eq!(3, restored.all().len());
+        assert_eq!(2, builds.len());
+        assert_eq!(BuildStatus::Failed, builds[0].status);
+        assert_eq!("develop", builds[1].branch);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_skip_corrupt_lines_when_loading_file() {
+        // Given
+        let path = get_temp_path("corrupt");
+        let repository = HistoryRepository::new();
+        repository
+            .configure(&get_storage(&path), &get_collectors())
+            .unwrap();
+        repository.record(&BuildBuilder::dummy().unwrap());
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"recorded_at\":15").unwrap();
+
+        // When
+        let restored = HistoryRepository::new();
+        let builds = restored
+            .configure(&get_storage(&path), &get_collectors())
+            .unwrap();
+
+        // Then
+        assert_eq!(1, builds.len());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_only_return_last_known_state_of_builds_in_partition() {
+        // Given
+        let repository = HistoryRepository::new();
+        let second = BuildBuilder::dummy().build_id("2").started_at(200);
+        let first = BuildBuilder::dummy().build_id("1").started_at(100);
+        repository.record(&second.status(BuildStatus::Running).unwrap());
+        repository.record(&first.status(BuildStatus::Failed).unwrap());
+        repository.record(&second.status(BuildStatus::Success).unwrap());
+        repository.record(&BuildBuilder::dummy().branch("develop").unwrap());
+
+        // When
+        let partition = repository.get_partition(first.unwrap().id).unwrap();
+        let results = repository.get_results(partition);
+
+        // Then
+        assert_eq!(2, results.len());
+        assert_eq!("1", results[0].build_id);
+        assert_eq!(BuildStatus::Failed, results[0].status);
+        assert_eq!("2", results[1].build_id);
+        assert_eq!(BuildStatus::Success, results[1].status);
+        assert_eq!(2, repository.get_all_results().len());
+    }
+
+    #[test]
+    fn should_not_restore_builds_when_configured_again() {
+        // Given
+        let path = get_temp_path("reconfigure");
+        let repository = HistoryRepository::new();
+        repository
+            .configure(&get_storage(&path), &get_collectors())
+            .unwrap();
+        repository.record(&BuildBuilder::dummy().unwrap());
+
+        // When
+        let builds = repository
+            .configure(&get_storage(&path), &get_collectors())
+            .unwrap();
+
+        // Then
+        assert!(builds.is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_remove_history_for_removed_collectors_when_configured_again() {
+        // Given
+        let path = get_temp_path("collectors");
+        let repository = HistoryRepository::new();
+        repository
+            .configure(&get_storage(&path), &get_collectors())
+            .unwrap();
+        repository.record(&BuildBuilder::dummy().unwrap());
+        repository.record(&BuildBuilder::dummy().collector("other").unwrap());
+
+        // When
+        repository
+            .configure(&get_storage(&path), &get_collectors())
+            .unwrap();
+
+        // Then
+        let entries = repository.all();
+        assert_eq!(1, entries.len());
+        assert_eq!("collector", entries[0].build.collector);
+        assert_eq!(1, count_lines(&path));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_only_retain_latest_builds_in_partition() {
+        // Given
+        let path = get_temp_path("retention");
+        let repository = HistoryRepository::new();
+        repository
+            .configure(&get_storage_with_retention(&path, 2), &get_collectors())
+            .unwrap();
+
+        // When
+        repository.record(&BuildBuilder::dummy().build_id("1").unwrap());
+        repository.record(&BuildBuilder::dummy().build_id("2").unwrap());
+        repository.record(&BuildBuilder::dummy().build_id("3").unwrap());
+        repository.record(&BuildBuilder::dummy().branch("develop").unwrap());
+
+        // Then
+        let entries = repository.all();
+        assert_eq!(3, entries.len());
+        assert_eq!("2", entries[0].build.build_id);
+        assert_eq!("3", entries[1].build.build_id);
+        assert_eq!("develop", entries[2].build.branch);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_compact_file_when_loading_expired_entries() {
+        // Given
+        let path = get_temp_path("compact");
+        let repository = HistoryRepository::new();
+        repository
+            .configure(&get_storage(&path), &get_collectors())
+            .unwrap();
+        repository.record(&BuildBuilder::dummy().build_id("1").unwrap());
+        repository.record(&BuildBuilder::dummy().build_id("2").unwrap());
+        repository.record(&BuildBuilder::dummy().build_id("3").unwrap());
+
+        // When
+        let restored = HistoryRepository::new();
+        restored
+            .configure(&get_storage_with_retention(&path, 1), &get_collectors())
+            .unwrap();
+
+        // Then
+        assert_eq!(1, restored.all().len());
+        assert_eq!("3", restored.all()[0].build.build_id);
+        assert_eq!(1, count_lines(&path));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_compact_file_when_it_contains_too_many_expired_entries() {
+        // Given
+        let path = get_temp_path("periodic");
+        let repository = HistoryRepository::new();
+        repository
+            .configure(&get_storage_with_retention(&path, 1), &get_collectors())
+            .unwrap();
+        repository.record(&BuildBuilder::dummy().build_id("1").unwrap());
+
+        // When
+        repository.record(&BuildBuilder::dummy().build_id("2").unwrap());
+
+        // Then
+        assert_eq!(1, count_lines(&path));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+use crate::builds::{Build, BuildStatus};
+
+/// Statistics for the builds in a partition.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BuildStatistics {
+    /// The number of finished builds.
+    pub builds: usize,
+    /// The ratio (0-1) of finished builds that succeeded.
+    pub success_rate: Option<f64>,
+    /// The mean duration of finished builds in seconds.
+    pub mean_duration: Option<i64>,
+    /// The 95th percentile duration of finished builds in seconds.
+    pub p95_duration: Option<i64>,
+    /// The mean time in seconds from a build
+    /// failing until a build succeeded again.
+    pub mean_time_to_recovery: Option<i64>,
+}
+
+impl BuildStatistics {
+    /// Calculates statistics for builds ordered by when they were started.
+    pub fn calculate(builds: &[Build]) -> Self {
+        // Only look at builds that either succeeded or failed.
+        let finished: Vec<&Build> = builds.iter().filter(|b| b.status.is_absolute()).collect();
+
+        let successful = finished
+            .iter()
+            .filter(|b| b.status == BuildStatus::Success)
+            .count();
+
+        let mut durations: Vec<i64> = finished
+            .iter()
+            .filter_map(|b| b.finished_at.map(|f| f - b.started_at))
+            .filter(|d| *d >= 0)
+            .collect();
+        durations.sort();
+
+        Self {
+            builds: finished.len(),
+            success_rate: if finished.is_empty() {
+                None
+            } else {
+                Some(successful as f64 / finished.len() as f64)
+            },
+            mean_duration: mean(&durations),
+            p95_duration: percentile(&durations, 95),
+            mean_time_to_recovery: mean(&get_recovery_times(&finished)),
+        }
+    }
+}
+
+fn get_recovery_times(builds: &[&Build]) -> Vec<i64> {
+    let mut result = Vec::new();
+    let mut failed_at: Option<i64> = None;
+    for build in builds.iter() {
+        let finished_at = build.finished_at.unwrap_or(build.started_at);
+        match build.status {
+            BuildStatus::Failed => {
+                if failed_at.is_none() {
+                    failed_at = Some(finished_at);
+                }
+            }
+            BuildStatus::Success => {
+                if let Some(failed_at) = failed_at.take() {
+                    result.push(finished_at - failed_at);
+                }
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+fn mean(values: &[i64]) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<i64>() / values.len() as i64)
+}
+
+fn percentile(sorted: &[i64], percentile: usize) -> Option<i64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    // Use the nearest rank method.
+    let rank = (percentile * sorted.len() + 99) / 100;
+    Some(sorted[rank.max(1) - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+
+    fn create_build(status: BuildStatus, started_at: i64, duration: i64) -> Build {
+        BuildBuilder::dummy()
+            .build_id(started_at.to_string())
+            .status(status)
+            .started_at(started_at)
+            .finished_at(Some(started_at + duration))
+            .unwrap()
+    }
+
+    #[test]
+    fn should_return_empty_statistics_if_there_are_no_finished_builds() {
+        // Given
+        let builds = vec![BuildBuilder::dummy()
+            .status(BuildStatus::Running)
+            .finished_at(None)
+            .unwrap()];
+
+        // When
+        let statistics = BuildStatistics::calculate(&builds);
+
+        // Then
+        assert_eq!(0, statistics.builds);
+        assert_eq!(None, statistics.success_rate);
+        assert_eq!(None, statistics.mean_duration);
+        assert_eq!(None, statistics.p95_duration);
+        assert_eq!(None, statistics.mean_time_to_recovery);
+    }
+
+    #[test]
+    fn should_calculate_success_rate() {
+        // Given
+        let builds = vec![
+            create_build(BuildStatus::Success, 100, 10),
+            create_build(BuildStatus::Failed, 200, 10),
+            create_build(BuildStatus::Canceled, 300, 10),
+            create_build(BuildStatus::Success, 400, 10),
+            create_build(BuildStatus::Success, 500, 10),
+        ];
+
+        // When
+        let statistics = BuildStatistics::calculate(&builds);
+
+        // Then
+        assert_eq!(4, statistics.builds);
+        assert_eq!(Some(0.75), statistics.success_rate);
+    }
+
+    #[test]
+    fn should_calculate_mean_and_p95_duration() {
+        // Given
+        let mut builds: Vec<Build> = (1..=20)
+            .map(|i| create_build(BuildStatus::Success, i * 100, i))
+            .collect();
+        builds.push(create_build(BuildStatus::Failed, 3000, 190));
+
+        // When
+        let statistics = BuildStatistics::calculate(&builds);
+
+        // Then
+        assert_eq!(Some(19), statistics.mean_duration);
+        assert_eq!(Some(20), statistics.p95_duration);
+    }
+
+    #[test]
+    fn should_calculate_mean_time_to_recovery() {
+        // Given
+        let builds = vec![
+            create_build(BuildStatus::Failed, 100, 10),
+            create_build(BuildStatus::Failed, 200, 10),
+            create_build(BuildStatus::Success, 300, 10),
+            create_build(BuildStatus::Failed, 400, 10),
+            create_build(BuildStatus::Success, 500, 10),
+            create_build(BuildStatus::Failed, 600, 10),
+        ];
+
+        // When
+        let statistics = BuildStatistics::calculate(&builds);
+
+        // Then
+        assert_eq!(Some(150), statistics.mean_time_to_recovery);
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::providers::collectors::push::PushInbox;
+
+/// Keeps track of the inboxes for collectors
+/// that receive builds pushed to them.
+pub struct IngestRepository {
+    inboxes: Mutex<HashMap<String, Arc<PushInbox>>>,
+}
+
+impl IngestRepository {
+    pub fn new() -> Self {
+        Self {
+            inboxes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replaces all registered inboxes. Builds in inboxes for collectors
+    /// that are still configured are moved to the new inbox.
+    pub fn set_inboxes(&self, inboxes: HashMap<String, Arc<PushInbox>>) {
+        let mut guard = self.inboxes.lock().unwrap();
+        for (id, inbox) in inboxes.iter() {
+            if let Some(previous) = guard.get(id) {
+                if !Arc::ptr_eq(previous, inbox) {
+                    inbox.take_from(previous);
+                }
+            }
+        }
+        *guard = inboxes;
+    }
+
+    pub fn get_inbox(&self, collector: &str) -> Option<Arc<PushInbox>> {
+        let guard = self.inboxes.lock().unwrap();
+        guard.get(collector).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+    use std::time::Duration;
+
+    #[test]
+    fn should_return_inbox_for_collector() {
+        // Given
+        let repository = IngestRepository::new();
+        let mut inboxes = HashMap::new();
+        inboxes.insert(
+            "push".to_owned(),
+            Arc::new(PushInbox::new("SECRET", Duration::from_secs(60))),
+        );
+        repository.set_inboxes(inboxes);
+
+        // When
+        let inbox = repository.get_inbox("push");
+
+        // Then
+        assert!(inbox.is_some());
+        assert!(repository.get_inbox("other").is_none());
+    }
+
+    #[test]
+    fn should_keep_pushed_builds_when_inboxes_are_replaced() {
+        // Given
+        let repository = IngestRepository::new();
+        let mut inboxes = HashMap::new();
+        inboxes.insert(
+            "push".to_owned(),
+            Arc::new(PushInbox::new("SECRET", Duration::from_secs(60))),
+        );
+        repository.set_inboxes(inboxes);
+        repository
+            .get_inbox("push")
+            .unwrap()
+            .push(BuildBuilder::dummy().unwrap());
+
+        // When
+        let mut inboxes = HashMap::new();
+        inboxes.insert(
+            "push".to_owned(),
+            Arc::new(PushInbox::new("SECRET", Duration::from_secs(60))),
+        );
+        repository.set_inboxes(inboxes);
+
+        // Then
+        let builds = repository.get_inbox("push").unwrap().get_builds();
+        assert_eq!(1, builds.len());
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+use std::sync::Mutex;
+
+use log::warn;
+
+/// Keeps track of the running collectors so that
+/// collection can be triggered without waiting for
+/// the collector's next scheduled update.
+pub struct TriggerRepository {
+    triggers: Mutex<HashMap<String, Sender<()>>>,
+}
+
+impl TriggerRepository {
+    pub fn new() -> Self {
+        Self {
+            triggers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn add(&self, collector: &str, trigger: Sender<()>) {
+        let mut triggers = self.triggers.lock().unwrap();
+        triggers.insert(collector.to_owned(), trigger);
+    }
+
+    pub fn clear(&self) {
+        let mut triggers = self.triggers.lock().unwrap();
+        triggers.clear();
+    }
+
+    /// Triggers an immediate collection for a collector.
+    /// Returns `false` if the collector isn't running.
+    pub fn trigger(&self, collector: &str) -> bool {
+        let triggers = self.triggers.lock().unwrap();
+        match triggers.get(collector) {
+            Some(trigger) => {
+                if let Err(e) = trigger.send(()) {
+                    warn!("Could not trigger collector '{}': {}", collector, e);
+                    return false;
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn should_trigger_collector() {
+        // Given
+        let repository = TriggerRepository::new();
+        let (sender, receiver) = channel::<()>();
+        repository.add("github", sender);
+
+        // When
+        let result = repository.trigger("github");
+
+        // Then
+        assert!(result);
+        assert!(receiver.try_recv().is_ok());
+    }
+
+    #[test]
+    fn should_not_trigger_unknown_collector() {
+        // Given
+        let repository = TriggerRepository::new();
+        let (sender, _receiver) = channel::<()>();
+        repository.add("github", sender);
+
+        // When
+        let result = repository.trigger("azure");
+
+        // Then
+        assert!(!result);
+    }
+}
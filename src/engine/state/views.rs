@@ -1,10 +1,56 @@
 use std::collections::HashSet;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
+use log::{error, trace};
+
+use crate::builds::Build;
 use crate::config::ViewConfiguration;
+use crate::filters::{BuildFilter, FilterResult};
+use crate::DuckResult;
+
+/// A view with its collectors and filter resolved.
+pub struct View {
+    config: ViewConfiguration,
+    collectors: Option<HashSet<String>>,
+    filter: BuildFilter,
+}
+
+impl View {
+    pub fn new(config: &ViewConfiguration) -> DuckResult<Self> {
+        Ok(Self {
+            config: config.clone(),
+            collectors: config
+                .collectors
+                .as_ref()
+                .map(|c| c.iter().cloned().collect()),
+            filter: BuildFilter::new(config.filter.clone())?,
+        })
+    }
+
+    /// Checks whether or not a build is part of the view.
+    pub fn includes(&self, build: &Build) -> bool {
+        if let Some(collectors) = &self.collectors {
+            if !collectors.contains(&build.collector) {
+                return false;
+            }
+        }
+        match self.filter.evaluate(build) {
+            FilterResult::Retain => true,
+            FilterResult::Filter => false,
+            FilterResult::Error(e) => {
+                trace!(
+                    "Could not filter build for view '{}': {}",
+                    self.config.id,
+                    e
+                );
+                false
+            }
+        }
+    }
+}
 
 pub struct ViewRepository {
-    views: Mutex<Vec<ViewConfiguration>>,
+    views: Mutex<Vec<Arc<View>>>,
 }
 
 impl ViewRepository {
@@ -18,32 +64,30 @@ impl ViewRepository {
         let mut guard = self.views.lock().unwrap();
         guard.clear();
         for view in views.iter() {
-            guard.push(view.clone());
+            // The configuration have already been validated,
+            // so this should never happen.
+            match View::new(view) {
+                Ok(view) => guard.push(Arc::new(view)),
+                Err(e) => error!("Could not add view '{}': {}", view.id, e),
+            }
         }
     }
 
-    pub fn get_collectors(&self, view_id: &str) -> Option<HashSet<String>> {
+    pub fn get_view(&self, view_id: &str) -> Option<Arc<View>> {
         let guard = self.views.lock().unwrap();
-        let view = guard.iter().find(|&x| x.id == view_id);
-        if let Some(view) = view {
-            let mut result = HashSet::<String>::new();
-            for collector in view.collectors.iter() {
-                result.insert(collector.clone());
-            }
-            return Some(result);
-        }
-        None
+        guard.iter().find(|&x| x.config.id == view_id).cloned()
     }
 
     pub fn get_views(&self) -> Vec<ViewConfiguration> {
         let guard = self.views.lock().unwrap();
-        guard.clone()
+        guard.iter().map(|v| v.config.clone()).collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::builds::{BuildBuilder, BuildStatus};
     use crate::config::Configuration;
     use crate::utils::text::TestVariableProvider;
 
@@ -92,12 +136,50 @@ mod tests {
         );
 
         // When
-        let collectors = repository.get_collectors("bar").unwrap();
+        let view = repository.get_view("bar").unwrap();
+
+        // Then
+        assert!(view.includes(&BuildBuilder::dummy().collector("b1").build().unwrap()));
+        assert!(view.includes(&BuildBuilder::dummy().collector("b2").build().unwrap()));
+        assert!(view.includes(&BuildBuilder::dummy().collector("b3").build().unwrap()));
+        assert!(!view.includes(&BuildBuilder::dummy().collector("a1").build().unwrap()));
+    }
+
+    #[test]
+    fn should_only_include_builds_matching_view_filter() {
+        // Given
+        let repository = ViewRepository::new();
+        repository.add_views(&[ViewConfiguration {
+            id: "failing".to_owned(),
+            name: "Failing master builds".to_owned(),
+            collectors: None,
+            filter: Some("branch == 'master' and status == 'failed'".to_owned()),
+        }]);
+
+        // When
+        let view = repository.get_view("failing").unwrap();
 
         // Then
-        assert_eq!(3, collectors.len());
-        assert!(collectors.contains("b1"));
-        assert!(collectors.contains("b2"));
-        assert!(collectors.contains("b3"));
+        assert!(view.includes(
+            &BuildBuilder::dummy()
+                .branch("master")
+                .status(BuildStatus::Failed)
+                .build()
+                .unwrap()
+        ));
+        assert!(!view.includes(
+            &BuildBuilder::dummy()
+                .branch("develop")
+                .status(BuildStatus::Failed)
+                .build()
+                .unwrap()
+        ));
+        assert!(!view.includes(
+            &BuildBuilder::dummy()
+                .branch("master")
+                .status(BuildStatus::Success)
+                .build()
+                .unwrap()
+        ));
     }
 }
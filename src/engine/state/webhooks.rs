@@ -0,0 +1,130 @@
+use std::sync::Mutex;
+
+use crate::config::{CollectorConfiguration, Configuration};
+
+/// A collector that can be triggered by an incoming webhook.
+#[derive(Clone)]
+pub struct WebhookTarget {
+    pub collector: String,
+    pub secret: String,
+    pub source: WebhookSource,
+}
+
+#[derive(Clone)]
+pub enum WebhookSource {
+    GitHub { owner: String, repository: String },
+    AzureDevOps { organization: String },
+    GitLab { projects: Vec<String> },
+}
+
+pub struct WebhookRepository {
+    targets: Mutex<Vec<WebhookTarget>>,
+}
+
+impl WebhookRepository {
+    pub fn new() -> Self {
+        Self {
+            targets: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Registers all enabled collectors that have a webhook secret.
+    pub fn configure(&self, config: &Configuration) {
+        let mut targets = self.targets.lock().unwrap();
+        targets.clear();
+        for collector in config.collectors.iter().filter(|c| c.is_enabled()) {
+            let (secret, source) = match collector {
+                CollectorConfiguration::GitHub(c) => (
+                    &c.webhook_secret,
+                    WebhookSource::GitHub {
+                        owner: c.owner.clone(),
+                        repository: c.repository.clone(),
+                    },
+                ),
+                CollectorConfiguration::Azure(c) => (
+                    &c.webhook_secret,
+                    WebhookSource::AzureDevOps {
+                        organization: c.organization.clone(),
+                    },
+                ),
+                CollectorConfiguration::GitLab(c) => (
+                    &c.webhook_secret,
+                    WebhookSource::GitLab {
+                        projects: c.projects.clone(),
+                    },
+                ),
+                _ => continue,
+            };
+            if let Some(secret) = secret {
+                targets.push(WebhookTarget {
+                    collector: collector.get_id().to_owned(),
+                    secret: secret.clone(),
+                    source,
+                });
+            }
+        }
+    }
+
+    pub fn get_targets(&self) -> Vec<WebhookTarget> {
+        let targets = self.targets.lock().unwrap();
+        targets.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    fn should_only_register_collectors_with_webhook_secret() {
+        // Given
+        let repository = WebhookRepository::new();
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "github": {
+                            "id": "github",
+                            "owner": "duckhq",
+                            "repository": "duck",
+                            "credentials": { "token": "SECRET" },
+                            "webhookSecret": "WEBHOOK"
+                        }
+                    },
+                    {
+                        "github": {
+                            "id": "github_nosecret",
+                            "owner": "duckhq",
+                            "repository": "duck",
+                            "credentials": { "token": "SECRET" }
+                        }
+                    },
+                    {
+                        "gitlab": {
+                            "id": "gitlab_disabled",
+                            "enabled": false,
+                            "credentials": "anonymous",
+                            "projects": [ "duckhq/duck" ],
+                            "branches": [ "master" ],
+                            "webhookSecret": "WEBHOOK"
+                        }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        // When
+        repository.configure(&config);
+
+        // Then
+        let targets = repository.get_targets();
+        assert_eq!(1, targets.len());
+        assert_eq!("github", targets[0].collector);
+        assert_eq!("WEBHOOK", targets[0].secret);
+    }
+}
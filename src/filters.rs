@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
 use crate::builds::{Build, BuildStatus};
 use crate::query;
-use crate::query::{Constant, Expression, Operator, Property, Visitor};
+use crate::query::{Constant, Expression, Function, Operator, Property, Visitor};
 use crate::DuckResult;
 
 pub enum FilterResult {
@@ -19,16 +23,21 @@ pub struct BuildFilter {
 
 impl BuildFilter {
     pub fn new(expression: Option<String>) -> DuckResult<Self> {
+        let expression = match expression {
+            Some(expression) => {
+                let expression = query::parse(expression)?;
+                BuildFilterValidator::validate(&expression)?;
+                Some(expression)
+            }
+            None => None,
+        };
+        let mut evaluator = FilterEvaluator::<Build>::new();
+        if let Some(expression) = &expression {
+            evaluator.patterns = Patterns::compile(expression);
+        }
         Ok(Self {
-            expression: match expression {
-                Some(expression) => {
-                    let expression = query::parse(expression)?;
-                    BuildFilterValidator::validate(&expression)?;
-                    Some(expression)
-                }
-                None => None,
-            },
-            evaluator: FilterEvaluator::<Build>::new(),
+            expression,
+            evaluator,
         })
     }
 
@@ -54,6 +63,65 @@ impl BuildFilter {
         }
         FilterResult::Retain
     }
+
+    /// Explains the outcome of a filter by evaluating
+    /// each condition in the expression on its own.
+    pub fn explain(&self, build: &Build) -> Vec<String> {
+        let mut result = Vec::new();
+        if let Some(expression) = &self.expression {
+            self.explain_expression(expression, build, &mut result);
+        }
+        result
+    }
+
+    fn explain_expression(&self, expression: &Expression, build: &Build, result: &mut Vec<String>) {
+        match expression {
+            Expression::And(lhs, rhs) | Expression::Or(lhs, rhs) => {
+                self.explain_expression(lhs, build, result);
+                self.explain_expression(rhs, build, result);
+            }
+            Expression::Not(expression) | Expression::Scope(expression) => {
+                self.explain_expression(expression, build, result)
+            }
+            Expression::Relational(lhs, rhs, _) => {
+                // Show the actual values that were compared.
+                let values: Vec<String> = [lhs, rhs]
+                    .iter()
+                    .filter(|o| match o.as_ref() {
+                        Expression::Property(_) | Expression::Function(_, _) => true,
+                        _ => false,
+                    })
+                    .filter_map(|o| match o.accept(build, &self.evaluator) {
+                        Ok(value) => Some(format!("{} = {}", o, value)),
+                        Err(_) => None,
+                    })
+                    .collect();
+                let outcome = self.get_outcome(expression, build);
+                if values.is_empty() {
+                    result.push(format!("{} is {}", expression, outcome));
+                } else {
+                    result.push(format!(
+                        "{} is {} ({})",
+                        expression,
+                        outcome,
+                        values.join(", ")
+                    ));
+                }
+            }
+            _ => result.push(format!(
+                "{} is {}",
+                expression,
+                self.get_outcome(expression, build)
+            )),
+        }
+    }
+
+    fn get_outcome(&self, expression: &Expression, build: &Build) -> String {
+        match expression.accept(build, &self.evaluator) {
+            Ok(value) => value.to_string(),
+            Err(e) => format!("an error: {}", e),
+        }
+    }
 }
 
 ///////////////////////////////////////////////////////////
@@ -85,6 +153,14 @@ impl FilterEvaluatorContext for BuildFilterValidator {
             Property::Build => Constant::String("".to_owned()),
             Property::Collector => Constant::String("".to_owned()),
             Property::Provider => Constant::String("".to_owned()),
+            Property::ProjectName => Constant::String("".to_owned()),
+            Property::DefinitionName => Constant::String("".to_owned()),
+            Property::BuildNumber => Constant::String("".to_owned()),
+            Property::Origin => Constant::String("".to_owned()),
+            Property::Url => Constant::String("".to_owned()),
+            Property::StartedAt => Constant::Integer(0),
+            Property::FinishedAt => Constant::Integer(0),
+            Property::Duration => Constant::Integer(0),
         })
     }
 }
@@ -99,6 +175,21 @@ impl FilterEvaluatorContext for Build {
             Property::Build => Constant::String(self.build_id.clone()),
             Property::Collector => Constant::String(self.collector.clone()),
             Property::Provider => Constant::String(self.provider.clone()),
+            Property::ProjectName => Constant::String(self.project_name.clone()),
+            Property::DefinitionName => Constant::String(self.definition_name.clone()),
+            Property::BuildNumber => Constant::String(self.build_number.clone()),
+            Property::Origin => Constant::String(self.origin.clone()),
+            Property::Url => Constant::String(self.url.clone()),
+            Property::StartedAt => Constant::Integer(self.started_at),
+            Property::FinishedAt => match self.finished_at {
+                Some(finished_at) => Constant::Integer(finished_at),
+                None => Constant::Null,
+            },
+            Property::Duration => {
+                // Running builds report how long they've been running so far.
+                let finished_at = self.finished_at.unwrap_or_else(get_now);
+                Constant::Integer(finished_at - self.started_at)
+            }
         })
     }
 }
@@ -107,17 +198,77 @@ impl FilterEvaluatorContext for Build {
 // Evaluator
 
 struct FilterEvaluator<T: FilterEvaluatorContext> {
+    patterns: Patterns,
     _p: std::marker::PhantomData<T>,
 }
 
 impl<T: FilterEvaluatorContext> FilterEvaluator<T> {
     pub fn new() -> Self {
         Self {
+            patterns: Patterns::default(),
             _p: std::marker::PhantomData,
         }
     }
 }
 
+/// The compiled patterns used by `like` and `matches`, so that
+/// constant patterns aren't compiled again for every build.
+#[derive(Default)]
+struct Patterns {
+    globs: HashMap<String, Regex>,
+    regexes: HashMap<String, Regex>,
+}
+
+impl Patterns {
+    fn compile(expression: &Expression) -> Self {
+        let mut patterns = Patterns::default();
+        patterns.collect(expression);
+        patterns
+    }
+
+    fn collect(&mut self, expression: &Expression) {
+        match expression {
+            Expression::And(lhs, rhs) | Expression::Or(lhs, rhs) => {
+                self.collect(lhs);
+                self.collect(rhs);
+            }
+            Expression::Not(expression) | Expression::Scope(expression) => self.collect(expression),
+            Expression::Relational(_, rhs, operator) => {
+                if let Expression::Constant(Constant::String(pattern)) = rhs.as_ref() {
+                    match operator {
+                        Operator::Like => {
+                            if let Ok(regex) = create_glob(pattern) {
+                                self.globs.insert(pattern.clone(), regex);
+                            }
+                        }
+                        Operator::Matches => {
+                            if let Ok(regex) = create_regex(pattern) {
+                                self.regexes.insert(pattern.clone(), regex);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn is_glob_match(&self, pattern: &str, value: &str) -> DuckResult<bool> {
+        match self.globs.get(pattern) {
+            Some(regex) => Ok(regex.is_match(value)),
+            None => Ok(create_glob(pattern)?.is_match(value)),
+        }
+    }
+
+    fn is_regex_match(&self, pattern: &str, value: &str) -> DuckResult<bool> {
+        match self.regexes.get(pattern) {
+            Some(regex) => Ok(regex.is_match(value)),
+            None => Ok(create_regex(pattern)?.is_match(value)),
+        }
+    }
+}
+
 impl<T: FilterEvaluatorContext> Visitor<T, Constant> for FilterEvaluator<T> {
     fn or(&self, ctx: &T, left: &Expression, right: &Expression) -> DuckResult<Constant> {
         let left = left.accept(ctx, self)?;
@@ -156,6 +307,22 @@ impl<T: FilterEvaluatorContext> Visitor<T, Constant> for FilterEvaluator<T> {
         Ok(exp.accept(ctx, self)?)
     }
 
+    fn function(&self, ctx: &T, function: &Function, args: &[Expression]) -> DuckResult<Constant> {
+        let args = args
+            .iter()
+            .map(|arg| arg.accept(ctx, self))
+            .collect::<DuckResult<Vec<Constant>>>()?;
+
+        match function {
+            Function::Age => match &args[..] {
+                [Constant::Integer(timestamp)] => Ok(Constant::Integer(get_now() - timestamp)),
+                [Constant::Null] => Ok(Constant::Null),
+                _ => Err(format_err!("Mismatched types in 'age' function")),
+            },
+            Function::Now => Ok(Constant::Integer(get_now())),
+        }
+    }
+
     fn relational(
         &self,
         ctx: &T,
@@ -166,6 +333,16 @@ impl<T: FilterEvaluatorContext> Visitor<T, Constant> for FilterEvaluator<T> {
         let left = left.accept(ctx, self)?;
         let right = right.accept(ctx, self)?;
 
+        // Missing values (such as the finish time of a running build)
+        // are only equal to other missing values.
+        if left == Constant::Null || right == Constant::Null {
+            return match operator {
+                Operator::EqualTo => Ok(Constant::Boolean(left == right)),
+                Operator::NotEqualTo => Ok(Constant::Boolean(left != right)),
+                _ => Ok(Constant::Boolean(false)),
+            };
+        }
+
         match operator {
             Operator::EqualTo => match (left, right) {
                 (Constant::Integer(lhs), Constant::Integer(rhs)) => {
@@ -213,10 +390,81 @@ impl<T: FilterEvaluatorContext> Visitor<T, Constant> for FilterEvaluator<T> {
                 }
                 _ => Err(format_err!("Mismatched types in '<=' expression")),
             },
+            Operator::Like => match (left, right) {
+                (Constant::String(lhs), Constant::String(rhs)) => {
+                    Ok(Constant::Boolean(self.patterns.is_glob_match(&rhs, &lhs)?))
+                }
+                _ => Err(format_err!("Mismatched types in 'like' expression")),
+            },
+            Operator::Matches => match (left, right) {
+                (Constant::String(lhs), Constant::String(rhs)) => {
+                    Ok(Constant::Boolean(self.patterns.is_regex_match(&rhs, &lhs)?))
+                }
+                _ => Err(format_err!("Mismatched types in 'matches' expression")),
+            },
+            Operator::In => match (left, right) {
+                (lhs, Constant::List(items)) => {
+                    let mut result = false;
+                    for item in items.iter() {
+                        result |= is_equal(&lhs, item)
+                            .ok_or_else(|| format_err!("Mismatched types in 'in' expression"))?;
+                    }
+                    Ok(Constant::Boolean(result))
+                }
+                _ => Err(format_err!("Mismatched types in 'in' expression")),
+            },
+            Operator::StartsWith => match (left, right) {
+                (Constant::String(lhs), Constant::String(rhs)) => {
+                    Ok(Constant::Boolean(lhs.starts_with(&rhs[..])))
+                }
+                _ => Err(format_err!("Mismatched types in 'startswith' expression")),
+            },
+            Operator::Contains => match (left, right) {
+                (Constant::String(lhs), Constant::String(rhs)) => {
+                    Ok(Constant::Boolean(lhs.contains(&rhs[..])))
+                }
+                _ => Err(format_err!("Mismatched types in 'contains' expression")),
+            },
         }
     }
 }
 
+fn get_now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+/// Compares two constants of the same type.
+/// Returns `None` if the types differ.
+fn is_equal(lhs: &Constant, rhs: &Constant) -> Option<bool> {
+    match (lhs, rhs) {
+        (Constant::Integer(lhs), Constant::Integer(rhs)) => Some(lhs == rhs),
+        (Constant::Boolean(lhs), Constant::Boolean(rhs)) => Some(lhs == rhs),
+        (Constant::String(lhs), Constant::String(rhs)) => Some(lhs == rhs),
+        (Constant::Status(lhs), Constant::Status(rhs)) => Some(lhs == rhs),
+        _ => None,
+    }
+}
+
+/// Creates a regular expression from a glob pattern where
+/// `*` matches any sequence of characters and `?` matches
+/// a single character.
+fn create_glob(pattern: &str) -> DuckResult<Regex> {
+    let mut expression = String::from("^");
+    for character in pattern.chars() {
+        match character {
+            '*' => expression.push_str(".*"),
+            '?' => expression.push('.'),
+            _ => expression.push_str(&regex::escape(&character.to_string())),
+        }
+    }
+    expression.push('$');
+    Ok(Regex::new(&expression)?)
+}
+
+fn create_regex(pattern: &str) -> DuckResult<Regex> {
+    Regex::new(pattern).map_err(|e| format_err!("Invalid regular expression '{}': {}", pattern, e))
+}
+
 ///////////////////////////////////////////////////////////
 // Tests
 
@@ -253,6 +501,19 @@ mod tests {
     #[test_case("false or true", Constant::Boolean(true) ; "or_4")]
     #[test_case("(1 > 2) or (2 > 1)", Constant::Boolean(true) ; "scoped_1")]
     #[test_case("(1 > 2) and (2 > 1)", Constant::Boolean(false) ; "scoped_2")]
+    #[test_case("'release/1.0' like 'release/*'", Constant::Boolean(true) ; "like_1")]
+    #[test_case("'hotfix/1.0' like 'release/*'", Constant::Boolean(false) ; "like_2")]
+    #[test_case("'v1.0' like 'v?.?'", Constant::Boolean(true) ; "like_3")]
+    #[test_case("'v1x0' like 'v1.0'", Constant::Boolean(false) ; "like_4")]
+    #[test_case("'feature/foo' matches '^feature/.+'", Constant::Boolean(true) ; "matches_1")]
+    #[test_case("'feature/' matches '^feature/.+'", Constant::Boolean(false) ; "matches_2")]
+    #[test_case("2 in [1, 2, 3]", Constant::Boolean(true) ; "in_1")]
+    #[test_case("4 in [1, 2, 3]", Constant::Boolean(false) ; "in_2")]
+    #[test_case("'a' in []", Constant::Boolean(false) ; "in_3")]
+    #[test_case("'release/1.0' startswith 'release/'", Constant::Boolean(true) ; "startswith_1")]
+    #[test_case("'hotfix/1.0' startswith 'release/'", Constant::Boolean(false) ; "startswith_2")]
+    #[test_case("'feature/duck-123' contains 'duck'", Constant::Boolean(true) ; "contains_1")]
+    #[test_case("'feature/goose-123' contains 'duck'", Constant::Boolean(false) ; "contains_2")]
     fn should_evaluate_expression(expression: &str, expected: Constant) {
         // Given
         let build = BuildBuilder::dummy().build().unwrap();
@@ -273,6 +534,21 @@ mod tests {
     #[test_case("build == '123'", Constant::Boolean(true))]
     #[test_case("collector == 'test'", Constant::Boolean(true))]
     #[test_case("provider == 'TeamCity'", Constant::Boolean(true))]
+    #[test_case("branch in ['master', 'develop']", Constant::Boolean(true))]
+    #[test_case("status in ['failed', 'queued']", Constant::Boolean(true))]
+    #[test_case("branch like 'dev*'", Constant::Boolean(true))]
+    #[test_case("project_name == 'Foo'", Constant::Boolean(true))]
+    #[test_case("definition_name == 'Bar'", Constant::Boolean(true))]
+    #[test_case("build_number == '1.2.3'", Constant::Boolean(true))]
+    #[test_case("origin == 'https://ci'", Constant::Boolean(true))]
+    #[test_case("url startswith 'https://ci/'", Constant::Boolean(true))]
+    #[test_case("started_at == 1578819921", Constant::Boolean(true))]
+    #[test_case("finished_at == 1578822921", Constant::Boolean(true))]
+    #[test_case("duration > 30m", Constant::Boolean(true))]
+    #[test_case("duration == 3000", Constant::Boolean(true))]
+    #[test_case("age(finished_at) > 1h", Constant::Boolean(true))]
+    #[test_case("age(started_at) > age(finished_at)", Constant::Boolean(true))]
+    #[test_case("finished_at < now()", Constant::Boolean(true))]
     fn should_evaluate_expression_with_property(expression: &str, expected: Constant) {
         // Given
         let evaluator = FilterEvaluator::<Build>::new();
@@ -283,8 +559,112 @@ mod tests {
             .provider("TeamCity")
             .status(BuildStatus::Queued)
             .project_id("foo")
+            .project_name("Foo")
             .definition_id("bar")
+            .definition_name("Bar")
             .build_id("123")
+            .build_number("1.2.3")
+            .origin("https://ci")
+            .url("https://ci/builds/123")
+            .started_at(1578819921)
+            .finished_at(Some(1578822921))
+            .build()
+            .unwrap();
+
+        // When
+        let result = expression.accept(&build, &evaluator).unwrap();
+
+        // Then
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn should_explain_filter() {
+        // Given
+        let filter = BuildFilter::new(Some(
+            "branch == 'master' and (status == 'failed' or duration > 30m)".to_owned(),
+        ))
+        .unwrap();
+        let build = BuildBuilder::dummy()
+            .branch("master")
+            .status(BuildStatus::Success)
+            .started_at(1000)
+            .finished_at(Some(1600))
+            .build()
+            .unwrap();
+
+        // When
+        let result = filter.explain(&build);
+
+        // Then
+        assert_eq!(3, result.len());
+        assert_eq!("branch == 'master' is true (branch = 'master')", result[0]);
+        assert_eq!(
+            "status == 'failed' is false (status = 'success')",
+            result[1]
+        );
+        assert_eq!("duration > 1800 is false (duration = 600)", result[2]);
+    }
+
+    #[test_case("age(branch) > 3", "Mismatched types in 'age' function" ; "age")]
+    #[test_case("duration > '30m'", "Mismatched types in '>' expression" ; "duration")]
+    #[test_case("branch like 3", "Mismatched types in 'like' expression" ; "like")]
+    #[test_case("branch matches 3", "Mismatched types in 'matches' expression" ; "matches")]
+    #[test_case("branch in 'master'", "Mismatched types in 'in' expression" ; "in_without_list")]
+    #[test_case("branch in [1, 2]", "Mismatched types in 'in' expression" ; "in_with_mismatched_list")]
+    #[test_case("status startswith 'fail'", "Mismatched types in 'startswith' expression" ; "startswith")]
+    #[test_case("status contains 'fail'", "Mismatched types in 'contains' expression" ; "contains")]
+    fn should_return_error_if_filter_has_mismatched_types(expression: &str, expected: &str) {
+        // Given, When
+        let result = BuildFilter::new(Some(expression.to_owned()));
+
+        // Then
+        match result {
+            Ok(_) => panic!("Expected filter to be invalid"),
+            Err(e) => assert!(format!("{}", e).starts_with(expected)),
+        }
+    }
+
+    #[test]
+    fn should_return_error_with_location_if_regular_expression_is_invalid() {
+        // Given, When
+        let result = BuildFilter::new(Some("branch matches '['".to_owned()));
+
+        // Then
+        let error = format!("{}", result.err().unwrap());
+        assert!(error.starts_with("Error parsing expression: Invalid regular expression '['\n"));
+        assert!(error.ends_with("|                ^^^"));
+    }
+
+    #[test]
+    fn should_compile_patterns_when_filter_is_created() {
+        // Given, When
+        let filter = BuildFilter::new(Some(
+            "branch like 'feature/*' and (not definition_name matches '^ci-')".to_owned(),
+        ))
+        .unwrap();
+
+        // Then
+        assert!(filter.evaluator.patterns.globs.contains_key("feature/*"));
+        assert!(filter.evaluator.patterns.regexes.contains_key("^ci-"));
+        assert!(matches!(
+            filter.evaluate(&BuildBuilder::dummy().branch("feature/foo").unwrap()),
+            FilterResult::Retain
+        ));
+    }
+
+    #[test_case("finished_at == 3", Constant::Boolean(false) ; "equal_to")]
+    #[test_case("finished_at != 3", Constant::Boolean(true) ; "not_equal_to")]
+    #[test_case("finished_at > 3", Constant::Boolean(false) ; "greater_than")]
+    #[test_case("age(finished_at) < 1h", Constant::Boolean(false) ; "age")]
+    #[test_case("duration > 0", Constant::Boolean(true) ; "duration")]
+    fn should_evaluate_expression_for_running_build(expression: &str, expected: Constant) {
+        // Given
+        let evaluator = FilterEvaluator::<Build>::new();
+        let expression = query::parse(expression).unwrap();
+        let build = BuildBuilder::dummy()
+            .status(BuildStatus::Running)
+            .finished_at(None)
             .build()
             .unwrap();
 
@@ -13,10 +13,13 @@ extern crate derive_builder;
 use std::path::PathBuf;
 
 use failure::Error;
-use log::info;
+use log::{info, warn};
 
+use crate::api::models::BuildViewModel;
+use crate::builds::Build;
 use crate::config::loader::JsonConfigurationLoader;
 use crate::config::ConfigurationLoader;
+use crate::filters::{BuildFilter, FilterResult};
 use crate::utils::text::EnvironmentVariableProvider;
 
 pub type DuckResult<T> = Result<T, Error>;
@@ -94,3 +97,81 @@ pub fn validate_config<T: Into<PathBuf>>(config_path: T) -> DuckResult<()> {
     loader.load(&EnvironmentVariableProvider::new())?;
     Ok(())
 }
+
+///////////////////////////////////////////////////////////
+// Query
+
+/// The builds to evaluate a query against.
+pub enum QuerySource {
+    /// Collect builds once using the collectors in a configuration file.
+    Configuration(PathBuf),
+    /// Read builds from a JSON file, such as the response from the builds API.
+    File(PathBuf),
+}
+
+pub struct QueryResult {
+    pub build: String,
+    pub matched: bool,
+    pub reasons: Vec<String>,
+}
+
+pub fn query<T: Into<String>>(expression: T, source: QuerySource) -> DuckResult<Vec<QueryResult>> {
+    let filter = BuildFilter::new(Some(expression.into()))?;
+    let builds = match source {
+        QuerySource::Configuration(path) => collect_builds(path)?,
+        QuerySource::File(path) => read_builds(path)?,
+    };
+
+    Ok(builds
+        .iter()
+        .map(|build| QueryResult {
+            build: format!(
+                "[{}] {}::{} #{} ({}) {}",
+                build.collector,
+                build.project_name,
+                build.definition_name,
+                build.build_number,
+                build.branch,
+                build.status
+            ),
+            matched: match filter.evaluate(build) {
+                FilterResult::Retain => true,
+                FilterResult::Filter | FilterResult::Error(_) => false,
+            },
+            reasons: filter.explain(build),
+        })
+        .collect())
+}
+
+fn collect_builds(config_path: PathBuf) -> DuckResult<Vec<Build>> {
+    let loader = JsonConfigurationLoader::new(config_path);
+    let config = loader.load(&EnvironmentVariableProvider::new())?;
+
+    let (_signaler, listener) = waithandle::new();
+    let mut builds = Vec::new();
+    for collector in providers::create_collectors(&config)?.iter() {
+        info!("Collecting builds from '{}'...", collector.info().id);
+        if let Err(e) = collector.collect(listener.clone(), &mut |build| builds.push(build)) {
+            warn!(
+                "Could not collect builds from '{}': {}",
+                collector.info().id,
+                e
+            );
+        }
+    }
+
+    Ok(builds)
+}
+
+fn read_builds(path: PathBuf) -> DuckResult<Vec<Build>> {
+    let json = std::fs::read_to_string(&path)?;
+    let models: Vec<BuildViewModel> = serde_json::from_str(&json)?;
+    let mut builds = Vec::new();
+    for (index, model) in models.iter().enumerate() {
+        match model.to_build() {
+            Ok(build) => builds.push(build),
+            Err(e) => return Err(format_err!("Build at index {} is invalid: {}", index, e)),
+        }
+    }
+    Ok(builds)
+}
@@ -48,6 +48,8 @@ enum Command {
     Schema(commands::schema::Arguments),
     /// Validates the Duck configuration
     Validate(commands::validate::Arguments),
+    /// Evaluates a filter expression against builds
+    Query(commands::query::Arguments),
     /// Starts Duck as a Windows service
     #[cfg(windows)]
     #[structopt(setting = structopt::clap::AppSettings::Hidden)]
@@ -68,6 +70,7 @@ impl Command {
             Command::Start(_) => true,
             Command::Schema(_) => false,
             Command::Validate(_) => false,
+            Command::Query(_) => false,
             #[cfg(windows)]
             Command::Service => false,
             #[cfg(windows)]
@@ -99,6 +102,7 @@ async fn main() {
         Command::Start(args) => commands::start::execute(args).await,
         Command::Schema(args) => commands::schema::execute(args),
         Command::Validate(args) => commands::validate::execute(args),
+        Command::Query(args) => commands::query::execute(args),
         #[cfg(windows)]
         Command::Service => commands::service::start(),
         #[cfg(windows)]
@@ -28,11 +28,16 @@ pub fn create_collectors(config: &Configuration) -> DuckResult<Vec<Box<dyn Colle
 fn get_collector_loader(config: &CollectorConfiguration) -> Box<&dyn CollectorLoader> {
     match config {
         CollectorConfiguration::TeamCity(config) => Box::new(config),
+        CollectorConfiguration::Jenkins(config) => Box::new(config),
         CollectorConfiguration::Azure(config) => Box::new(config),
         CollectorConfiguration::GitHub(config) => Box::new(config),
+        CollectorConfiguration::Bitbucket(config) => Box::new(config),
+        CollectorConfiguration::CircleCi(config) => Box::new(config),
+        CollectorConfiguration::GitLab(config) => Box::new(config),
         CollectorConfiguration::OctopusDeploy(config) => Box::new(config),
         CollectorConfiguration::AppVeyor(config) => Box::new(config),
         CollectorConfiguration::Duck(config) => Box::new(config),
+        CollectorConfiguration::Push(config) => Box::new(config),
         CollectorConfiguration::Debugger(config) => Box::new(config),
     }
 }
@@ -71,5 +76,7 @@ fn get_observer_loader(config: &ObserverConfiguration) -> Box<&dyn ObserverLoade
         ObserverConfiguration::Hue(config) => Box::new(config),
         ObserverConfiguration::Mattermost(config) => Box::new(config),
         ObserverConfiguration::Slack(config) => Box::new(config),
+        ObserverConfiguration::Teams(config) => Box::new(config),
+        ObserverConfiguration::Webhook(config) => Box::new(config),
     }
 }
@@ -1,14 +1,23 @@
+use std::sync::Arc;
+
 use waithandle::WaitHandleListener;
 
 use crate::builds::Build;
 use crate::DuckResult;
 
+use self::push::PushInbox;
+
 mod appveyor;
 mod azure;
+mod bitbucket;
+mod circleci;
 mod debugger;
 mod duck;
 mod github;
+mod gitlab;
+mod jenkins;
 mod octopus;
+pub mod push;
 mod teamcity;
 
 pub trait CollectorLoader {
@@ -22,10 +31,17 @@ pub trait Collector: Send {
         handle: WaitHandleListener,
         callback: &mut dyn FnMut(Build),
     ) -> DuckResult<()>;
+
+    /// Gets the inbox for collectors that receive
+    /// builds pushed to them instead of polling.
+    fn inbox(&self) -> Option<Arc<PushInbox>> {
+        None
+    }
 }
 
 pub struct CollectorInfo {
     pub id: String,
     pub enabled: bool,
     pub provider: String,
+    pub interval: Option<u16>,
 }
@@ -1,4 +1,6 @@
 use log::{trace, warn};
+use serde::de::DeserializeOwned;
+use url::form_urlencoded::byte_serialize;
 
 use crate::builds::BuildStatus;
 use crate::config::{AppVeyorConfiguration, AppVeyorCredentials};
@@ -6,6 +8,8 @@ use crate::utils::date;
 use crate::utils::http::*;
 use crate::DuckResult;
 
+static API_URL: &str = "https://ci.appveyor.com/api/";
+
 pub struct AppVeyorClient {
     credentials: AppVeyorCredentials,
 }
@@ -17,6 +21,13 @@ impl AppVeyorClient {
         }
     }
 
+    /// Gets all projects that the credentials have access to.
+    pub fn get_projects(&self, client: &impl HttpClient) -> DuckResult<Vec<AppVeyorProject>> {
+        let url = format!("{api}projects", api = API_URL);
+        self.send_get_request(client, url)
+    }
+
+    /// Gets the build history for a project.
     pub fn get_builds(
         &self,
         client: &impl HttpClient,
@@ -25,12 +36,38 @@ impl AppVeyorClient {
         count: u16,
     ) -> DuckResult<AppVeyorResponse> {
         let url = format!(
-            "https://ci.appveyor.com/api/projects/{account}/{project}/history?recordsNumber={count}",
+            "{api}projects/{account}/{project}/history?recordsNumber={count}",
+            api = API_URL,
             account = account,
             project = project,
             count = count
         );
+        self.send_get_request(client, url)
+    }
+
+    /// Gets the latest build for a branch in a project.
+    pub fn get_branch_build(
+        &self,
+        client: &impl HttpClient,
+        account: &str,
+        project: &str,
+        branch: &str,
+    ) -> DuckResult<AppVeyorBranchResponse> {
+        let url = format!(
+            "{api}projects/{account}/{project}/branch/{branch}",
+            api = API_URL,
+            account = account,
+            project = project,
+            branch = encode(branch)
+        );
+        self.send_get_request(client, url)
+    }
 
+    fn send_get_request<T: DeserializeOwned>(
+        &self,
+        client: &impl HttpClient,
+        url: String,
+    ) -> DuckResult<T> {
         trace!("Sending request to: {}", url);
         let mut builder = HttpRequestBuilder::get(&url);
         builder.add_header("Content-Type", "application/json");
@@ -64,12 +101,22 @@ impl AppVeyorCredentials {
     }
 }
 
+fn encode(value: &str) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
+
 #[derive(Deserialize, Debug)]
 pub struct AppVeyorResponse {
     pub project: AppVeyorProject,
     pub builds: Vec<AppVeyorBuild>,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct AppVeyorBranchResponse {
+    pub project: AppVeyorProject,
+    pub build: AppVeyorBuild,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct AppVeyorProject {
     #[serde(alias = "accountId")]
@@ -80,6 +127,7 @@ pub struct AppVeyorProject {
     pub project_id: u64,
     #[serde(alias = "name")]
     pub project_name: String,
+    pub slug: String,
     #[serde(alias = "repositoryName")]
     pub repository_name: String,
 }
@@ -1,3 +1,4 @@
+use log::warn;
 use waithandle::WaitHandleListener;
 
 use crate::builds::{Build, BuildBuilder};
@@ -22,7 +23,8 @@ pub struct AppVeyorCollector<T: HttpClient + Default> {
     http: T,
     client: AppVeyorClient,
     account: String,
-    project: String,
+    project: Option<String>,
+    branches: Option<Vec<String>>,
     count: u16,
 }
 
@@ -33,6 +35,7 @@ impl<T: HttpClient + Default> AppVeyorCollector<T> {
             client: AppVeyorClient::new(config),
             account: config.account.clone(),
             project: config.project.clone(),
+            branches: config.branches.clone(),
             count: config.get_count(),
             info: CollectorInfo {
                 id: config.id.clone(),
@@ -41,6 +44,7 @@ impl<T: HttpClient + Default> AppVeyorCollector<T> {
                     Option::Some(e) => e,
                 },
                 provider: "AppVeyor".to_owned(),
+                interval: config.interval,
             },
         }
     }
@@ -61,52 +65,110 @@ impl<T: HttpClient + Default> Collector for AppVeyorCollector<T> {
         listener: WaitHandleListener,
         callback: &mut dyn FnMut(Build),
     ) -> DuckResult<()> {
-        let result =
-            self.client
-                .get_builds(&self.http, &self.account, &self.project, self.count)?;
-
-        for (count, build) in result.builds.iter().enumerate() {
+        for project in self.get_projects()?.iter() {
             if listener.check().unwrap() {
-                break;
-            }
-            if count >= self.count as usize {
-                break;
+                return Ok(());
             }
 
-            callback(
-                BuildBuilder::new()
-                    .build_id(build.build_id.to_string())
-                    .provider("AppVeyor")
-                    .origin(format!(
-                        "https://ci.appveyor.com/project/{account}/{project}",
-                        account = self.account,
-                        project = self.project
-                    ))
-                    .collector(&self.info.id)
-                    .project_id(&result.project.project_id.to_string())
-                    .project_name(&result.project.repository_name)
-                    .definition_id(&result.project.account_id.to_string())
-                    .definition_name(&result.project.account_name)
-                    .build_number(&build.build_number.to_string())
-                    .status(build.get_status())
-                    .url(format!(
-                        "https://ci.appveyor.com/project/{account}/{project}/builds/{id}",
-                        account = self.account,
-                        project = self.project,
-                        id = build.build_id
-                    ))
-                    .started_at(build.get_started_timestamp()?)
-                    .finished_at(build.get_finished_timestamp()?)
-                    .branch(&build.branch)
-                    .build()
-                    .unwrap(),
-            );
+            match &self.branches {
+                Some(branches) => {
+                    // Get the latest build for each branch.
+                    for branch in branches.iter() {
+                        if listener.check().unwrap() {
+                            return Ok(());
+                        }
+                        match self.client.get_branch_build(
+                            &self.http,
+                            &self.account,
+                            project,
+                            branch,
+                        ) {
+                            Ok(result) => {
+                                callback(self.create_build(&result.project, &result.build)?)
+                            }
+                            Err(e) => warn!(
+                                "Could not get AppVeyor build for branch '{}' in '{}': {}",
+                                branch, project, e
+                            ),
+                        };
+                    }
+                }
+                None => {
+                    // Get the build history.
+                    let result =
+                        match self
+                            .client
+                            .get_builds(&self.http, &self.account, project, self.count)
+                        {
+                            Ok(result) => result,
+                            Err(e) => {
+                                warn!("Could not get AppVeyor builds for '{}': {}", project, e);
+                                continue;
+                            }
+                        };
+                    for (count, build) in result.builds.iter().enumerate() {
+                        if listener.check().unwrap() {
+                            return Ok(());
+                        }
+                        if count >= self.count as usize {
+                            break;
+                        }
+                        callback(self.create_build(&result.project, build)?);
+                    }
+                }
+            }
         }
 
         Ok(())
     }
 }
 
+impl<T: HttpClient + Default> AppVeyorCollector<T> {
+    /// Gets the slugs of the projects to collect builds for.
+    /// If no project have been configured, all projects in the account are used.
+    fn get_projects(&self) -> DuckResult<Vec<String>> {
+        if let Some(project) = &self.project {
+            return Ok(vec![project.clone()]);
+        }
+        Ok(self
+            .client
+            .get_projects(&self.http)?
+            .into_iter()
+            .filter(|p| p.account_name.eq_ignore_ascii_case(&self.account))
+            .map(|p| p.slug)
+            .collect())
+    }
+
+    fn create_build(&self, project: &AppVeyorProject, build: &AppVeyorBuild) -> DuckResult<Build> {
+        Ok(BuildBuilder::new()
+            .build_id(build.build_id.to_string())
+            .provider("AppVeyor")
+            .origin(format!(
+                "https://ci.appveyor.com/project/{account}/{project}",
+                account = self.account,
+                project = project.slug
+            ))
+            .collector(&self.info.id)
+            .project_id(&project.project_id.to_string())
+            .project_name(&project.repository_name)
+            .definition_id(&project.account_id.to_string())
+            .definition_name(&project.account_name)
+            .build_number(&build.build_number.to_string())
+            .status(build.get_status())
+            .url(format!(
+                "https://ci.appveyor.com/project/{account}/{project}/builds/{id}",
+                account = self.account,
+                project = project.slug,
+                id = build.build_id
+            ))
+            .started_at(build.get_started_timestamp()?)
+            .finished_at(build.get_finished_timestamp()?)
+            .branch(&build.branch)
+            .build()
+            .unwrap())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,15 +177,33 @@ mod tests {
     use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
     use reqwest::StatusCode;
 
-    fn create_collector() -> AppVeyorCollector<MockHttpClient> {
-        AppVeyorCollector::<MockHttpClient>::new(&AppVeyorConfiguration {
+    fn create_config() -> AppVeyorConfiguration {
+        AppVeyorConfiguration {
             id: "appveyor".to_owned(),
             enabled: Some(true),
+            interval: None,
             account: "patriksvensson".to_owned(),
-            project: "spectre-commandline".to_owned(),
+            project: Some("spectre-commandline".to_owned()),
+            branches: None,
             credentials: AppVeyorCredentials::Bearer("SECRET".to_owned()),
             count: Option::None,
-        })
+        }
+    }
+
+    fn create_collector() -> AppVeyorCollector<MockHttpClient> {
+        AppVeyorCollector::<MockHttpClient>::new(&create_config())
+    }
+
+    fn collect(collector: &AppVeyorCollector<MockHttpClient>) -> Vec<Build> {
+        let (_, listener) = waithandle::new();
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+        result
     }
 
     #[test]
@@ -181,4 +261,80 @@ mod tests {
         assert_eq!(1583929960, result[0].started_at);
         assert_eq!(1583930062, result[0].finished_at.unwrap());
     }
+
+    #[test]
+    fn should_collect_all_projects_in_account_if_project_is_omitted() {
+        // Given
+        let appveyor = AppVeyorCollector::<MockHttpClient>::new(&AppVeyorConfiguration {
+            project: None,
+            ..create_config()
+        });
+        let client = appveyor.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://ci.appveyor.com/api/projects")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/projects.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://ci.appveyor.com/api/projects/patriksvensson/spectre-commandline/history?recordsNumber=1"
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/builds.json"))
+        );
+
+        // When
+        let result = collect(&appveyor);
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(3, requests.len());
+        assert_eq!(
+            "https://ci.appveyor.com/api/projects/patriksvensson/duck/history?recordsNumber=1",
+            requests[2].url
+        );
+        assert_eq!(1, result.len());
+        assert_eq!("31395671", result[0].build_id);
+    }
+
+    #[test]
+    fn should_get_latest_build_per_branch() {
+        // Given
+        let appveyor = AppVeyorCollector::<MockHttpClient>::new(&AppVeyorConfiguration {
+            branches: Some(vec!["master".to_owned(), "develop".to_owned()]),
+            ..create_config()
+        });
+        let client = appveyor.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://ci.appveyor.com/api/projects/patriksvensson/spectre-commandline/branch/master",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/branch_master.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://ci.appveyor.com/api/projects/patriksvensson/spectre-commandline/branch/develop",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/branch_develop.json")),
+        );
+
+        // When
+        let result = collect(&appveyor);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("31400001", result[0].build_id);
+        assert_eq!("master", result[0].branch);
+        assert_eq!(BuildStatus::Success, result[0].status);
+        assert_eq!("31400002", result[1].build_id);
+        assert_eq!("develop", result[1].branch);
+        assert_eq!(BuildStatus::Running, result[1].status);
+        assert_eq!(None, result[1].finished_at);
+        assert_ne!(result[0].partition, result[1].partition);
+    }
 }
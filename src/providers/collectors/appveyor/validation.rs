@@ -6,8 +6,21 @@ impl Validate for AppVeyorConfiguration {
         if self.account.is_empty() {
             return Err(format_err!("[{}] AppVeyor account is empty", self.id));
         }
-        if self.project.is_empty() {
-            return Err(format_err!("[{}] AppVeyor project is empty", self.id));
+        if let Some(project) = &self.project {
+            if project.is_empty() {
+                return Err(format_err!("[{}] AppVeyor project is empty", self.id));
+            }
+        }
+        if let Some(branches) = &self.branches {
+            if branches.is_empty() {
+                return Err(format_err!(
+                    "[{}] AppVeyor configuration have not specified any branches",
+                    self.id
+                ));
+            }
+            if branches.iter().any(|b| b.is_empty()) {
+                return Err(format_err!("[{}] AppVeyor branch is empty", self.id));
+            }
         }
         match &self.credentials {
             crate::config::AppVeyorCredentials::Bearer(token) => {
@@ -126,4 +139,50 @@ mod tests {
         }"#,
         );
     }
+
+    #[test]
+    #[should_panic(
+        expected = "[appveyor_spectrecli] AppVeyor configuration have not specified any branches"
+    )]
+    fn should_return_error_if_branches_are_empty() {
+        create_collectors_from_config(
+            r#"
+        {
+            "collectors": [
+                {
+                    "appveyor": {
+                        "id": "appveyor_spectrecli",
+                        "credentials": {
+                            "bearer": "SECRET"
+                        },
+                        "account": "patriksvensson",
+                        "branches": [ ]
+                    }
+                }
+            ]
+        }"#,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "[appveyor_spectrecli] AppVeyor branch is empty")]
+    fn should_return_error_if_branch_is_empty() {
+        create_collectors_from_config(
+            r#"
+        {
+            "collectors": [
+                {
+                    "appveyor": {
+                        "id": "appveyor_spectrecli",
+                        "credentials": {
+                            "bearer": "SECRET"
+                        },
+                        "account": "patriksvensson",
+                        "branches": [ "" ]
+                    }
+                }
+            ]
+        }"#,
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use log::trace;
+use serde::de::DeserializeOwned;
 use url::Url;
 
 use crate::config::{AzureDevOpsConfiguration, AzureDevOpsCredentials};
@@ -7,8 +8,9 @@ use crate::DuckResult;
 
 pub struct AzureDevOpsClient {
     server_url: Url,
+    release_url: Url,
     organization: String,
-    project: String,
+    pub project: String,
     credentials: AzureDevOpsCredentials,
 }
 
@@ -19,6 +21,10 @@ impl AzureDevOpsClient {
                 Some(url) => Url::parse(&url[..]).unwrap(),
                 None => Url::parse("https://dev.azure.com").unwrap(),
             },
+            release_url: match &config.server_url {
+                Some(url) => Url::parse(&url[..]).unwrap(),
+                None => Url::parse("https://vsrm.dev.azure.com").unwrap(),
+            },
             organization: config.organization.clone(),
             project: config.project.clone(),
             credentials: config.credentials.clone(),
@@ -39,7 +45,7 @@ impl AzureDevOpsClient {
         client: &impl HttpClient,
         branch: &str,
         definitions: &[String],
-    ) -> DuckResult<AzureResponse> {
+    ) -> DuckResult<AzureResponse<AzureBuild>> {
         let url = format!(
             "{server}{organization}/{project}/_apis/build/builds?api-version=5.0\
              &branchName={branch}&definitions={definitions}&maxBuildsPerDefinition=1\
@@ -51,7 +57,63 @@ impl AzureDevOpsClient {
             branch = branch,
             definitions = definitions.join(","),
         );
+        self.send_get_request(client, url)
+    }
+
+    /// Gets the latest deployment attempts for a classic release definition.
+    pub fn get_release_deployments(
+        &self,
+        client: &impl HttpClient,
+        definition: &str,
+    ) -> DuckResult<AzureResponse<AzureReleaseDeployment>> {
+        let url = format!(
+            "{server}{organization}/{project}/_apis/release/deployments?api-version=5.0\
+             &definitionId={definition}&latestAttemptsOnly=true&queryOrder=descending&$top=50",
+            server = self.release_url,
+            organization = self.organization,
+            project = self.project,
+            definition = definition,
+        );
+        self.send_get_request(client, url)
+    }
 
+    /// Gets all YAML environments in the project.
+    pub fn get_environments(
+        &self,
+        client: &impl HttpClient,
+    ) -> DuckResult<AzureResponse<AzureEnvironment>> {
+        let url = format!(
+            "{server}{organization}/{project}/_apis/distributedtask/environments\
+             ?api-version=6.0-preview.1",
+            server = self.server_url,
+            organization = self.organization,
+            project = self.project,
+        );
+        self.send_get_request(client, url)
+    }
+
+    /// Gets the latest deployments to a YAML environment.
+    pub fn get_environment_deployments(
+        &self,
+        client: &impl HttpClient,
+        environment: &AzureEnvironment,
+    ) -> DuckResult<AzureResponse<AzureEnvironmentDeployment>> {
+        let url = format!(
+            "{server}{organization}/{project}/_apis/distributedtask/environments/{environment}\
+             /environmentdeploymentrecords?api-version=6.0-preview.1&top=50",
+            server = self.server_url,
+            organization = self.organization,
+            project = self.project,
+            environment = environment.id,
+        );
+        self.send_get_request(client, url)
+    }
+
+    fn send_get_request<T: DeserializeOwned>(
+        &self,
+        client: &impl HttpClient,
+        url: String,
+    ) -> DuckResult<T> {
         trace!("Sending request to: {}", url);
         let mut builder = HttpRequestBuilder::get(&url);
         builder.add_header("Content-Type", "application/json");
@@ -87,8 +149,8 @@ impl AzureDevOpsCredentials {
 }
 
 #[derive(Deserialize, Debug)]
-pub struct AzureResponse {
-    pub value: Vec<AzureBuild>,
+pub struct AzureResponse<T> {
+    pub value: Vec<T>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -131,3 +193,77 @@ pub struct AzureBuildDefinition {
     pub id: u64,
     pub name: String,
 }
+
+///////////////////////////////////////////////////////////
+// Releases
+
+#[derive(Deserialize, Debug)]
+pub struct AzureReleaseDeployment {
+    pub id: u64,
+    #[serde(alias = "definitionEnvironmentId")]
+    pub definition_environment_id: u64,
+    #[serde(alias = "deploymentStatus")]
+    pub deployment_status: String,
+    #[serde(alias = "operationStatus")]
+    pub operation_status: String,
+    #[serde(alias = "queuedOn")]
+    pub queued_on: String,
+    #[serde(alias = "startedOn")]
+    pub started_on: Option<String>,
+    #[serde(alias = "completedOn")]
+    pub completed_on: Option<String>,
+    pub release: AzureRelease,
+    #[serde(alias = "releaseDefinition")]
+    pub release_definition: AzureReleaseReference,
+    #[serde(alias = "releaseEnvironment")]
+    pub release_environment: AzureReleaseReference,
+    #[serde(alias = "projectReference")]
+    pub project: AzureProject,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureRelease {
+    pub id: u64,
+    pub name: String,
+    #[serde(alias = "_links")]
+    pub links: AzureLinks,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureReleaseReference {
+    pub id: u64,
+    pub name: String,
+}
+
+///////////////////////////////////////////////////////////
+// Environments
+
+#[derive(Deserialize, Debug)]
+pub struct AzureEnvironment {
+    pub id: u64,
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureEnvironmentDeployment {
+    pub id: u64,
+    #[serde(alias = "stageName")]
+    pub stage_name: Option<String>,
+    pub definition: AzureEnvironmentReference,
+    pub owner: AzureEnvironmentReference,
+    pub result: Option<String>,
+    #[serde(alias = "queueTime")]
+    pub queue_time: String,
+    #[serde(alias = "startTime")]
+    pub start_time: Option<String>,
+    #[serde(alias = "finishTime")]
+    pub finish_time: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureEnvironmentReference {
+    pub id: u64,
+    pub name: String,
+    #[serde(alias = "_links")]
+    pub links: Option<AzureLinks>,
+}
@@ -1,10 +1,12 @@
+use std::collections::HashSet;
 use std::time::Duration;
 
+use log::warn;
 use waithandle::WaitHandleListener;
 
 use crate::builds::{Build, BuildBuilder, BuildStatus};
 use crate::config::AzureDevOpsConfiguration;
-use crate::providers::collectors::azure::client::{AzureBuild, AzureDevOpsClient};
+use crate::providers::collectors::azure::client::*;
 use crate::providers::collectors::{Collector, CollectorInfo, CollectorLoader};
 use crate::utils::date;
 use crate::utils::http::*;
@@ -24,6 +26,8 @@ pub struct AzureDevOpsCollector<T: HttpClient + Default> {
     client: AzureDevOpsClient,
     branches: Vec<String>,
     definitions: Vec<String>,
+    releases: Vec<String>,
+    environments: Vec<String>,
     info: CollectorInfo,
 }
 
@@ -34,6 +38,8 @@ impl<T: HttpClient + Default> AzureDevOpsCollector<T> {
             client: AzureDevOpsClient::new(config),
             branches: config.branches.clone(),
             definitions: config.definitions.clone(),
+            releases: config.releases.clone().unwrap_or_default(),
+            environments: config.environments.clone().unwrap_or_default(),
             info: CollectorInfo {
                 id: config.id.clone(),
                 enabled: match config.enabled {
@@ -41,6 +47,7 @@ impl<T: HttpClient + Default> AzureDevOpsCollector<T> {
                     Option::Some(e) => e,
                 },
                 provider: "AzureDevOps".to_string(),
+                interval: config.interval,
             },
         }
     }
@@ -60,6 +67,25 @@ impl<T: HttpClient + Default> Collector for AzureDevOpsCollector<T> {
         &self,
         listener: WaitHandleListener,
         callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        if !self.definitions.is_empty() {
+            self.collect_builds(&listener, callback)?;
+        }
+        if !self.releases.is_empty() {
+            self.collect_releases(&listener, callback)?;
+        }
+        if !self.environments.is_empty() {
+            self.collect_environments(&listener, callback)?;
+        }
+        return Ok(());
+    }
+}
+
+impl<T: HttpClient + Default> AzureDevOpsCollector<T> {
+    fn collect_builds(
+        &self,
+        listener: &WaitHandleListener,
+        callback: &mut dyn FnMut(Build),
     ) -> DuckResult<()> {
         for branch in self.branches.iter() {
             if listener.check().unwrap() {
@@ -91,13 +117,7 @@ impl<T: HttpClient + Default> Collector for AzureDevOpsCollector<T> {
                             &build.start_time,
                             date::AZURE_DEVOPS_FORMAT,
                         )?)
-                        .finished_at(match &build.finish_time {
-                            Option::None => None,
-                            Option::Some(value) => Option::Some(date::to_timestamp(
-                                &value[..],
-                                date::AZURE_DEVOPS_FORMAT,
-                            )?),
-                        })
+                        .finished_at(to_timestamp(&build.finish_time)?)
                         .branch(&build.branch)
                         .build()
                         .unwrap(),
@@ -112,6 +132,139 @@ impl<T: HttpClient + Default> Collector for AzureDevOpsCollector<T> {
 
         return Ok(());
     }
+
+    /// Collects the latest deployment for every stage in
+    /// the configured classic release definitions.
+    fn collect_releases(
+        &self,
+        listener: &WaitHandleListener,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        for release in self.releases.iter() {
+            if listener.check().unwrap() {
+                return Ok(());
+            }
+
+            // Deployments are returned with the most recent one first.
+            let deployments = self.client.get_release_deployments(&self.http, release)?;
+            let mut stages = HashSet::new();
+            for deployment in deployments.value.iter() {
+                if !stages.insert(deployment.definition_environment_id) {
+                    continue;
+                }
+
+                callback(
+                    BuildBuilder::new()
+                        .build_id(deployment.id.to_string())
+                        .provider("AzureDevOps")
+                        .origin(&self.client.get_origin())
+                        .collector(&self.info.id)
+                        .project_id(&deployment.project.id)
+                        .project_name(&deployment.project.name)
+                        .definition_id(format!("release/{}", deployment.release_definition.id))
+                        .definition_name(&deployment.release_definition.name)
+                        .build_number(&deployment.release.name)
+                        .status(deployment.get_status())
+                        .url(&deployment.release.links.web.href)
+                        .started_at(date::to_timestamp(
+                            deployment
+                                .started_on
+                                .as_ref()
+                                .unwrap_or(&deployment.queued_on),
+                            date::AZURE_DEVOPS_FORMAT,
+                        )?)
+                        .finished_at(to_timestamp(&deployment.completed_on)?)
+                        .branch(&deployment.release_environment.name)
+                        .build()
+                        .unwrap(),
+                );
+            }
+
+            // Wait for a litle time between calls.
+            if listener.wait(Duration::from_millis(300)).unwrap() {
+                return Ok(());
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Collects the latest deployment for every pipeline
+    /// that has been deployed to the configured YAML environments.
+    fn collect_environments(
+        &self,
+        listener: &WaitHandleListener,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        let environments = self.client.get_environments(&self.http)?;
+        for name in self.environments.iter() {
+            if listener.check().unwrap() {
+                return Ok(());
+            }
+
+            let environment = match environments.find(name) {
+                Some(environment) => environment,
+                None => {
+                    warn!("The Azure DevOps environment '{}' does not exist.", name);
+                    continue;
+                }
+            };
+
+            // Deployments are returned with the most recent one first.
+            let deployments = self
+                .client
+                .get_environment_deployments(&self.http, environment)?;
+            let mut pipelines = HashSet::new();
+            for deployment in deployments.value.iter() {
+                if !pipelines.insert(deployment.definition.id) {
+                    continue;
+                }
+
+                callback(
+                    BuildBuilder::new()
+                        .build_id(deployment.id.to_string())
+                        .provider("AzureDevOps")
+                        .origin(&self.client.get_origin())
+                        .collector(&self.info.id)
+                        .project_id(&self.client.project)
+                        .project_name(&self.client.project)
+                        .definition_id(deployment.definition.id.to_string())
+                        .definition_name(&deployment.definition.name)
+                        .build_number(&deployment.owner.name)
+                        .status(deployment.get_status())
+                        .url(deployment.get_url())
+                        .started_at(date::to_timestamp(
+                            deployment
+                                .start_time
+                                .as_ref()
+                                .unwrap_or(&deployment.queue_time),
+                            date::AZURE_DEVOPS_FORMAT,
+                        )?)
+                        .finished_at(to_timestamp(&deployment.finish_time)?)
+                        .branch(&environment.name)
+                        .build()
+                        .unwrap(),
+                );
+            }
+
+            // Wait for a litle time between calls.
+            if listener.wait(Duration::from_millis(300)).unwrap() {
+                return Ok(());
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+fn to_timestamp(value: &Option<String>) -> DuckResult<Option<i64>> {
+    match value {
+        Option::None => Ok(None),
+        Option::Some(value) => Ok(Option::Some(date::to_timestamp(
+            &value[..],
+            date::AZURE_DEVOPS_FORMAT,
+        )?)),
+    }
 }
 
 impl AzureBuild {
@@ -133,6 +286,57 @@ impl AzureBuild {
     }
 }
 
+impl AzureReleaseDeployment {
+    pub fn get_status(&self) -> BuildStatus {
+        match &self.operation_status[..] {
+            "Canceled" | "Cancelling" | "PhaseCanceled" | "Rejected" => {
+                return BuildStatus::Canceled
+            }
+            "Queued" | "QueuedForAgent" | "QueuedForPipeline" | "Scheduled" | "Pending"
+            | "Deferred" => return BuildStatus::Queued,
+            _ => {}
+        }
+        match &self.deployment_status[..] {
+            "succeeded" => BuildStatus::Success,
+            "inProgress" => BuildStatus::Running,
+            "notDeployed" => BuildStatus::Skipped,
+            _ => BuildStatus::Failed,
+        }
+    }
+}
+
+impl AzureResponse<AzureEnvironment> {
+    pub fn find(&self, name: &str) -> Option<&AzureEnvironment> {
+        self.value
+            .iter()
+            .find(|e| e.id.to_string() == name || e.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl AzureEnvironmentDeployment {
+    pub fn get_status(&self) -> BuildStatus {
+        match &self.result {
+            None => match self.start_time {
+                None => BuildStatus::Queued,
+                Some(_) => BuildStatus::Running,
+            },
+            Some(result) => match &result[..] {
+                "succeeded" => BuildStatus::Success,
+                "canceled" => BuildStatus::Canceled,
+                "skipped" => BuildStatus::Skipped,
+                _ => BuildStatus::Failed,
+            },
+        }
+    }
+
+    pub fn get_url(&self) -> String {
+        match &self.owner.links {
+            Some(links) => links.web.href.clone(),
+            None => "".to_owned(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,18 +344,39 @@ mod tests {
     use crate::config::*;
     use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
     use reqwest::StatusCode;
+    use test_case::test_case;
 
-    fn create_collector(server_url: Option<String>) -> AzureDevOpsCollector<MockHttpClient> {
-        AzureDevOpsCollector::<MockHttpClient>::new(&AzureDevOpsConfiguration {
+    fn create_config(server_url: Option<String>) -> AzureDevOpsConfiguration {
+        AzureDevOpsConfiguration {
             id: "azure".to_owned(),
             enabled: Some(true),
+            interval: None,
             server_url,
             organization: "cake-build".to_owned(),
             project: "cake".to_owned(),
             credentials: AzureDevOpsCredentials::PersonalAccessToken("SECRET".to_owned()),
             branches: vec!["refs/heads/develop".to_owned()],
             definitions: vec!["5".to_owned(), "6".to_owned()],
-        })
+            releases: None,
+            environments: None,
+            webhook_secret: None,
+        }
+    }
+
+    fn create_collector(server_url: Option<String>) -> AzureDevOpsCollector<MockHttpClient> {
+        AzureDevOpsCollector::<MockHttpClient>::new(&create_config(server_url))
+    }
+
+    fn collect(collector: &AzureDevOpsCollector<MockHttpClient>) -> Vec<Build> {
+        let (_, listener) = waithandle::new();
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+        result
     }
 
     #[test]
@@ -263,4 +488,122 @@ mod tests {
         assert_eq!(1587697251, result[0].started_at);
         assert_eq!(1587697564, result[0].finished_at.unwrap());
     }
+
+    #[test]
+    fn should_get_latest_deployment_for_each_release_stage() {
+        // Given
+        let collector = AzureDevOpsCollector::<MockHttpClient>::new(&AzureDevOpsConfiguration {
+            definitions: vec![],
+            releases: Some(vec!["3".to_owned()]),
+            ..create_config(None)
+        });
+        collector.get_client().add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://vsrm.dev.azure.com/cake-build/cake/_apis/release/deployments?api-version=5.0&definitionId=3&latestAttemptsOnly=true&queryOrder=descending&$top=50"
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/deployments.json"))
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("512", result[0].build_id);
+        assert_eq!("AzureDevOps", result[0].provider);
+        assert_eq!("https://dev.azure.com/cake-build/cake", result[0].origin);
+        assert_eq!("af63183c-ac1f-4dbb-93bc-4fa862ea5809", result[0].project_id);
+        assert_eq!("Cake", result[0].project_name);
+        assert_eq!("release/3", result[0].definition_id);
+        assert_eq!("Cake Deploy", result[0].definition_name);
+        assert_eq!("Release-98", result[0].build_number);
+        assert_eq!("Staging", result[0].branch);
+        assert_eq!(BuildStatus::Running, result[0].status);
+        assert_eq!(
+            "https://dev.azure.com/cake-build/cake/_release?releaseId=98&_a=release-summary",
+            result[0].url
+        );
+        assert_eq!(1587719431, result[0].started_at);
+        assert_eq!(None, result[0].finished_at);
+        assert_eq!("509", result[1].build_id);
+        assert_eq!("Production", result[1].branch);
+        assert_eq!(BuildStatus::Success, result[1].status);
+        assert_eq!(1587654171, result[1].started_at);
+        assert_eq!(Some(1587654437), result[1].finished_at);
+    }
+
+    #[test]
+    fn should_get_latest_deployment_for_each_pipeline_in_environment() {
+        // Given
+        let collector = AzureDevOpsCollector::<MockHttpClient>::new(&AzureDevOpsConfiguration {
+            definitions: vec![],
+            environments: Some(vec!["Production".to_owned()]),
+            ..create_config(None)
+        });
+        let client = collector.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://dev.azure.com/cake-build/cake/_apis/distributedtask/environments?api-version=6.0-preview.1"
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/environments.json"))
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://dev.azure.com/cake-build/cake/_apis/distributedtask/environments/5/environmentdeploymentrecords?api-version=6.0-preview.1&top=50"
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/environment_deployments.json"))
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("77", result[0].build_id);
+        assert_eq!("cake", result[0].project_name);
+        assert_eq!("12", result[0].definition_id);
+        assert_eq!("cake-pipeline", result[0].definition_name);
+        assert_eq!("20200424.3", result[0].build_number);
+        assert_eq!("production", result[0].branch);
+        assert_eq!(BuildStatus::Failed, result[0].status);
+        assert_eq!(
+            "https://dev.azure.com/cake-build/af63183c-ac1f-4dbb-93bc-4fa862ea5809/_build/results?buildId=10214",
+            result[0].url
+        );
+        assert_eq!(1587727209, result[0].started_at);
+        assert_eq!(Some(1587727431), result[0].finished_at);
+        assert_eq!("docs-pipeline", result[1].definition_name);
+        assert_eq!(BuildStatus::Queued, result[1].status);
+        assert_eq!(1587722564, result[1].started_at);
+    }
+
+    #[test_case("succeeded", "Approved", BuildStatus::Success ; "Succeeded")]
+    #[test_case("partiallySucceeded", "PhasePartiallySucceeded", BuildStatus::Failed ; "Partially succeeded")]
+    #[test_case("failed", "PhaseFailed", BuildStatus::Failed ; "Failed")]
+    #[test_case("inProgress", "PhaseInProgress", BuildStatus::Running ; "In progress")]
+    #[test_case("inProgress", "QueuedForAgent", BuildStatus::Queued ; "Queued")]
+    #[test_case("notDeployed", "Canceled", BuildStatus::Canceled ; "Canceled")]
+    #[test_case("notDeployed", "Undefined", BuildStatus::Skipped ; "Not deployed")]
+    fn should_map_release_deployment_status(
+        deployment_status: &str,
+        operation_status: &str,
+        expected: BuildStatus,
+    ) {
+        // Given
+        let mut deployments: AzureResponse<AzureReleaseDeployment> =
+            serde_json::from_str(include_str!("test_data/deployments.json")).unwrap();
+        let mut deployment = deployments.value.remove(0);
+        deployment.deployment_status = deployment_status.to_owned();
+        deployment.operation_status = operation_status.to_owned();
+        // When
+        let status = deployment.get_status();
+        // Then
+        assert_eq!(expected, status);
+    }
 }
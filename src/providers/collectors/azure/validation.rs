@@ -12,19 +12,30 @@ impl Validate for AzureDevOpsConfiguration {
         if self.project.is_empty() {
             return Err(format_err!("[{}] Azure DevOps project is empty", self.id));
         }
-        if self.definitions.is_empty() {
+        let releases = self.releases.as_ref().map_or(0, |r| r.len());
+        let environments = self.environments.as_ref().map_or(0, |e| e.len());
+        if self.definitions.is_empty() && releases == 0 && environments == 0 {
             return Err(format_err!(
-                "[{}] Azure DevOps configuration have not specified any build definitions",
+                "[{}] Azure DevOps configuration have not specified any build definitions, releases or environments",
                 self.id
             ));
         }
-        if self.branches.is_empty() {
+        if !self.definitions.is_empty() && self.branches.is_empty() {
             return Err(format_err!(
                 "[{}] Azure DevOps configuration have not specified any branches",
                 self.id
             ));
         }
 
+        if let Some(secret) = &self.webhook_secret {
+            if secret.is_empty() {
+                return Err(format_err!(
+                    "[{}] Azure DevOps webhook secret is empty",
+                    self.id
+                ));
+            }
+        }
+
         match &self.credentials {
             AzureDevOpsCredentials::Anonymous => {}
             AzureDevOpsCredentials::PersonalAccessToken(token) => {
@@ -209,4 +220,57 @@ mod tests {
 
         providers::create_collectors(&config).unwrap();
     }
+
+    #[test]
+    fn should_not_require_build_definitions_if_releases_are_specified() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "azure": {
+                            "id": "foo",
+                            "organization": "cake-build",
+                            "project": "Cake",
+                            "credentials": "anonymous",
+                            "releases": [ "3" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[foo] Azure DevOps webhook secret is empty")]
+    fn should_return_error_if_azure_devops_webhook_secret_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "azure": {
+                            "id": "foo",
+                            "organization": "cake-build",
+                            "project": "Cake",
+                            "credentials": "anonymous",
+                            "webhookSecret": "",
+                            "definitions": [ "1", "3", "5" ],
+                            "branches": [ "refs/heads/develop" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
 }
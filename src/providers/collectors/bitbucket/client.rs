@@ -0,0 +1,293 @@
+use log::{trace, warn};
+use url::form_urlencoded::byte_serialize;
+use url::Url;
+
+use crate::builds::BuildStatus;
+use crate::config::{BitbucketConfiguration, BitbucketCredentials};
+use crate::utils::date;
+use crate::utils::http::*;
+use crate::DuckResult;
+
+static CLOUD_API_URL: &str = "https://api.bitbucket.org/2.0/";
+static CLOUD_WEB_URL: &str = "https://bitbucket.org/";
+
+pub struct BitbucketClient {
+    server_url: Option<Url>,
+    credentials: BitbucketCredentials,
+}
+
+/// A build from either Bitbucket Cloud or Bitbucket Server.
+pub struct BitbucketBuild {
+    pub id: String,
+    pub number: String,
+    pub definition_id: String,
+    pub definition_name: String,
+    pub status: BuildStatus,
+    pub url: String,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+}
+
+impl BitbucketClient {
+    pub fn new(config: &BitbucketConfiguration) -> Self {
+        BitbucketClient {
+            server_url: config
+                .server_url
+                .as_ref()
+                .map(|url| Url::parse(&with_trailing_slash(url)[..]).unwrap()),
+            credentials: config.credentials.clone(),
+        }
+    }
+
+    pub fn get_origin(&self) -> &str {
+        match &self.server_url {
+            Some(url) => url.as_str(),
+            None => CLOUD_WEB_URL,
+        }
+    }
+
+    /// Gets the latest builds for a repository branch.
+    pub fn get_builds(
+        &self,
+        client: &impl HttpClient,
+        owner: &str,
+        repository: &str,
+        branch: &str,
+    ) -> DuckResult<Vec<BitbucketBuild>> {
+        match &self.server_url {
+            None => self.get_pipelines(client, owner, repository, branch),
+            Some(server_url) => {
+                self.get_build_statuses(client, server_url, owner, repository, branch)
+            }
+        }
+    }
+
+    fn get_pipelines(
+        &self,
+        client: &impl HttpClient,
+        workspace: &str,
+        repository: &str,
+        branch: &str,
+    ) -> DuckResult<Vec<BitbucketBuild>> {
+        let url = format!(
+            "{api}repositories/{workspace}/{repository}/pipelines/\
+             ?target.ref_name={branch}&sort=-created_on&pagelen=1",
+            api = CLOUD_API_URL,
+            workspace = encode(workspace),
+            repository = encode(repository),
+            branch = encode(branch)
+        );
+
+        let body = self.send_get_request(client, url)?;
+        let pipelines: BitbucketPage<BitbucketPipeline> = serde_json::from_str(&body[..])?;
+
+        let mut result = Vec::new();
+        for pipeline in pipelines.values.iter() {
+            result.push(BitbucketBuild {
+                id: pipeline.build_number.to_string(),
+                number: pipeline.build_number.to_string(),
+                definition_id: format!("{}/{}", workspace, repository),
+                definition_name: repository.to_owned(),
+                status: pipeline.get_status(),
+                url: format!(
+                    "{web}{workspace}/{repository}/addon/pipelines/home#!/results/{number}",
+                    web = CLOUD_WEB_URL,
+                    workspace = workspace,
+                    repository = repository,
+                    number = pipeline.build_number
+                ),
+                started_at: date::to_timestamp(&pipeline.created_on, date::BITBUCKET_FORMAT)?,
+                finished_at: match &pipeline.completed_on {
+                    Some(completed_on) => {
+                        Some(date::to_timestamp(completed_on, date::BITBUCKET_FORMAT)?)
+                    }
+                    None => None,
+                },
+            });
+        }
+
+        Ok(result)
+    }
+
+    fn get_build_statuses(
+        &self,
+        client: &impl HttpClient,
+        server_url: &Url,
+        project: &str,
+        repository: &str,
+        branch: &str,
+    ) -> DuckResult<Vec<BitbucketBuild>> {
+        // Get the latest commit on the branch.
+        let url = format!(
+            "{server}rest/api/1.0/projects/{project}/repos/{repository}/commits\
+             ?until={branch}&limit=1",
+            server = server_url,
+            project = encode(project),
+            repository = encode(repository),
+            branch = encode(branch)
+        );
+        let body = self.send_get_request(client, url)?;
+        let commits: BitbucketPage<BitbucketCommit> = serde_json::from_str(&body[..])?;
+        let commit = match commits.values.first() {
+            Some(commit) => commit,
+            None => return Ok(vec![]),
+        };
+
+        // Get all builds that have been reported for the commit.
+        let url = format!(
+            "{server}rest/build-status/1.0/commits/{commit}",
+            server = server_url,
+            commit = commit.id
+        );
+        let body = self.send_get_request(client, url)?;
+        let statuses: BitbucketPage<BitbucketBuildStatus> = serde_json::from_str(&body[..])?;
+
+        Ok(statuses
+            .values
+            .iter()
+            .map(|status| BitbucketBuild {
+                id: commit.id.clone(),
+                number: commit.display_id.clone(),
+                definition_id: status.key.clone(),
+                definition_name: status.name.clone().unwrap_or_else(|| status.key.clone()),
+                status: status.get_status(),
+                url: status.url.clone(),
+                started_at: status.date_added / 1000,
+                finished_at: match status.get_status() {
+                    BuildStatus::Running => None,
+                    _ => Some(status.date_added / 1000),
+                },
+            })
+            .collect())
+    }
+
+    fn send_get_request(&self, client: &impl HttpClient, url: String) -> DuckResult<String> {
+        trace!("Sending request to: {}", url);
+        let mut builder = HttpRequestBuilder::get(&url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+
+        self.credentials.authenticate(&mut builder);
+        let mut response = client.send(&builder)?;
+
+        trace!("Received response: {}", response.status());
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Received non 200 HTTP status code. ({})",
+                response.status()
+            ));
+        }
+
+        Ok(response.body()?)
+    }
+}
+
+impl BitbucketCredentials {
+    fn authenticate<'a>(&self, builder: &'a mut HttpRequestBuilder) {
+        match self {
+            BitbucketCredentials::Anonymous => {}
+            BitbucketCredentials::AppPassword { username, password } => {
+                builder.basic_auth(username, Some(password));
+            }
+            BitbucketCredentials::Bearer(token) => {
+                builder.bearer(token);
+            }
+        }
+    }
+}
+
+fn with_trailing_slash(url: &str) -> String {
+    if url.ends_with('/') {
+        url.to_owned()
+    } else {
+        format!("{}/", url)
+    }
+}
+
+fn encode(value: &str) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BitbucketPage<T> {
+    pub values: Vec<T>,
+}
+
+///////////////////////////////////////////////////////////
+// Bitbucket Cloud
+
+#[derive(Deserialize, Debug)]
+pub struct BitbucketPipeline {
+    pub build_number: u64,
+    pub state: BitbucketPipelineState,
+    pub created_on: String,
+    pub completed_on: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BitbucketPipelineState {
+    pub name: String,
+    pub result: Option<BitbucketPipelineResult>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BitbucketPipelineResult {
+    pub name: String,
+}
+
+impl BitbucketPipeline {
+    pub fn get_status(&self) -> BuildStatus {
+        match &self.state.name[..] {
+            "PENDING" => BuildStatus::Queued,
+            "IN_PROGRESS" => BuildStatus::Running,
+            "COMPLETED" => match self.state.result.as_ref().map(|r| &r.name[..]) {
+                Some("SUCCESSFUL") => BuildStatus::Success,
+                Some("FAILED") | Some("ERROR") => BuildStatus::Failed,
+                Some("STOPPED") | Some("EXPIRED") => BuildStatus::Canceled,
+                result => {
+                    warn!("Unknown pipeline result: {:?}", result);
+                    BuildStatus::Unknown
+                }
+            },
+            state => {
+                warn!("Unknown pipeline state: {}", state);
+                BuildStatus::Unknown
+            }
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Bitbucket Server
+
+#[derive(Deserialize, Debug)]
+pub struct BitbucketCommit {
+    pub id: String,
+    #[serde(rename = "displayId")]
+    pub display_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BitbucketBuildStatus {
+    pub state: String,
+    pub key: String,
+    pub name: Option<String>,
+    pub url: String,
+    #[serde(rename = "dateAdded")]
+    pub date_added: i64,
+}
+
+impl BitbucketBuildStatus {
+    pub fn get_status(&self) -> BuildStatus {
+        match &self.state[..] {
+            "SUCCESSFUL" => BuildStatus::Success,
+            "FAILED" => BuildStatus::Failed,
+            "INPROGRESS" => BuildStatus::Running,
+            "CANCELLED" => BuildStatus::Canceled,
+            state => {
+                warn!("Unknown build state: {}", state);
+                BuildStatus::Unknown
+            }
+        }
+    }
+}
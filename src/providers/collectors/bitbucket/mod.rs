@@ -0,0 +1,381 @@
+use std::time::Duration;
+
+use log::{trace, warn};
+use waithandle::WaitHandleListener;
+
+use crate::builds::{Build, BuildBuilder};
+use crate::config::BitbucketConfiguration;
+use crate::providers::collectors::{Collector, CollectorInfo, CollectorLoader};
+use crate::utils::http::{HttpClient, ReqwestClient};
+use crate::DuckResult;
+
+use self::client::BitbucketClient;
+
+mod client;
+mod validation;
+
+impl CollectorLoader for BitbucketConfiguration {
+    fn load(&self) -> DuckResult<Box<dyn Collector>> {
+        Ok(Box::new(BitbucketCollector::<ReqwestClient>::new(self)))
+    }
+}
+
+pub struct BitbucketCollector<T: HttpClient + Default> {
+    http: T,
+    client: BitbucketClient,
+    repositories: Vec<String>,
+    branches: Vec<String>,
+    info: CollectorInfo,
+}
+
+impl<T: HttpClient + Default> BitbucketCollector<T> {
+    pub fn new(config: &BitbucketConfiguration) -> Self {
+        BitbucketCollector {
+            http: Default::default(),
+            client: BitbucketClient::new(config),
+            repositories: config.repositories.clone(),
+            branches: config.branches.clone(),
+            info: CollectorInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    Option::None => true,
+                    Option::Some(e) => e,
+                },
+                provider: "Bitbucket".to_owned(),
+                interval: config.interval,
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Collector for BitbucketCollector<T> {
+    fn info(&self) -> &CollectorInfo {
+        &self.info
+    }
+
+    fn collect(
+        &self,
+        listener: WaitHandleListener,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        for repository in self.repositories.iter() {
+            // The repository has already been validated.
+            let (owner, name) = match repository.find('/') {
+                Some(index) => (&repository[..index], &repository[index + 1..]),
+                None => continue,
+            };
+
+            for branch in self.branches.iter() {
+                if listener.check().unwrap() {
+                    return Ok(());
+                }
+
+                let builds = match self.client.get_builds(&self.http, owner, name, branch) {
+                    Ok(builds) => builds,
+                    Err(e) => {
+                        warn!(
+                            "Could not get Bitbucket builds for branch '{}' in '{}': {}",
+                            branch, repository, e
+                        );
+                        continue;
+                    }
+                };
+
+                if builds.is_empty() {
+                    trace!(
+                        "No builds found for branch '{}' in '{}'",
+                        branch,
+                        repository
+                    );
+                }
+
+                for build in builds {
+                    callback(
+                        BuildBuilder::new()
+                            .build_id(build.id)
+                            .provider("Bitbucket")
+                            .origin(self.client.get_origin())
+                            .collector(&self.info.id)
+                            .project_id(repository)
+                            .project_name(repository)
+                            .definition_id(build.definition_id)
+                            .definition_name(build.definition_name)
+                            .build_number(build.number)
+                            .status(build.status)
+                            .url(build.url)
+                            .started_at(build.started_at)
+                            .finished_at(build.finished_at)
+                            .branch(branch)
+                            .build()
+                            .unwrap(),
+                    );
+                }
+
+                // Wait for a litle time between calls.
+                if listener.wait(Duration::from_millis(300)).unwrap() {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildStatus;
+    use crate::config::*;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+    use test_case::test_case;
+
+    fn create_collector(
+        server_url: Option<&str>,
+        credentials: BitbucketCredentials,
+    ) -> BitbucketCollector<MockHttpClient> {
+        BitbucketCollector::<MockHttpClient>::new(&BitbucketConfiguration {
+            id: "bitbucket".to_owned(),
+            enabled: Some(true),
+            interval: None,
+            server_url: server_url.map(|s| s.to_owned()),
+            credentials,
+            repositories: vec!["duckhq/duck".to_owned()],
+            branches: vec!["master".to_owned()],
+        })
+    }
+
+    fn collect(collector: &BitbucketCollector<MockHttpClient>) -> Vec<Build> {
+        let (_, listener) = waithandle::new();
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+        result
+    }
+
+    fn add_pipelines_response(collector: &BitbucketCollector<MockHttpClient>) {
+        collector.get_client().add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://api.bitbucket.org/2.0/repositories/duckhq/duck/pipelines/\
+                 ?target.ref_name=master&sort=-created_on&pagelen=1",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/pipelines.json")),
+        );
+    }
+
+    #[test]
+    fn should_return_correct_provider_name() {
+        // Given
+        let collector = create_collector(None, BitbucketCredentials::Anonymous);
+        // When
+        let provider = &collector.info().provider;
+        // Then
+        assert_eq!("Bitbucket", provider);
+    }
+
+    #[test]
+    fn should_get_correct_data_from_bitbucket_cloud() {
+        // Given
+        let collector = create_collector(None, BitbucketCredentials::Anonymous);
+        add_pipelines_response(&collector);
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("42", result[0].build_id);
+        assert_eq!("Bitbucket", result[0].provider);
+        assert_eq!("bitbucket", result[0].collector);
+        assert_eq!("https://bitbucket.org/", result[0].origin);
+        assert_eq!("duckhq/duck", result[0].project_id);
+        assert_eq!("duckhq/duck", result[0].project_name);
+        assert_eq!("duckhq/duck", result[0].definition_id);
+        assert_eq!("duck", result[0].definition_name);
+        assert_eq!("42", result[0].build_number);
+        assert_eq!(BuildStatus::Success, result[0].status);
+        assert_eq!("master", result[0].branch);
+        assert_eq!(
+            "https://bitbucket.org/duckhq/duck/addon/pipelines/home#!/results/42",
+            result[0].url
+        );
+        assert_eq!(1588580832, result[0].started_at);
+        assert_eq!(1588581177, result[0].finished_at.unwrap());
+    }
+
+    #[test]
+    fn should_get_correct_data_from_bitbucket_server() {
+        // Given
+        let collector = create_collector(
+            Some("https://bitbucket.example.com"),
+            BitbucketCredentials::Bearer("SECRET".to_owned()),
+        );
+        let client = collector.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://bitbucket.example.com/rest/api/1.0/projects/duckhq/repos/duck/commits\
+                 ?until=master&limit=1",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/commits.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://bitbucket.example.com/rest/build-status/1.0/commits/\
+                 4e7f3c0a1d59b1f8d2b9c3c7a3b0f5f0e4d6a2c1",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/statuses.json")),
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("https://bitbucket.example.com/", result[0].origin);
+        assert_eq!(
+            "4e7f3c0a1d59b1f8d2b9c3c7a3b0f5f0e4d6a2c1",
+            result[0].build_id
+        );
+        assert_eq!("4e7f3c0a1d5", result[0].build_number);
+        assert_eq!("DUCK-BUILD", result[0].definition_id);
+        assert_eq!("Duck build", result[0].definition_name);
+        assert_eq!(BuildStatus::Failed, result[0].status);
+        assert_eq!("https://ci.example.com/duck/12", result[0].url);
+        assert_eq!(1588580832, result[0].started_at);
+        assert_eq!(Some(1588580832), result[0].finished_at);
+        assert_eq!("DUCK-DEPLOY", result[1].definition_id);
+        assert_eq!("DUCK-DEPLOY", result[1].definition_name);
+        assert_eq!(BuildStatus::Running, result[1].status);
+        assert_eq!(None, result[1].finished_at);
+    }
+
+    #[test]
+    fn should_keep_builds_for_same_commit_on_different_branches_apart() {
+        // Given
+        let collector = BitbucketCollector::<MockHttpClient>::new(&BitbucketConfiguration {
+            id: "bitbucket".to_owned(),
+            enabled: Some(true),
+            interval: None,
+            server_url: Some("https://bitbucket.example.com".to_owned()),
+            credentials: BitbucketCredentials::Bearer("SECRET".to_owned()),
+            repositories: vec!["duckhq/duck".to_owned()],
+            branches: vec!["master".to_owned(), "develop".to_owned()],
+        });
+        let client = collector.get_client();
+        for branch in &["master", "develop"] {
+            client.add_response(
+                MockHttpResponseBuilder::new(
+                    HttpMethod::Get,
+                    &format!(
+                        "https://bitbucket.example.com/rest/api/1.0/projects/duckhq/repos/duck/commits\
+                         ?until={}&limit=1",
+                        branch
+                    ),
+                )
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/commits.json")),
+            );
+        }
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://bitbucket.example.com/rest/build-status/1.0/commits/\
+                 4e7f3c0a1d59b1f8d2b9c3c7a3b0f5f0e4d6a2c1",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/statuses.json")),
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(4, result.len());
+        assert_eq!("master", result[0].branch);
+        assert_eq!("develop", result[2].branch);
+        assert_eq!(result[0].definition_id, result[2].definition_id);
+        assert_eq!(result[0].build_id, result[2].build_id);
+        assert_ne!(result[0].id, result[2].id);
+    }
+
+    #[test]
+    fn should_authenticate_using_app_password() {
+        // Given
+        let collector = create_collector(
+            None,
+            BitbucketCredentials::AppPassword {
+                username: "patrik".to_owned(),
+                password: "SECRET".to_owned(),
+            },
+        );
+        add_pipelines_response(&collector);
+
+        // When
+        collect(&collector);
+
+        // Then
+        let requests = collector.get_client().get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "Basic cGF0cmlrOlNFQ1JFVA==",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_authenticate_using_bearer_token() {
+        // Given
+        let collector = create_collector(None, BitbucketCredentials::Bearer("SECRET".to_owned()));
+        add_pipelines_response(&collector);
+
+        // When
+        collect(&collector);
+
+        // Then
+        let requests = collector.get_client().get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "Bearer SECRET",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+    }
+
+    #[test_case("PENDING", None, BuildStatus::Queued ; "Pending")]
+    #[test_case("IN_PROGRESS", None, BuildStatus::Running ; "In progress")]
+    #[test_case("COMPLETED", Some("SUCCESSFUL"), BuildStatus::Success ; "Successful")]
+    #[test_case("COMPLETED", Some("FAILED"), BuildStatus::Failed ; "Failed")]
+    #[test_case("COMPLETED", Some("ERROR"), BuildStatus::Failed ; "Error")]
+    #[test_case("COMPLETED", Some("STOPPED"), BuildStatus::Canceled ; "Stopped")]
+    fn should_map_pipeline_status(state: &str, result: Option<&str>, expected: BuildStatus) {
+        // Given
+        let pipeline = client::BitbucketPipeline {
+            build_number: 1,
+            state: client::BitbucketPipelineState {
+                name: state.to_owned(),
+                result: result.map(|r| client::BitbucketPipelineResult { name: r.to_owned() }),
+            },
+            created_on: "2020-05-04T08:27:12.441Z".to_owned(),
+            completed_on: None,
+        };
+        // When
+        let status = pipeline.get_status();
+        // Then
+        assert_eq!(expected, status);
+    }
+}
@@ -0,0 +1,230 @@
+use url::Url;
+
+use crate::config::{BitbucketConfiguration, BitbucketCredentials, Validate};
+use crate::DuckResult;
+
+impl Validate for BitbucketConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if let Some(server_url) = &self.server_url {
+            if let Err(e) = Url::parse(&server_url[..]) {
+                return Err(format_err!(
+                    "[{}] Bitbucket server URL is invalid: {}",
+                    self.id,
+                    e
+                ));
+            }
+        }
+        if self.repositories.is_empty() {
+            return Err(format_err!(
+                "[{}] Bitbucket configuration have not specified any repositories",
+                self.id
+            ));
+        }
+        for repository in self.repositories.iter() {
+            let parts: Vec<&str> = repository.split('/').collect();
+            if parts.len() != 2 || parts.iter().any(|p| p.is_empty()) {
+                return Err(format_err!(
+                    "[{}] The Bitbucket repository '{}' is invalid. Expected 'owner/repository'",
+                    self.id,
+                    repository
+                ));
+            }
+        }
+        if self.branches.is_empty() {
+            return Err(format_err!(
+                "[{}] Bitbucket configuration have not specified any branches",
+                self.id
+            ));
+        }
+
+        match &self.credentials {
+            BitbucketCredentials::Anonymous => {}
+            BitbucketCredentials::AppPassword { username, password } => {
+                if username.is_empty() {
+                    return Err(format_err!("[{}] Bitbucket username is empty", self.id));
+                }
+                if password.is_empty() {
+                    return Err(format_err!("[{}] Bitbucket app password is empty", self.id));
+                }
+            }
+            BitbucketCredentials::Bearer(token) => {
+                if token.is_empty() {
+                    return Err(format_err!("[{}] Bitbucket bearer token is empty", self.id));
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::*;
+    use crate::providers;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(
+        expected = "[bitbucket] Bitbucket server URL is invalid: relative URL without a base"
+    )]
+    fn should_return_error_if_bitbucket_server_url_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "bitbucket": {
+                            "id": "bitbucket",
+                            "serverUrl": "",
+                            "credentials": "anonymous",
+                            "repositories": [ "duckhq/duck" ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "[bitbucket] Bitbucket configuration have not specified any repositories"
+    )]
+    fn should_return_error_if_bitbucket_repositories_are_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "bitbucket": {
+                            "id": "bitbucket",
+                            "credentials": "anonymous",
+                            "repositories": [ ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[bitbucket] The Bitbucket repository")]
+    fn should_return_error_if_bitbucket_repository_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "bitbucket": {
+                            "id": "bitbucket",
+                            "credentials": "anonymous",
+                            "repositories": [ "duck" ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "[bitbucket] Bitbucket configuration have not specified any branches"
+    )]
+    fn should_return_error_if_bitbucket_branches_are_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "bitbucket": {
+                            "id": "bitbucket",
+                            "credentials": "anonymous",
+                            "repositories": [ "duckhq/duck" ],
+                            "branches": [ ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[bitbucket] Bitbucket app password is empty")]
+    fn should_return_error_if_bitbucket_app_password_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "bitbucket": {
+                            "id": "bitbucket",
+                            "credentials": {
+                                "appPassword": {
+                                    "username": "patrik",
+                                    "password": ""
+                                }
+                            },
+                            "repositories": [ "duckhq/duck" ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[bitbucket] Bitbucket bearer token is empty")]
+    fn should_return_error_if_bitbucket_bearer_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "bitbucket": {
+                            "id": "bitbucket",
+                            "credentials": {
+                                "bearer": ""
+                            },
+                            "repositories": [ "duckhq/duck" ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+}
@@ -0,0 +1,159 @@
+use log::{trace, warn};
+use url::form_urlencoded::byte_serialize;
+
+use crate::builds::BuildStatus;
+use crate::config::{CircleCiConfiguration, CircleCiCredentials};
+use crate::utils::date;
+use crate::utils::http::*;
+use crate::DuckResult;
+
+static API_URL: &str = "https://circleci.com/api/v2/";
+static WEB_URL: &str = "https://app.circleci.com/";
+
+pub struct CircleCiClient {
+    credentials: CircleCiCredentials,
+}
+
+impl CircleCiClient {
+    pub fn new(config: &CircleCiConfiguration) -> Self {
+        CircleCiClient {
+            credentials: config.credentials.clone(),
+        }
+    }
+
+    pub fn get_origin(&self) -> &str {
+        WEB_URL
+    }
+
+    pub fn get_pipelines(
+        &self,
+        client: &impl HttpClient,
+        project: &str,
+        branch: &str,
+    ) -> DuckResult<Vec<CircleCiPipeline>> {
+        let url = format!(
+            "{api}project/{project}/pipeline?branch={branch}",
+            api = API_URL,
+            project = project,
+            branch = encode(branch)
+        );
+
+        let body = self.send_get_request(client, url)?;
+        let page: CircleCiPage<CircleCiPipeline> = serde_json::from_str(&body[..])?;
+        Ok(page.items)
+    }
+
+    pub fn get_workflows(
+        &self,
+        client: &impl HttpClient,
+        pipeline: &CircleCiPipeline,
+    ) -> DuckResult<Vec<CircleCiWorkflow>> {
+        let url = format!(
+            "{api}pipeline/{pipeline}/workflow",
+            api = API_URL,
+            pipeline = pipeline.id
+        );
+
+        let body = self.send_get_request(client, url)?;
+        let page: CircleCiPage<CircleCiWorkflow> = serde_json::from_str(&body[..])?;
+        Ok(page.items)
+    }
+
+    fn send_get_request(&self, client: &impl HttpClient, url: String) -> DuckResult<String> {
+        trace!("Sending request to: {}", url);
+        let mut builder = HttpRequestBuilder::get(&url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+
+        self.credentials.authenticate(&mut builder);
+        let mut response = client.send(&builder)?;
+
+        trace!("Received response: {}", response.status());
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Received non 200 HTTP status code. ({})",
+                response.status()
+            ));
+        }
+
+        Ok(response.body()?)
+    }
+}
+
+impl CircleCiCredentials {
+    fn authenticate<'a>(&self, builder: &'a mut HttpRequestBuilder) {
+        match self {
+            CircleCiCredentials::Anonymous => {}
+            CircleCiCredentials::Token(token) => {
+                builder.add_header("Circle-Token", token);
+            }
+        }
+    }
+}
+
+fn encode(value: &str) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CircleCiPage<T> {
+    pub items: Vec<T>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CircleCiPipeline {
+    pub id: String,
+    pub number: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CircleCiWorkflow {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub project_slug: String,
+    pub pipeline_number: u64,
+    pub created_at: String,
+    pub stopped_at: Option<String>,
+}
+
+impl CircleCiWorkflow {
+    pub fn get_status(&self) -> BuildStatus {
+        match &self.status[..] {
+            "success" => BuildStatus::Success,
+            // A failing workflow is still running, but at least one
+            // job has failed so the workflow will eventually fail.
+            "failed" | "error" | "failing" | "unauthorized" => BuildStatus::Failed,
+            "running" => BuildStatus::Running,
+            // Workflows on hold are waiting for an approval.
+            "on_hold" => BuildStatus::Queued,
+            "canceled" => BuildStatus::Canceled,
+            "not_run" => BuildStatus::Skipped,
+            status => {
+                warn!("Unknown workflow status: {}", status);
+                BuildStatus::Unknown
+            }
+        }
+    }
+
+    pub fn get_url(&self) -> String {
+        format!(
+            "{web}pipelines/{project}/{number}/workflows/{id}",
+            web = WEB_URL,
+            project = self.project_slug,
+            number = self.pipeline_number,
+            id = self.id
+        )
+    }
+
+    pub fn get_started_timestamp(&self) -> DuckResult<i64> {
+        date::to_timestamp(&self.created_at, date::CIRCLECI_FORMAT)
+    }
+
+    pub fn get_finished_timestamp(&self) -> DuckResult<Option<i64>> {
+        match &self.stopped_at {
+            Some(stopped_at) => Ok(Some(date::to_timestamp(stopped_at, date::CIRCLECI_FORMAT)?)),
+            None => Ok(None),
+        }
+    }
+}
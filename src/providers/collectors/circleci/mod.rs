@@ -0,0 +1,306 @@
+use std::time::Duration;
+
+use log::{trace, warn};
+use waithandle::WaitHandleListener;
+
+use crate::builds::{Build, BuildBuilder};
+use crate::config::CircleCiConfiguration;
+use crate::providers::collectors::{Collector, CollectorInfo, CollectorLoader};
+use crate::utils::http::{HttpClient, ReqwestClient};
+use crate::DuckResult;
+
+use self::client::CircleCiClient;
+
+mod client;
+mod validation;
+
+impl CollectorLoader for CircleCiConfiguration {
+    fn load(&self) -> DuckResult<Box<dyn Collector>> {
+        Ok(Box::new(CircleCiCollector::<ReqwestClient>::new(self)))
+    }
+}
+
+pub struct CircleCiCollector<T: HttpClient + Default> {
+    http: T,
+    client: CircleCiClient,
+    project: String,
+    branches: Vec<String>,
+    info: CollectorInfo,
+}
+
+impl<T: HttpClient + Default> CircleCiCollector<T> {
+    pub fn new(config: &CircleCiConfiguration) -> Self {
+        CircleCiCollector {
+            http: Default::default(),
+            client: CircleCiClient::new(config),
+            project: config.project.clone(),
+            branches: config.branches.clone(),
+            info: CollectorInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    Option::None => true,
+                    Option::Some(e) => e,
+                },
+                provider: "CircleCI".to_owned(),
+                interval: config.interval,
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Collector for CircleCiCollector<T> {
+    fn info(&self) -> &CollectorInfo {
+        &self.info
+    }
+
+    fn collect(
+        &self,
+        listener: WaitHandleListener,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        for branch in self.branches.iter() {
+            if listener.check().unwrap() {
+                return Ok(());
+            }
+
+            // Pipelines are returned with the most recent one first.
+            let pipelines = match self.client.get_pipelines(&self.http, &self.project, branch) {
+                Ok(pipelines) => pipelines,
+                Err(e) => {
+                    warn!(
+                        "Could not get CircleCI pipelines for branch '{}' in '{}': {}",
+                        branch, self.project, e
+                    );
+                    continue;
+                }
+            };
+            let pipeline = match pipelines.first() {
+                Some(pipeline) => pipeline,
+                None => {
+                    trace!(
+                        "No pipelines found for branch '{}' in '{}'",
+                        branch,
+                        self.project
+                    );
+                    continue;
+                }
+            };
+
+            let workflows = match self.client.get_workflows(&self.http, pipeline) {
+                Ok(workflows) => workflows,
+                Err(e) => {
+                    warn!(
+                        "Could not get CircleCI workflows for pipeline #{} in '{}': {}",
+                        pipeline.number, self.project, e
+                    );
+                    continue;
+                }
+            };
+            for workflow in workflows.iter() {
+                let started_at = match workflow.get_started_timestamp() {
+                    Ok(started_at) => started_at,
+                    Err(e) => {
+                        warn!("Could not parse start time of workflow: {}", e);
+                        continue;
+                    }
+                };
+                let finished_at = match workflow.get_finished_timestamp() {
+                    Ok(finished_at) => finished_at,
+                    Err(e) => {
+                        warn!("Could not parse finish time of workflow: {}", e);
+                        continue;
+                    }
+                };
+
+                callback(
+                    BuildBuilder::new()
+                        .build_id(&workflow.id)
+                        .provider("CircleCI")
+                        .origin(self.client.get_origin())
+                        .collector(&self.info.id)
+                        .project_id(&self.project)
+                        .project_name(&self.project)
+                        .definition_id(&workflow.name)
+                        .definition_name(&workflow.name)
+                        .build_number(pipeline.number.to_string())
+                        .status(workflow.get_status())
+                        .url(workflow.get_url())
+                        .started_at(started_at)
+                        .finished_at(finished_at)
+                        .branch(branch)
+                        .build()
+                        .unwrap(),
+                );
+            }
+
+            // Wait for a litle time between calls.
+            if listener.wait(Duration::from_millis(300)).unwrap() {
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildStatus;
+    use crate::config::*;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+    use test_case::test_case;
+
+    fn create_collector(credentials: CircleCiCredentials) -> CircleCiCollector<MockHttpClient> {
+        create_collector_with_workflows(credentials, include_str!("test_data/workflows.json"))
+    }
+
+    fn create_collector_with_workflows(
+        credentials: CircleCiCredentials,
+        workflows: &str,
+    ) -> CircleCiCollector<MockHttpClient> {
+        let collector = CircleCiCollector::<MockHttpClient>::new(&CircleCiConfiguration {
+            id: "circleci".to_owned(),
+            enabled: Some(true),
+            interval: None,
+            credentials,
+            project: "gh/duckhq/duck".to_owned(),
+            branches: vec!["master".to_owned()],
+        });
+
+        let client = collector.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://circleci.com/api/v2/project/gh/duckhq/duck/pipeline?branch=master",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/pipelines.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://circleci.com/api/v2/pipeline/5034460f-c7c4-4c43-9457-de07e2029e7b/workflow",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(workflows),
+        );
+
+        collector
+    }
+
+    fn collect(collector: &CircleCiCollector<MockHttpClient>) -> Vec<Build> {
+        let (_, listener) = waithandle::new();
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+        result
+    }
+
+    #[test]
+    fn should_return_correct_provider_name() {
+        // Given
+        let collector = create_collector(CircleCiCredentials::Anonymous);
+        // When
+        let provider = &collector.info().provider;
+        // Then
+        assert_eq!("CircleCI", provider);
+    }
+
+    #[test]
+    fn should_get_correct_data() {
+        // Given
+        let collector = create_collector(CircleCiCredentials::Anonymous);
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("fda08377-fe7e-46b1-8992-3a7aaecac9c3", result[0].build_id);
+        assert_eq!("CircleCI", result[0].provider);
+        assert_eq!("circleci", result[0].collector);
+        assert_eq!("https://app.circleci.com/", result[0].origin);
+        assert_eq!("gh/duckhq/duck", result[0].project_id);
+        assert_eq!("gh/duckhq/duck", result[0].project_name);
+        assert_eq!("build-and-test", result[0].definition_id);
+        assert_eq!("build-and-test", result[0].definition_name);
+        assert_eq!("123", result[0].build_number);
+        assert_eq!(BuildStatus::Success, result[0].status);
+        assert_eq!("master", result[0].branch);
+        assert_eq!(
+            "https://app.circleci.com/pipelines/gh/duckhq/duck/123/workflows/fda08377-fe7e-46b1-8992-3a7aaecac9c3",
+            result[0].url
+        );
+        assert_eq!(1588580832, result[0].started_at);
+        assert_eq!(Some(1588581237), result[0].finished_at);
+        assert_eq!("deploy", result[1].definition_name);
+        assert_eq!(BuildStatus::Queued, result[1].status);
+        assert_eq!(None, result[1].finished_at);
+    }
+
+    #[test]
+    fn should_skip_workflow_if_finish_time_could_not_be_parsed() {
+        // Given
+        let workflows =
+            include_str!("test_data/workflows.json").replace("2020-05-04T08:33:57Z", "yesterday");
+        let collector = create_collector_with_workflows(CircleCiCredentials::Anonymous, &workflows);
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("deploy", result[0].definition_name);
+    }
+
+    #[test]
+    fn should_authenticate_using_token() {
+        // Given
+        let collector = create_collector(CircleCiCredentials::Token("SECRET".to_owned()));
+
+        // When
+        collect(&collector);
+
+        // Then
+        let requests = collector.get_client().get_sent_requests();
+        assert_eq!(2, requests.len());
+        assert_eq!("SECRET", requests[0].headers.get("Circle-Token").unwrap());
+        assert_eq!("SECRET", requests[1].headers.get("Circle-Token").unwrap());
+    }
+
+    #[test_case("success", BuildStatus::Success ; "Success")]
+    #[test_case("running", BuildStatus::Running ; "Running")]
+    #[test_case("failing", BuildStatus::Failed ; "Failing")]
+    #[test_case("failed", BuildStatus::Failed ; "Failed")]
+    #[test_case("error", BuildStatus::Failed ; "Error")]
+    #[test_case("on_hold", BuildStatus::Queued ; "On hold")]
+    #[test_case("canceled", BuildStatus::Canceled ; "Canceled")]
+    #[test_case("not_run", BuildStatus::Skipped ; "Not run")]
+    fn should_map_workflow_status(status: &str, expected: BuildStatus) {
+        // Given
+        let workflow = client::CircleCiWorkflow {
+            id: "fda08377-fe7e-46b1-8992-3a7aaecac9c3".to_owned(),
+            name: "build-and-test".to_owned(),
+            status: status.to_owned(),
+            project_slug: "gh/duckhq/duck".to_owned(),
+            pipeline_number: 123,
+            created_at: "2020-05-04T08:27:12Z".to_owned(),
+            stopped_at: None,
+        };
+        // When
+        let status = workflow.get_status();
+        // Then
+        assert_eq!(expected, status);
+    }
+}
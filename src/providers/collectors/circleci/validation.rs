@@ -0,0 +1,116 @@
+use crate::config::{CircleCiConfiguration, CircleCiCredentials, Validate};
+use crate::DuckResult;
+
+impl Validate for CircleCiConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        let parts: Vec<&str> = self.project.split('/').collect();
+        if parts.len() != 3 || parts.iter().any(|p| p.is_empty()) {
+            return Err(format_err!(
+                "[{}] The CircleCI project slug '{}' is invalid. Expected 'vcs/owner/repository'",
+                self.id,
+                self.project
+            ));
+        }
+        if self.branches.is_empty() {
+            return Err(format_err!(
+                "[{}] CircleCI configuration have not specified any branches",
+                self.id
+            ));
+        }
+
+        match &self.credentials {
+            CircleCiCredentials::Anonymous => {}
+            CircleCiCredentials::Token(token) => {
+                if token.is_empty() {
+                    return Err(format_err!("[{}] CircleCI token is empty", self.id));
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::*;
+    use crate::providers;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "[circleci] The CircleCI project slug")]
+    fn should_return_error_if_circleci_project_slug_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "circleci": {
+                            "id": "circleci",
+                            "credentials": "anonymous",
+                            "project": "duckhq/duck",
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[circleci] CircleCI configuration have not specified any branches")]
+    fn should_return_error_if_circleci_branches_are_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "circleci": {
+                            "id": "circleci",
+                            "credentials": "anonymous",
+                            "project": "gh/duckhq/duck",
+                            "branches": [ ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[circleci] CircleCI token is empty")]
+    fn should_return_error_if_circleci_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "circleci": {
+                            "id": "circleci",
+                            "credentials": {
+                                "token": ""
+                            },
+                            "project": "gh/duckhq/duck",
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+}
@@ -38,6 +38,7 @@ impl<T: HttpClient + Default> DebuggerCollector<T> {
                     Option::Some(e) => e,
                 },
                 provider: "Debugger".to_owned(),
+                interval: config.interval,
             },
         };
     }
@@ -101,6 +102,7 @@ mod tests {
         DebuggerCollector::<MockHttpClient>::new(&DebuggerConfiguration {
             id: "debug".to_owned(),
             enabled: Some(true),
+            interval: None,
             server_url: "http://localhost:5000".to_owned(),
         })
     }
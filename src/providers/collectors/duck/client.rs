@@ -18,23 +18,40 @@ impl DuckClient {
         }
     }
 
-    pub fn get_server_version(&self, client: &impl HttpClient) -> DuckResult<String> {
-        let url = format!("{}/api/server", owner = self.server_url,);
-
-        let body = self.send_get_request(client, url)?;
-        let info: DuckServerInfo = serde_json::from_str(&body[..])?;
+    /// Gets information about the remote Duck server.
+    /// Servers that predate the versioned API are queried
+    /// using the legacy endpoint instead.
+    pub fn get_server_info(&self, client: &impl HttpClient) -> DuckResult<DuckServerInfo> {
+        let url = format!("{owner}/api/v1/server", owner = self.server_url);
+        let body = match self.try_send_get_request(client, url)? {
+            Some(body) => body,
+            None => {
+                trace!("Remote Duck server does not support the versioned API");
+                let url = format!("{owner}/api/server", owner = self.server_url);
+                self.send_get_request(client, url)?
+            }
+        };
 
-        Ok(info.version)
+        Ok(serde_json::from_str(&body[..])?)
     }
 
-    pub fn get_builds(&self, client: &impl HttpClient) -> DuckResult<Vec<DuckBuild>> {
+    pub fn get_builds(
+        &self,
+        client: &impl HttpClient,
+        server: &DuckServerInfo,
+    ) -> DuckResult<Vec<DuckBuild>> {
+        let api = match server.api {
+            Some(_) => "api/v1",
+            None => "api",
+        };
         let url = match &self.view {
             Some(view) => format!(
-                "{owner}/api/builds/view/{view}",
+                "{owner}/{api}/builds/view/{view}",
                 owner = self.server_url,
+                api = api,
                 view = view
             ),
-            None => format!("{owner}/api/builds", owner = self.server_url),
+            None => format!("{owner}/{api}/builds", owner = self.server_url, api = api),
         };
 
         let body = self.send_get_request(client, url)?;
@@ -42,6 +59,21 @@ impl DuckClient {
     }
 
     fn send_get_request(&self, client: &impl HttpClient, url: String) -> DuckResult<String> {
+        match self.try_send_get_request(client, url)? {
+            Some(body) => Ok(body),
+            None => Err(format_err!(
+                "Received non 200 HTTP status code. ({})",
+                reqwest::StatusCode::NOT_FOUND
+            )),
+        }
+    }
+
+    /// Sends a GET request and returns `None` if the resource was not found.
+    fn try_send_get_request(
+        &self,
+        client: &impl HttpClient,
+        url: String,
+    ) -> DuckResult<Option<String>> {
         trace!("Sending request to: {}", url);
         let mut builder = HttpRequestBuilder::get(&url);
         builder.add_header("Content-Type", "application/json");
@@ -50,6 +82,9 @@ impl DuckClient {
         let mut response = client.send(&builder)?;
 
         trace!("Received response: {}", response.status());
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
         if !response.status().is_success() {
             return Err(format_err!(
                 "Received non 200 HTTP status code. ({})",
@@ -57,13 +92,73 @@ impl DuckClient {
             ));
         }
 
-        Ok(response.body()?)
+        Ok(Some(response.body()?))
     }
 }
 
 #[derive(Deserialize, Debug)]
 pub struct DuckServerInfo {
     pub version: String,
+    /// Not available for servers that predate the versioned API.
+    #[serde(default)]
+    pub api: Option<DuckApiInfo>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DuckApiInfo {
+    pub version: u16,
+    /// Not available for servers that only support a single API version.
+    #[serde(default)]
+    pub versions: Vec<u16>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+impl DuckServerInfo {
+    /// Makes sure that we can talk to the remote Duck server.
+    pub fn check_compatibility(&self, capabilities: &[&str]) -> DuckResult<()> {
+        match &self.api {
+            Some(api) => {
+                if !api.supports(crate::utils::API_VERSION) {
+                    return Err(format_err!(
+                        "The remote duck server {} does not support API version {}",
+                        self.version,
+                        crate::utils::API_VERSION
+                    ));
+                }
+                for capability in capabilities.iter() {
+                    if !api.capabilities.iter().any(|c| c == capability) {
+                        return Err(format_err!(
+                            "The remote duck server {} does not support '{}'",
+                            self.version,
+                            capability
+                        ));
+                    }
+                }
+            }
+            None => {
+                // Legacy servers have no API contract,
+                // so we require the exact same version.
+                if self.version != crate::utils::VERSION {
+                    return Err(format_err!(
+                        "The remote duck server version is {} ({} is required)",
+                        self.version,
+                        crate::utils::VERSION
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DuckApiInfo {
+    fn supports(&self, version: u16) -> bool {
+        if self.versions.is_empty() {
+            return self.version == version;
+        }
+        self.versions.contains(&version)
+    }
 }
 
 #[derive(Deserialize, Debug)]
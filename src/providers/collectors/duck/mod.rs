@@ -41,10 +41,18 @@ impl<T: HttpClient + Default> DuckCollector<T> {
                     Option::Some(e) => e,
                 },
                 provider: "Duck".to_owned(),
+                interval: config.interval,
             },
         };
     }
 
+    fn get_required_capabilities(&self) -> Vec<&'static str> {
+        match self.client.view {
+            Some(_) => vec!["builds", "views"],
+            None => vec!["builds"],
+        }
+    }
+
     #[cfg(test)]
     pub fn get_client(&self) -> &T {
         &self.http
@@ -61,16 +69,12 @@ impl<T: HttpClient + Default> Collector for DuckCollector<T> {
         _handle: WaitHandleListener,
         callback: &mut dyn FnMut(Build),
     ) -> DuckResult<()> {
-        // Make sure we're on the same version as the server
-        let version = self.client.get_server_version(&self.http)?;
-        if version != crate::utils::VERSION {
+        // Make sure that we can talk to the server
+        let server = self.client.get_server_info(&self.http)?;
+        if let Err(e) = server.check_compatibility(&self.get_required_capabilities()) {
             if self.version_error_switch.is_off() {
                 self.version_error_switch.turn_on();
-                return Err(format_err!(
-                    "The remote duck server version is {} ({} is required)",
-                    version,
-                    crate::utils::VERSION
-                ));
+                return Err(e);
             }
             return Ok(());
         } else {
@@ -80,7 +84,7 @@ impl<T: HttpClient + Default> Collector for DuckCollector<T> {
             self.version_error_switch.turn_off();
         }
 
-        let builds = self.client.get_builds(&self.http)?;
+        let builds = self.client.get_builds(&self.http, &server)?;
         for build in builds {
             callback(
                 BuildBuilder::new()
@@ -119,11 +123,29 @@ mod tests {
         DuckCollector::<MockHttpClient>::new(&DuckConfiguration {
             id: "duck_other".to_owned(),
             enabled: Some(true),
+            interval: None,
             server_url: "http://localhost:15826".to_owned(),
             view,
         })
     }
 
+    fn create_server_info(version: &str, api_version: u16, capabilities: &str) -> String {
+        format!(
+            "{{ \"version\": \"{}\", \"api\": {{ \"version\": {}, \"capabilities\": {} }} }}",
+            version, api_version, capabilities
+        )
+    }
+
+    fn collect(duck: &DuckCollector<MockHttpClient>) -> DuckResult<Vec<Build>> {
+        let (_, listener) = waithandle::new();
+        let mut result = Vec::<Build>::new();
+        duck.collect(listener, &mut |build: Build| {
+            // Store the results
+            result.push(build);
+        })?;
+        Ok(result)
+    }
+
     #[test]
     fn should_return_correct_provider_name() {
         // Given
@@ -141,12 +163,12 @@ mod tests {
         let client = duck.get_client();
 
         client.add_response(
-            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/server")
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/server")
                 .returns_status(StatusCode::OK)
-                .returns_body(format!("{{ \"version\": \"{}\" }}", crate::utils::VERSION)),
+                .returns_body(create_server_info("0.1.0", 1, "[\"builds\", \"views\"]")),
         );
         client.add_response(
-            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/builds")
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/builds")
                 .returns_status(StatusCode::OK)
                 .returns_body(include_str!("test_data/builds.json")),
         );
@@ -194,14 +216,14 @@ mod tests {
         let client = duck.get_client();
 
         client.add_response(
-            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/server")
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/server")
                 .returns_status(StatusCode::OK)
-                .returns_body(format!("{{ \"version\": \"{}\" }}", crate::utils::VERSION)),
+                .returns_body(create_server_info("0.1.0", 1, "[\"builds\", \"views\"]")),
         );
         client.add_response(
             MockHttpResponseBuilder::new(
                 HttpMethod::Get,
-                "http://localhost:15826/api/builds/view/foo",
+                "http://localhost:15826/api/v1/builds/view/foo",
             )
             .returns_status(StatusCode::OK)
             .returns_body(include_str!("test_data/view.json")),
@@ -236,4 +258,165 @@ mod tests {
         assert_eq!(1584617069, result[0].started_at);
         assert_eq!(1584617318, result[0].finished_at.unwrap());
     }
+
+    #[test]
+    fn should_return_error_if_api_version_is_not_supported() {
+        // Given
+        let duck = create_collector(None);
+        let client = duck.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/server")
+                .returns_status(StatusCode::OK)
+                .returns_body(create_server_info("9.0.0", 2, "[\"builds\"]")),
+        );
+
+        // When
+        let result = collect(&duck);
+
+        // Then
+        assert!(result.is_err());
+        assert_eq!(
+            "The remote duck server 9.0.0 does not support API version 1",
+            result.err().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn should_accept_server_that_supports_multiple_api_versions() {
+        // Given
+        let duck = create_collector(None);
+        let client = duck.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/server")
+                .returns_status(StatusCode::OK)
+                .returns_body(
+                    "{ \"version\": \"9.0.0\", \"api\": { \"version\": 2, \"versions\": [1, 2], \"capabilities\": [\"builds\"] } }",
+                ),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        let result = collect(&duck).unwrap();
+
+        // Then
+        assert_eq!(8, result.len());
+    }
+
+    #[test]
+    fn should_only_return_incompatibility_error_once() {
+        // Given
+        let duck = create_collector(None);
+        let client = duck.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/server")
+                .returns_status(StatusCode::OK)
+                .returns_body(create_server_info("9.0.0", 2, "[\"builds\"]")),
+        );
+
+        // When
+        let first = collect(&duck);
+        let second = collect(&duck);
+
+        // Then
+        assert!(first.is_err());
+        assert_eq!(0, second.unwrap().len());
+    }
+
+    #[test]
+    fn should_return_error_if_capability_is_not_supported() {
+        // Given
+        let duck = create_collector(Some("foo".to_owned()));
+        let client = duck.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/server")
+                .returns_status(StatusCode::OK)
+                .returns_body(create_server_info("0.1.0", 1, "[\"builds\"]")),
+        );
+
+        // When
+        let result = collect(&duck);
+
+        // Then
+        assert!(result.is_err());
+        assert_eq!(
+            "The remote duck server 0.1.0 does not support 'views'",
+            result.err().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn should_ignore_unknown_capabilities() {
+        // Given
+        let duck = create_collector(None);
+        let client = duck.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/server")
+                .returns_status(StatusCode::OK)
+                .returns_body(create_server_info("0.2.0", 1, "[\"builds\", \"foo\"]")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        let result = collect(&duck).unwrap();
+
+        // Then
+        assert_eq!(8, result.len());
+    }
+
+    #[test]
+    fn should_fall_back_to_legacy_api_if_versioned_api_is_not_available() {
+        // Given
+        let duck = create_collector(None);
+        let client = duck.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/server")
+                .returns_status(StatusCode::NOT_FOUND),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/server")
+                .returns_status(StatusCode::OK)
+                .returns_body(format!("{{ \"version\": \"{}\" }}", crate::utils::VERSION)),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        let result = collect(&duck).unwrap();
+
+        // Then
+        assert_eq!(8, result.len());
+    }
+
+    #[test]
+    fn should_return_error_if_legacy_server_version_differs() {
+        // Given
+        let duck = create_collector(None);
+        let client = duck.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/v1/server")
+                .returns_status(StatusCode::NOT_FOUND),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:15826/api/server")
+                .returns_status(StatusCode::OK)
+                .returns_body("{ \"version\": \"0.0.1\" }"),
+        );
+
+        // When
+        let result = collect(&duck);
+
+        // Then
+        assert!(result.is_err());
+    }
 }
@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
 use log::trace;
 
 use crate::builds::BuildStatus;
@@ -6,13 +10,25 @@ use crate::utils::date;
 use crate::utils::http::*;
 use crate::DuckResult;
 
+static DEFAULT_API_URL: &str = "https://api.github.com";
+
 pub struct GitHubClient {
     pub owner: String,
     pub repository: String,
-    pub workflow: String,
+    api_url: String,
     credentials: GitHubCredentials,
-    etag: std::sync::Mutex<Option<String>>,
-    cached: std::sync::Mutex<Option<String>>,
+    cache: Mutex<HashMap<String, CachedResponse>>,
+    installation_token: Mutex<Option<InstallationToken>>,
+}
+
+struct CachedResponse {
+    etag: String,
+    body: String,
+}
+
+struct InstallationToken {
+    token: String,
+    expires_at: i64,
 }
 
 impl GitHubClient {
@@ -20,56 +36,70 @@ impl GitHubClient {
         Self {
             owner: config.owner.clone(),
             repository: config.repository.clone(),
-            workflow: config.workflow.clone(),
+            api_url: config
+                .api_url
+                .as_ref()
+                .map(|url| url.trim_end_matches('/').to_owned())
+                .unwrap_or_else(|| DEFAULT_API_URL.to_owned()),
             credentials: config.credentials.clone(),
-            etag: std::sync::Mutex::new(None),
-            cached: std::sync::Mutex::new(None),
+            cache: Mutex::new(HashMap::new()),
+            installation_token: Mutex::new(None),
         }
     }
 
-    pub fn get_builds(&self, client: &impl HttpClient) -> DuckResult<GitHubResponse> {
+    /// Gets the latest runs for a specific workflow.
+    pub fn get_workflow_runs(
+        &self,
+        client: &impl HttpClient,
+        workflow: &str,
+    ) -> DuckResult<GitHubResponse> {
         let url = format!(
-            "https://api.github.com/repos/{owner}/{repo}/actions/workflows/{workflow}/runs?page=0&per_page=25",
+            "{api}/repos/{owner}/{repo}/actions/workflows/{workflow}/runs?per_page=100",
+            api = self.api_url,
             owner = self.owner,
             repo = self.repository,
-            workflow = self.workflow
+            workflow = workflow
         );
+        self.get(client, url)
+    }
+
+    /// Gets the latest runs for all workflows in the repository.
+    pub fn get_runs(&self, client: &impl HttpClient) -> DuckResult<GitHubResponse> {
+        let url = format!(
+            "{api}/repos/{owner}/{repo}/actions/runs?per_page=100",
+            api = self.api_url,
+            owner = self.owner,
+            repo = self.repository,
+        );
+        self.get(client, url)
+    }
 
+    fn get(&self, client: &impl HttpClient, url: String) -> DuckResult<GitHubResponse> {
         trace!("Sending request to: {}", url);
         let mut builder = HttpRequestBuilder::get(&url);
         builder.add_header("Content-Type", "application/json");
         builder.add_header("Accept", "application/json");
 
         // Do we have an etag?
-        let mut etag = self.etag.lock().unwrap();
-        if etag.is_some() {
-            let etag_value = etag.as_ref().unwrap();
-            trace!("Using etag {}", etag_value);
-            builder.add_header("If-None-Match", etag_value);
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(cached) = cache.get(&url) {
+            trace!("Using etag {}", cached.etag);
+            builder.add_header("If-None-Match", &cached.etag);
         }
 
-        self.credentials.authenticate(&mut builder);
+        self.authenticate(client, &mut builder)?;
         let mut response = client.send(&builder)?;
 
-        // Got an etag?
-        let new_etag = response.headers().get("ETag");
-        if let Some(new_etag) = new_etag {
-            let new_etag_value = new_etag.to_str()?;
-            trace!("Got a new etag: {}", new_etag_value);
-            *etag = Some(new_etag_value.to_owned());
-        }
-
         trace!("Received response: {}", response.status());
         if !response.status().is_success() {
             // Not modified?
             if response.status() == reqwest::StatusCode::NOT_MODIFIED {
-                let cached = self.cached.lock().unwrap();
-                if cached.is_none() {
-                    return Err(format_err!(
+                return match cache.get(&url) {
+                    Some(cached) => Ok(serde_json::from_str(&cached.body[..])?),
+                    None => Err(format_err!(
                         "Got a 304 not modified, but we didn't have a response cached"
-                    ));
-                }
-                return Ok(serde_json::from_str(&cached.as_ref().unwrap()[..])?);
+                    )),
+                };
             }
             return Err(format_err!(
                 "Received non 200 HTTP status code. ({})",
@@ -77,29 +107,139 @@ impl GitHubClient {
             ));
         }
 
+        // Got an etag?
+        let etag = match response.headers().get("ETag") {
+            Some(etag) => Some(etag.to_str()?.to_owned()),
+            None => None,
+        };
+
         // Get the response body.
         let body = response.body()?;
 
         // Cache the response. We need this if we return a 304 Not Modified.
-        trace!("Cached the response from GitHub.");
-        let mut cached = self.cached.lock().unwrap();
-        *cached = Some(body.clone());
+        if let Some(etag) = etag {
+            trace!("Got a new etag: {}", etag);
+            cache.insert(
+                url,
+                CachedResponse {
+                    etag,
+                    body: body.clone(),
+                },
+            );
+        }
 
         // Deserialize and return the value.
         Ok(serde_json::from_str(&body[..])?)
     }
-}
 
-impl GitHubCredentials {
-    fn authenticate<'a>(&self, builder: &'a mut HttpRequestBuilder) {
-        match self {
+    fn authenticate(
+        &self,
+        client: &impl HttpClient,
+        builder: &mut HttpRequestBuilder,
+    ) -> DuckResult<()> {
+        match &self.credentials {
             GitHubCredentials::Basic { username, password } => {
                 builder.basic_auth(username, Some(password));
             }
+            GitHubCredentials::Token(token) => {
+                builder.add_header("Authorization", &format!("token {}", token));
+            }
+            GitHubCredentials::App {
+                app_id,
+                installation_id,
+                private_key_path,
+            } => {
+                let token =
+                    self.get_installation_token(client, app_id, installation_id, private_key_path)?;
+                builder.add_header("Authorization", &format!("token {}", token));
+            }
+        };
+        Ok(())
+    }
+
+    /// Gets an installation access token for a GitHub App.
+    /// The token is reused until it's about to expire.
+    fn get_installation_token(
+        &self,
+        client: &impl HttpClient,
+        app_id: &str,
+        installation_id: &str,
+        private_key_path: &str,
+    ) -> DuckResult<String> {
+        let now = chrono::Utc::now().timestamp();
+        let mut installation_token = self.installation_token.lock().unwrap();
+        if let Some(token) = &*installation_token {
+            if token.expires_at - 60 > now {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let url = format!(
+            "{api}/app/installations/{installation}/access_tokens",
+            api = self.api_url,
+            installation = installation_id
+        );
+
+        trace!("Requesting installation token from: {}", url);
+        let mut builder = HttpRequestBuilder::post(url);
+        builder.add_header("Accept", "application/vnd.github.machine-man-preview+json");
+        builder.bearer(create_app_jwt(app_id, private_key_path, now)?);
+
+        let mut response = client.send(&builder)?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Could not get GitHub App installation token ({})",
+                response.status()
+            ));
         }
+
+        let body = response.body()?;
+        let token: GitHubInstallationToken = serde_json::from_str(&body[..])?;
+        *installation_token = Some(InstallationToken {
+            token: token.token.clone(),
+            expires_at: date::to_timestamp(&token.expires_at, date::GITHUB_FORMAT)?,
+        });
+
+        Ok(token.token)
     }
 }
 
+/// Creates a JWT that identifies the GitHub App.
+fn create_app_jwt(app_id: &str, private_key_path: &str, now: i64) -> DuckResult<String> {
+    let private_key = std::fs::read(private_key_path).map_err(|e| {
+        format_err!(
+            "Could not read GitHub App private key '{}' ({})",
+            private_key_path,
+            e
+        )
+    })?;
+    let key = EncodingKey::from_rsa_pem(&private_key[..])
+        .map_err(|e| format_err!("GitHub App private key is invalid ({})", e))?;
+
+    // Issue the token a little bit back in time to allow for clock drift.
+    let claims = GitHubAppClaims {
+        iat: now - 60,
+        exp: now + 600,
+        iss: app_id.to_owned(),
+    };
+
+    encode(&Header::new(Algorithm::RS256), &claims, &key)
+        .map_err(|e| format_err!("Could not create GitHub App token ({})", e))
+}
+
+#[derive(Serialize, Debug)]
+struct GitHubAppClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct GitHubInstallationToken {
+    token: String,
+    expires_at: String,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct GitHubResponse {
     pub total_count: u16,
@@ -109,6 +249,10 @@ pub struct GitHubResponse {
 #[derive(Deserialize, Debug)]
 pub struct GitHubWorkflowRun {
     pub id: u64,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub workflow_id: Option<u64>,
     #[serde(alias = "head_branch")]
     pub branch: String,
     #[serde(alias = "run_number")]
@@ -1,3 +1,6 @@
+use std::collections::HashSet;
+
+use log::warn;
 use waithandle::WaitHandleListener;
 
 use crate::builds::{Build, BuildBuilder};
@@ -6,7 +9,7 @@ use crate::providers::collectors::{Collector, CollectorInfo, CollectorLoader};
 use crate::utils::http::{HttpClient, ReqwestClient};
 use crate::DuckResult;
 
-use self::client::GitHubClient;
+use self::client::{GitHubClient, GitHubWorkflowRun};
 
 mod client;
 mod validation;
@@ -20,6 +23,9 @@ impl CollectorLoader for GitHubConfiguration {
 pub struct GitHubCollector<T: HttpClient + Default> {
     client: GitHubClient,
     http: T,
+    workflows: Vec<String>,
+    branches: Option<Vec<String>>,
+    excluded_branches: Option<Vec<String>>,
     info: CollectorInfo,
 }
 
@@ -28,6 +34,9 @@ impl<T: HttpClient + Default> GitHubCollector<T> {
         return GitHubCollector {
             client: GitHubClient::new(config),
             http: Default::default(),
+            workflows: config.get_workflows(),
+            branches: config.branches.clone(),
+            excluded_branches: config.excluded_branches.clone(),
             info: CollectorInfo {
                 id: config.id.clone(),
                 enabled: match config.enabled {
@@ -35,6 +44,7 @@ impl<T: HttpClient + Default> GitHubCollector<T> {
                     Option::Some(e) => e,
                 },
                 provider: "GitHub".to_owned(),
+                interval: config.interval,
             },
         };
     }
@@ -43,6 +53,52 @@ impl<T: HttpClient + Default> GitHubCollector<T> {
     pub fn get_client(&self) -> &T {
         &self.http
     }
+
+    fn is_branch_included(&self, branch: &str) -> bool {
+        if let Some(branches) = &self.branches {
+            if !branches.iter().any(|b| b == branch) {
+                return false;
+            }
+        }
+        if let Some(excluded) = &self.excluded_branches {
+            if excluded.iter().any(|b| b == branch) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn create_build(
+        &self,
+        run: &GitHubWorkflowRun,
+        origin: String,
+        definition_id: &str,
+        definition_name: &str,
+    ) -> DuckResult<Build> {
+        Ok(BuildBuilder::new()
+            .build_id(run.id.to_string())
+            .provider("GitHub")
+            .origin(origin)
+            .collector(&self.info.id)
+            .project_id(format!(
+                "{}_{}",
+                &self.client.owner, &self.client.repository
+            ))
+            .project_name(format!(
+                "{}/{}",
+                &self.client.owner, &self.client.repository
+            ))
+            .definition_id(definition_id)
+            .definition_name(definition_name)
+            .build_number(run.number.to_string())
+            .status(run.get_status()?)
+            .url(&run.html_url)
+            .started_at(run.get_started_timestamp()?)
+            .finished_at(run.get_finished_timestamp()?)
+            .branch(&run.branch)
+            .build()
+            .unwrap())
+    }
 }
 
 impl<T: HttpClient + Default> Collector for GitHubCollector<T> {
@@ -55,53 +111,70 @@ impl<T: HttpClient + Default> Collector for GitHubCollector<T> {
         listener: WaitHandleListener,
         callback: &mut dyn FnMut(Build),
     ) -> DuckResult<()> {
-        let response = self.client.get_builds(&self.http)?;
-
-        // Convert the workflow run to a Duck build representation.
+        // Convert the workflow runs to a Duck build representation.
         let mut builds = Vec::<Build>::new();
-        for run in response.workflow_runs.iter() {
-            if listener.check().unwrap() {
-                return Ok(());
+        if self.workflows.is_empty() {
+            let response = self.client.get_runs(&self.http)?;
+            for run in response.workflow_runs.iter() {
+                if listener.check().unwrap() {
+                    return Ok(());
+                }
+                if !self.is_branch_included(&run.branch) {
+                    continue;
+                }
+
+                let definition_id = match run.workflow_id {
+                    Some(id) => id.to_string(),
+                    None => continue,
+                };
+                let definition_name = run.name.as_ref().unwrap_or(&definition_id);
+                builds.push(self.create_build(
+                    run,
+                    format!("{}/{}", &self.client.owner, &self.client.repository),
+                    &definition_id,
+                    definition_name,
+                )?);
             }
+        } else {
+            for workflow in self.workflows.iter() {
+                if listener.check().unwrap() {
+                    return Ok(());
+                }
 
-            builds.push(
-                BuildBuilder::new()
-                    .build_id(run.id.to_string())
-                    .provider("GitHub")
-                    .origin(format!(
-                        "{}/{}/{}",
-                        &self.client.owner, &self.client.repository, &self.client.workflow
-                    ))
-                    .collector(&self.info.id)
-                    .project_id(format!(
-                        "{}_{}",
-                        &self.client.owner, &self.client.repository
-                    ))
-                    .project_name(format!(
-                        "{}/{}",
-                        &self.client.owner, &self.client.repository
-                    ))
-                    .definition_id(&self.client.workflow)
-                    .definition_name(&self.client.workflow)
-                    .build_number(run.number.to_string())
-                    .status(run.get_status()?)
-                    .url(&run.html_url)
-                    .started_at(run.get_started_timestamp()?)
-                    .finished_at(run.get_finished_timestamp()?)
-                    .branch(&run.branch)
-                    .build()
-                    .unwrap(),
-            );
+                let response = match self.client.get_workflow_runs(&self.http, workflow) {
+                    Ok(response) => response,
+                    Err(e) => {
+                        warn!(
+                            "Could not get runs for GitHub workflow '{}': {}",
+                            workflow, e
+                        );
+                        continue;
+                    }
+                };
+                for run in response.workflow_runs.iter() {
+                    if !self.is_branch_included(&run.branch) {
+                        continue;
+                    }
+                    builds.push(self.create_build(
+                        run,
+                        format!(
+                            "{}/{}/{}",
+                            &self.client.owner, &self.client.repository, workflow
+                        ),
+                        workflow,
+                        workflow,
+                    )?);
+                }
+            }
         }
 
         // Sort the builds by date.
         builds.sort_by(|a, b| b.started_at.cmp(&a.started_at));
 
-        // Now only keep the latest ones of the
-        let mut branches = std::collections::HashSet::<&String>::new();
+        // Now only keep the latest ones for each workflow and branch.
+        let mut seen = HashSet::<(&String, &String)>::new();
         for build in builds.iter() {
-            if !branches.contains(&build.branch) {
-                branches.insert(&build.branch);
+            if seen.insert((&build.definition_id, &build.branch)) {
                 callback(build.clone()); // Really want to get rid of this clone...
             }
         }
@@ -118,18 +191,48 @@ mod tests {
     use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
     use reqwest::StatusCode;
 
-    fn create_collector() -> GitHubCollector<MockHttpClient> {
-        GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+    fn create_config() -> GitHubConfiguration {
+        GitHubConfiguration {
             id: "github".to_owned(),
             enabled: Some(true),
+            interval: None,
+            api_url: None,
             owner: "spectresystems".to_owned(),
             repository: "duck".to_owned(),
-            workflow: "pull_request.yml".to_owned(),
+            workflow: Some("pull_request.yml".to_owned()),
+            workflows: None,
+            branches: None,
+            excluded_branches: None,
+            webhook_secret: None,
             credentials: GitHubCredentials::Basic {
                 username: "foo".to_owned(),
                 password: "lol".to_owned(),
             },
-        })
+        }
+    }
+
+    fn create_collector() -> GitHubCollector<MockHttpClient> {
+        GitHubCollector::<MockHttpClient>::new(&create_config())
+    }
+
+    fn collect(github: &GitHubCollector<MockHttpClient>) -> Vec<Build> {
+        let (_, listener) = waithandle::new();
+        let mut result = Vec::<Build>::new();
+        github
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+        result
+    }
+
+    fn add_runs_response(github: &GitHubCollector<MockHttpClient>, url: &str) {
+        github.get_client().add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, url)
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/runs.json")),
+        );
     }
 
     #[test]
@@ -151,7 +254,7 @@ mod tests {
         client.add_response(
             MockHttpResponseBuilder::new(
                 HttpMethod::Get,
-                "https://api.github.com/repos/spectresystems/duck/actions/workflows/pull_request.yml/runs?page=0&per_page=25"
+                "https://api.github.com/repos/spectresystems/duck/actions/workflows/pull_request.yml/runs?per_page=100"
             )
             .returns_status(StatusCode::OK)
             .returns_body(include_str!("test_data/builds.json"))
@@ -187,4 +290,227 @@ mod tests {
         assert_eq!(1580601850, result[0].started_at);
         assert_eq!(1580602219, result[0].finished_at.unwrap());
     }
+
+    #[test]
+    fn should_get_builds_from_all_workflows_if_none_are_specified() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            workflow: None,
+            ..create_config()
+        });
+        add_runs_response(
+            &github,
+            "https://api.github.com/repos/spectresystems/duck/actions/runs?per_page=100",
+        );
+
+        // When
+        let result = collect(&github);
+
+        // Then
+        assert_eq!(3, result.len());
+        assert_eq!("89125912", result[0].build_id);
+        assert_eq!("411889", result[0].definition_id);
+        assert_eq!("Continuous integration", result[0].definition_name);
+        assert_eq!("master", result[0].branch);
+        assert_eq!(BuildStatus::Running, result[0].status);
+        assert_eq!("spectresystems/duck", result[0].origin);
+        assert_eq!(1588580832, result[0].started_at);
+        assert_eq!(None, result[0].finished_at);
+        assert_eq!("512004", result[1].definition_id);
+        assert_eq!("Publish", result[1].definition_name);
+        assert_eq!("feature/GH-99", result[2].branch);
+    }
+
+    #[test]
+    fn should_get_builds_from_multiple_workflows() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            workflow: None,
+            workflows: Some(vec!["ci.yml".to_owned(), "publish.yml".to_owned()]),
+            ..create_config()
+        });
+        add_runs_response(
+            &github,
+            "https://api.github.com/repos/spectresystems/duck/actions/workflows/ci.yml/runs?per_page=100",
+        );
+        add_runs_response(
+            &github,
+            "https://api.github.com/repos/spectresystems/duck/actions/workflows/publish.yml/runs?per_page=100",
+        );
+
+        // When
+        let result = collect(&github);
+
+        // Then
+        let requests = github.get_client().get_sent_requests();
+        assert_eq!(2, requests.len());
+        assert_eq!(4, result.len());
+        assert_eq!("ci.yml", result[0].definition_id);
+        assert_eq!("spectresystems/duck/ci.yml", result[0].origin);
+        assert_eq!("publish.yml", result[1].definition_id);
+    }
+
+    #[test]
+    fn should_continue_with_next_workflow_if_runs_could_not_be_retrieved() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            workflow: None,
+            workflows: Some(vec!["ci.yml".to_owned(), "publish.yml".to_owned()]),
+            ..create_config()
+        });
+        github.get_client().add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://api.github.com/repos/spectresystems/duck/actions/workflows/ci.yml/runs?per_page=100",
+            )
+            .returns_status(StatusCode::INTERNAL_SERVER_ERROR),
+        );
+        add_runs_response(
+            &github,
+            "https://api.github.com/repos/spectresystems/duck/actions/workflows/publish.yml/runs?per_page=100",
+        );
+
+        // When
+        let result = collect(&github);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert!(result.iter().all(|b| b.definition_id == "publish.yml"));
+    }
+
+    #[test]
+    fn should_only_get_builds_from_included_branches() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            workflow: None,
+            branches: Some(vec!["feature/GH-99".to_owned()]),
+            ..create_config()
+        });
+        add_runs_response(
+            &github,
+            "https://api.github.com/repos/spectresystems/duck/actions/runs?per_page=100",
+        );
+
+        // When
+        let result = collect(&github);
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("feature/GH-99", result[0].branch);
+    }
+
+    #[test]
+    fn should_not_get_builds_from_excluded_branches() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            workflow: None,
+            excluded_branches: Some(vec!["feature/GH-99".to_owned()]),
+            ..create_config()
+        });
+        add_runs_response(
+            &github,
+            "https://api.github.com/repos/spectresystems/duck/actions/runs?per_page=100",
+        );
+
+        // When
+        let result = collect(&github);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert!(result.iter().all(|b| b.branch == "master"));
+    }
+
+    #[test]
+    fn should_use_configured_api_url() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            api_url: Some("https://github.example.com/api/v3/".to_owned()),
+            ..create_config()
+        });
+        add_runs_response(
+            &github,
+            "https://github.example.com/api/v3/repos/spectresystems/duck/actions/workflows/pull_request.yml/runs?per_page=100",
+        );
+
+        // When
+        let result = collect(&github);
+
+        // Then
+        assert_eq!(2, result.len());
+    }
+
+    #[test]
+    fn should_authenticate_using_token() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            credentials: GitHubCredentials::Token("SECRET".to_owned()),
+            ..create_config()
+        });
+        add_runs_response(
+            &github,
+            "https://api.github.com/repos/spectresystems/duck/actions/workflows/pull_request.yml/runs?per_page=100",
+        );
+
+        // When
+        collect(&github);
+
+        // Then
+        let requests = github.get_client().get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "token SECRET",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_authenticate_as_github_app_installation() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            credentials: GitHubCredentials::App {
+                app_id: "1234".to_owned(),
+                installation_id: "5678".to_owned(),
+                private_key_path: concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/src/providers/collectors/github/test_data/app.pem"
+                )
+                .to_owned(),
+            },
+            ..create_config()
+        });
+        github.get_client().add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://api.github.com/app/installations/5678/access_tokens",
+            )
+            .returns_status(StatusCode::CREATED)
+            .returns_body(include_str!("test_data/installation_token.json")),
+        );
+        add_runs_response(
+            &github,
+            "https://api.github.com/repos/spectresystems/duck/actions/workflows/pull_request.yml/runs?per_page=100",
+        );
+
+        // When
+        collect(&github);
+        collect(&github);
+
+        // Then
+        let requests = github.get_client().get_sent_requests();
+        assert_eq!(3, requests.len());
+        assert_eq!(HttpMethod::Post, requests[0].method);
+        assert!(requests[0]
+            .headers
+            .get("Authorization")
+            .unwrap()
+            .starts_with("Bearer "));
+        assert_eq!(
+            "token v1.1f699f1069f60xxx",
+            requests[1].headers.get("Authorization").unwrap()
+        );
+        assert_eq!(
+            "token v1.1f699f1069f60xxx",
+            requests[2].headers.get("Authorization").unwrap()
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use url::Url;
+
 use crate::config::{GitHubConfiguration, GitHubCredentials, Validate};
 use crate::DuckResult;
 
@@ -9,9 +11,24 @@ impl Validate for GitHubConfiguration {
         if self.repository.is_empty() {
             return Err(format_err!("[{}] GitHub repository is empty", self.id));
         }
-        if self.workflow.is_empty() {
+        if self.get_workflows().iter().any(|w| w.is_empty()) {
             return Err(format_err!("[{}] GitHub workflow is empty", self.id));
         }
+        if let Some(api_url) = &self.api_url {
+            if let Err(e) = Url::parse(&api_url[..]) {
+                return Err(format_err!(
+                    "[{}] GitHub API URL is invalid: {}",
+                    self.id,
+                    e
+                ));
+            }
+        }
+
+        if let Some(secret) = &self.webhook_secret {
+            if secret.is_empty() {
+                return Err(format_err!("[{}] GitHub webhook secret is empty", self.id));
+            }
+        }
 
         match &self.credentials {
             GitHubCredentials::Basic { username, password } => {
@@ -22,6 +39,32 @@ impl Validate for GitHubConfiguration {
                     return Err(format_err!("[{}] GitHub password is empty", self.id));
                 }
             }
+            GitHubCredentials::Token(token) => {
+                if token.is_empty() {
+                    return Err(format_err!("[{}] GitHub token is empty", self.id));
+                }
+            }
+            GitHubCredentials::App {
+                app_id,
+                installation_id,
+                private_key_path,
+            } => {
+                if app_id.is_empty() {
+                    return Err(format_err!("[{}] GitHub App ID is empty", self.id));
+                }
+                if installation_id.is_empty() {
+                    return Err(format_err!(
+                        "[{}] GitHub App installation ID is empty",
+                        self.id
+                    ));
+                }
+                if private_key_path.is_empty() {
+                    return Err(format_err!(
+                        "[{}] GitHub App private key path is empty",
+                        self.id
+                    ));
+                }
+            }
         };
 
         Ok(())
@@ -221,4 +264,152 @@ mod tests {
 
         providers::create_collectors(&config).unwrap();
     }
+
+    #[test]
+    #[should_panic(expected = "[duck_pullrequests] GitHub workflow is empty")]
+    fn should_return_error_if_any_github_workflow_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "github": {
+                            "id": "duck_pullrequests",
+                            "owner": "spectresystems",
+                            "repository": "duck",
+                            "workflows": [ "ci.yml", "" ],
+                            "credentials": {
+                                "token": "SECRET"
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "[duck_pullrequests] GitHub API URL is invalid: relative URL without a base"
+    )]
+    fn should_return_error_if_github_api_url_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "github": {
+                            "id": "duck_pullrequests",
+                            "apiUrl": "github.example.com",
+                            "owner": "spectresystems",
+                            "repository": "duck",
+                            "credentials": {
+                                "token": "SECRET"
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[duck_pullrequests] GitHub token is empty")]
+    fn should_return_error_if_github_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "github": {
+                            "id": "duck_pullrequests",
+                            "owner": "spectresystems",
+                            "repository": "duck",
+                            "credentials": {
+                                "token": ""
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[duck_pullrequests] GitHub App private key path is empty")]
+    fn should_return_error_if_github_app_private_key_path_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "github": {
+                            "id": "duck_pullrequests",
+                            "owner": "spectresystems",
+                            "repository": "duck",
+                            "credentials": {
+                                "app": {
+                                    "appId": "1234",
+                                    "installationId": "5678",
+                                    "privateKeyPath": ""
+                                }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[duck_pullrequests] GitHub webhook secret is empty")]
+    fn should_return_error_if_github_webhook_secret_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "github": {
+                            "id": "duck_pullrequests",
+                            "owner": "spectresystems",
+                            "repository": "duck",
+                            "workflow": "pull_request.yml",
+                            "webhookSecret": "",
+                            "credentials": {
+                                "basic": {
+                                    "username": "patrik",
+                                    "password": "hunter1!"
+                                }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
 }
@@ -0,0 +1,167 @@
+use log::{trace, warn};
+use url::form_urlencoded::byte_serialize;
+use url::Url;
+
+use crate::builds::BuildStatus;
+use crate::config::{GitLabConfiguration, GitLabCredentials};
+use crate::utils::date;
+use crate::utils::http::*;
+use crate::DuckResult;
+
+pub struct GitLabClient {
+    server_url: Url,
+    credentials: GitLabCredentials,
+}
+
+impl GitLabClient {
+    pub fn new(config: &GitLabConfiguration) -> Self {
+        GitLabClient {
+            server_url: match &config.server_url {
+                Some(url) => Url::parse(&with_trailing_slash(url)[..]).unwrap(),
+                None => Url::parse("https://gitlab.com").unwrap(),
+            },
+            credentials: config.credentials.clone(),
+        }
+    }
+
+    pub fn get_origin(&self) -> &str {
+        self.server_url.as_str()
+    }
+
+    pub fn get_project(
+        &self,
+        client: &impl HttpClient,
+        project: &str,
+    ) -> DuckResult<GitLabProject> {
+        let url = format!(
+            "{server}api/v4/projects/{project}",
+            server = self.server_url,
+            project = encode(project)
+        );
+
+        let body = self.send_get_request(client, url)?;
+        Ok(serde_json::from_str(&body[..])?)
+    }
+
+    pub fn get_pipelines(
+        &self,
+        client: &impl HttpClient,
+        project: &GitLabProject,
+        branch: &str,
+    ) -> DuckResult<Vec<GitLabPipeline>> {
+        let url = format!(
+            "{server}api/v4/projects/{project}/pipelines?ref={branch}\
+             &order_by=id&sort=desc&per_page=1",
+            server = self.server_url,
+            project = project.id,
+            branch = encode(branch)
+        );
+
+        let body = self.send_get_request(client, url)?;
+        Ok(serde_json::from_str(&body[..])?)
+    }
+
+    fn send_get_request(&self, client: &impl HttpClient, url: String) -> DuckResult<String> {
+        trace!("Sending request to: {}", url);
+        let mut builder = HttpRequestBuilder::get(&url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+
+        self.credentials.authenticate(&mut builder);
+        let mut response = client.send(&builder)?;
+
+        trace!("Received response: {}", response.status());
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Received non 200 HTTP status code. ({})",
+                response.status()
+            ));
+        }
+
+        Ok(response.body()?)
+    }
+}
+
+impl GitLabCredentials {
+    fn authenticate<'a>(&self, builder: &'a mut HttpRequestBuilder) {
+        match self {
+            GitLabCredentials::Anonymous => {}
+            GitLabCredentials::AccessToken(token) => {
+                builder.add_header("PRIVATE-TOKEN", token);
+            }
+        }
+    }
+}
+
+fn with_trailing_slash(url: &str) -> String {
+    if url.ends_with('/') {
+        url.to_owned()
+    } else {
+        format!("{}/", url)
+    }
+}
+
+fn encode(value: &str) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitLabProject {
+    pub id: u64,
+    pub name: String,
+    pub name_with_namespace: String,
+    pub path_with_namespace: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitLabPipeline {
+    pub id: u64,
+    #[serde(alias = "ref")]
+    pub branch: String,
+    pub status: String,
+    pub web_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl GitLabPipeline {
+    pub fn get_status(&self) -> BuildStatus {
+        match &self.status[..] {
+            "success" => BuildStatus::Success,
+            "failed" => BuildStatus::Failed,
+            "canceled" => BuildStatus::Canceled,
+            "skipped" => BuildStatus::Skipped,
+            "running" => BuildStatus::Running,
+            "created"
+            | "waiting_for_resource"
+            | "preparing"
+            | "pending"
+            | "scheduled"
+            | "manual" => BuildStatus::Queued,
+            status => {
+                warn!("Unknown pipeline status: {}", status);
+                BuildStatus::Unknown
+            }
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        match &self.status[..] {
+            "success" | "failed" | "canceled" | "skipped" => true,
+            _ => false,
+        }
+    }
+
+    pub fn get_started_timestamp(&self) -> DuckResult<i64> {
+        date::to_timestamp(&self.created_at, date::GITLAB_FORMAT)
+    }
+
+    pub fn get_finished_timestamp(&self) -> DuckResult<Option<i64>> {
+        if self.is_finished() {
+            let result = date::to_timestamp(&self.updated_at, date::GITLAB_FORMAT)?;
+            Ok(Some(result))
+        } else {
+            Ok(None)
+        }
+    }
+}
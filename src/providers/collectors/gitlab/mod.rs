@@ -0,0 +1,359 @@
+use std::time::Duration;
+
+use log::{trace, warn};
+use waithandle::WaitHandleListener;
+
+use crate::builds::{Build, BuildBuilder};
+use crate::config::GitLabConfiguration;
+use crate::providers::collectors::{Collector, CollectorInfo, CollectorLoader};
+use crate::utils::http::{HttpClient, ReqwestClient};
+use crate::DuckResult;
+
+use self::client::GitLabClient;
+
+mod client;
+mod validation;
+
+impl CollectorLoader for GitLabConfiguration {
+    fn load(&self) -> DuckResult<Box<dyn Collector>> {
+        Ok(Box::new(GitLabCollector::<ReqwestClient>::new(self)))
+    }
+}
+
+pub struct GitLabCollector<T: HttpClient + Default> {
+    http: T,
+    client: GitLabClient,
+    projects: Vec<String>,
+    branches: Vec<String>,
+    info: CollectorInfo,
+}
+
+impl<T: HttpClient + Default> GitLabCollector<T> {
+    pub fn new(config: &GitLabConfiguration) -> Self {
+        GitLabCollector {
+            http: Default::default(),
+            client: GitLabClient::new(config),
+            projects: config.projects.clone(),
+            branches: config.branches.clone(),
+            info: CollectorInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    Option::None => true,
+                    Option::Some(e) => e,
+                },
+                provider: "GitLab".to_owned(),
+                interval: config.interval,
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Collector for GitLabCollector<T> {
+    fn info(&self) -> &CollectorInfo {
+        &self.info
+    }
+
+    fn collect(
+        &self,
+        listener: WaitHandleListener,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        for project in self.projects.iter() {
+            if listener.check().unwrap() {
+                return Ok(());
+            }
+
+            // Get information about the project.
+            let project = match self.client.get_project(&self.http, project) {
+                Ok(p) => p,
+                Err(e) => {
+                    warn!("Could not get GitLab project '{}': {}", project, e);
+                    continue;
+                }
+            };
+
+            for branch in self.branches.iter() {
+                if listener.check().unwrap() {
+                    return Ok(());
+                }
+
+                let pipelines = match self.client.get_pipelines(&self.http, &project, branch) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        warn!(
+                            "Could not get GitLab pipelines for branch '{}' in '{}': {}",
+                            branch, project.path_with_namespace, e
+                        );
+                        continue;
+                    }
+                };
+                match pipelines.first() {
+                    None => trace!(
+                        "No pipelines found for branch '{}' in '{}'",
+                        branch,
+                        project.path_with_namespace
+                    ),
+                    Some(pipeline) => {
+                        callback(
+                            BuildBuilder::new()
+                                .build_id(pipeline.id.to_string())
+                                .provider("GitLab")
+                                .origin(self.client.get_origin())
+                                .collector(&self.info.id)
+                                .project_id(project.id.to_string())
+                                .project_name(&project.name_with_namespace)
+                                .definition_id(&project.path_with_namespace)
+                                .definition_name(&project.name)
+                                .build_number(pipeline.id.to_string())
+                                .status(pipeline.get_status())
+                                .url(&pipeline.web_url)
+                                .started_at(pipeline.get_started_timestamp()?)
+                                .finished_at(pipeline.get_finished_timestamp()?)
+                                .branch(&pipeline.branch)
+                                .build()
+                                .unwrap(),
+                        );
+                    }
+                };
+
+                // Wait for a litle time between calls.
+                if listener.wait(Duration::from_millis(300)).unwrap() {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildStatus;
+    use crate::config::*;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+
+    fn create_collector(server_url: Option<String>) -> GitLabCollector<MockHttpClient> {
+        GitLabCollector::<MockHttpClient>::new(&GitLabConfiguration {
+            id: "gitlab".to_owned(),
+            enabled: Some(true),
+            interval: None,
+            server_url,
+            credentials: GitLabCredentials::AccessToken("SECRET".to_owned()),
+            projects: vec!["duckhq/duck".to_owned()],
+            branches: vec!["master".to_owned()],
+            webhook_secret: None,
+        })
+    }
+
+    #[test]
+    fn should_return_correct_provider_name() {
+        // Given
+        let collector = create_collector(None);
+        // When
+        let provider = &collector.info().provider;
+        // Then
+        assert_eq!("GitLab", provider);
+    }
+
+    #[test]
+    fn should_get_correct_data_for_default_server_address() {
+        // Given
+        let collector = create_collector(None);
+        let client = collector.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://gitlab.com/api/v4/projects/duckhq%2Fduck",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/project.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://gitlab.com/api/v4/projects/18721633/pipelines?ref=master&order_by=id&sort=desc&per_page=1",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/pipelines.json")),
+        );
+
+        let (_, listener) = waithandle::new();
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("141208523", result[0].build_id);
+        assert_eq!("GitLab", result[0].provider);
+        assert_eq!("gitlab", result[0].collector);
+        assert_eq!("https://gitlab.com/", result[0].origin);
+        assert_eq!("18721633", result[0].project_id);
+        assert_eq!("Duck HQ / Duck", result[0].project_name);
+        assert_eq!("duckhq/duck", result[0].definition_id);
+        assert_eq!("Duck", result[0].definition_name);
+        assert_eq!("141208523", result[0].build_number);
+        assert_eq!(BuildStatus::Success, result[0].status);
+        assert_eq!("master", result[0].branch);
+        assert_eq!(
+            "https://gitlab.com/duckhq/duck/-/pipelines/141208523",
+            result[0].url
+        );
+        assert_eq!(1588580832, result[0].started_at);
+        assert_eq!(1588581177, result[0].finished_at.unwrap());
+    }
+
+    #[test]
+    fn should_get_correct_data_for_self_hosted_server_address() {
+        // Given
+        let collector = create_collector(Some("https://git.example.com/gitlab".to_owned()));
+        let client = collector.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://git.example.com/gitlab/api/v4/projects/duckhq%2Fduck",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/project.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://git.example.com/gitlab/api/v4/projects/18721633/pipelines?ref=master&order_by=id&sort=desc&per_page=1",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/pipelines.json")),
+        );
+
+        let (_, listener) = waithandle::new();
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("https://git.example.com/gitlab/", result[0].origin);
+        assert_eq!("141208523", result[0].build_id);
+    }
+
+    #[test]
+    fn should_continue_with_next_project_if_pipelines_could_not_be_retrieved() {
+        // Given
+        let collector = GitLabCollector::<MockHttpClient>::new(&GitLabConfiguration {
+            id: "gitlab".to_owned(),
+            enabled: Some(true),
+            interval: None,
+            server_url: None,
+            credentials: GitLabCredentials::AccessToken("SECRET".to_owned()),
+            projects: vec!["duckhq/duck".to_owned(), "duckhq/docs".to_owned()],
+            branches: vec!["master".to_owned()],
+            webhook_secret: None,
+        });
+        let client = collector.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://gitlab.com/api/v4/projects/duckhq%2Fduck",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/project.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://gitlab.com/api/v4/projects/18721633/pipelines?ref=master&order_by=id&sort=desc&per_page=1",
+            )
+            .returns_status(StatusCode::INTERNAL_SERVER_ERROR),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://gitlab.com/api/v4/projects/duckhq%2Fdocs",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/other_project.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://gitlab.com/api/v4/projects/18721634/pipelines?ref=master&order_by=id&sort=desc&per_page=1",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/pipelines.json")),
+        );
+
+        let (_, listener) = waithandle::new();
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("18721634", result[0].project_id);
+        assert_eq!("duckhq/docs", result[0].definition_id);
+    }
+
+    #[test]
+    fn should_authenticate_using_access_token() {
+        // Given
+        let collector = create_collector(None);
+        let client = collector.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://gitlab.com/api/v4/projects/duckhq%2Fduck",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/project.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://gitlab.com/api/v4/projects/18721633/pipelines?ref=master&order_by=id&sort=desc&per_page=1",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/pipelines.json")),
+        );
+
+        let (_, listener) = waithandle::new();
+
+        // When
+        collector.collect(listener, &mut |_: Build| {}).unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(2, requests.len());
+        assert_eq!("SECRET", requests[0].headers.get("PRIVATE-TOKEN").unwrap());
+        assert_eq!("SECRET", requests[1].headers.get("PRIVATE-TOKEN").unwrap());
+    }
+}
@@ -0,0 +1,236 @@
+use url::Url;
+
+use crate::config::{GitLabConfiguration, GitLabCredentials, Validate};
+use crate::DuckResult;
+
+impl Validate for GitLabConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if let Some(server_url) = &self.server_url {
+            if let Err(e) = Url::parse(&server_url[..]) {
+                return Err(format_err!(
+                    "[{}] GitLab server URL is invalid: {}",
+                    self.id,
+                    e
+                ));
+            }
+        }
+        if self.projects.is_empty() {
+            return Err(format_err!(
+                "[{}] GitLab configuration have not specified any projects",
+                self.id
+            ));
+        }
+        if self.projects.iter().any(|p| p.is_empty()) {
+            return Err(format_err!("[{}] A GitLab project is empty", self.id));
+        }
+        if self.branches.is_empty() {
+            return Err(format_err!(
+                "[{}] GitLab configuration have not specified any branches",
+                self.id
+            ));
+        }
+
+        if let Some(secret) = &self.webhook_secret {
+            if secret.is_empty() {
+                return Err(format_err!("[{}] GitLab webhook secret is empty", self.id));
+            }
+        }
+
+        match &self.credentials {
+            GitLabCredentials::Anonymous => {}
+            GitLabCredentials::AccessToken(token) => {
+                if token.is_empty() {
+                    return Err(format_err!("[{}] GitLab access token is empty", self.id));
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::*;
+    use crate::providers;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "The id \\'\\' is invalid")]
+    fn should_return_error_if_gitlab_id_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "gitlab": {
+                            "id": "",
+                            "credentials": "anonymous",
+                            "projects": [ "duckhq/duck" ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[gitlab] GitLab server URL is invalid: relative URL without a base")]
+    fn should_return_error_if_gitlab_server_url_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "gitlab": {
+                            "id": "gitlab",
+                            "serverUrl": "",
+                            "credentials": "anonymous",
+                            "projects": [ "duckhq/duck" ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[gitlab] GitLab configuration have not specified any projects")]
+    fn should_return_error_if_gitlab_projects_are_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "gitlab": {
+                            "id": "gitlab",
+                            "credentials": "anonymous",
+                            "projects": [ ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[gitlab] A GitLab project is empty")]
+    fn should_return_error_if_a_gitlab_project_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "gitlab": {
+                            "id": "gitlab",
+                            "credentials": "anonymous",
+                            "projects": [ "duckhq/duck", "" ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[gitlab] GitLab configuration have not specified any branches")]
+    fn should_return_error_if_gitlab_branches_are_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "gitlab": {
+                            "id": "gitlab",
+                            "credentials": "anonymous",
+                            "projects": [ "duckhq/duck" ],
+                            "branches": [ ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[gitlab] GitLab access token is empty")]
+    fn should_return_error_if_gitlab_access_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "gitlab": {
+                            "id": "gitlab",
+                            "credentials": {
+                                "token": ""
+                            },
+                            "projects": [ "duckhq/duck" ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[gitlab] GitLab webhook secret is empty")]
+    fn should_return_error_if_gitlab_webhook_secret_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "gitlab": {
+                            "id": "gitlab",
+                            "credentials": "anonymous",
+                            "webhookSecret": "",
+                            "projects": [ "duckhq/duck" ],
+                            "branches": [ "master" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+use log::{trace, warn};
+use url::Url;
+
+use crate::builds::BuildStatus;
+use crate::config::{JenkinsConfiguration, JenkinsCredentials};
+use crate::utils::http::*;
+use crate::DuckResult;
+
+static MULTIBRANCH_CLASS: &str =
+    "org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject";
+static BUILD_TREE: &str = "number,url,result,building,timestamp,duration";
+
+pub struct JenkinsClient {
+    server_url: Url,
+    credentials: JenkinsCredentials,
+}
+
+impl JenkinsClient {
+    pub fn new(config: &JenkinsConfiguration) -> Self {
+        JenkinsClient {
+            server_url: Url::parse(&config.server_url[..]).unwrap(),
+            credentials: config.credentials.clone(),
+        }
+    }
+
+    pub fn get_origin(&self) -> &str {
+        self.server_url.as_str()
+    }
+
+    pub fn get_job(&self, client: &impl HttpClient, job: &str) -> DuckResult<JenkinsJob> {
+        let mut url = self.server_url.clone();
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .map_err(|_| format_err!("The Jenkins server URL cannot be used as a base"))?;
+            segments.pop_if_empty();
+            for name in job.split('/').filter(|n| !n.is_empty()) {
+                segments.push("job").push(name);
+            }
+            segments.push("api").push("json");
+        }
+
+        // Only ask for the things we need, including the
+        // last build of all child jobs (e.g. multibranch jobs).
+        url.set_query(Some(&format!(
+            "tree=name,fullName,displayName,fullDisplayName,lastBuild[{build}],\
+             jobs[name,fullName,displayName,lastBuild[{build}]]",
+            build = BUILD_TREE
+        )));
+
+        let body = self.send_get_request(client, url.as_str())?;
+        Ok(serde_json::from_str(&body[..])?)
+    }
+
+    fn send_get_request(&self, client: &impl HttpClient, url: &str) -> DuckResult<String> {
+        trace!("Sending request to: {}", url);
+        let mut builder = HttpRequestBuilder::get(url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+
+        self.credentials.authenticate(&mut builder);
+        let mut response = client.send(&builder)?;
+
+        trace!("Received response: {}", response.status());
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Received non 200 HTTP status code. ({})",
+                response.status()
+            ));
+        }
+
+        Ok(response.body()?)
+    }
+}
+
+impl JenkinsCredentials {
+    fn authenticate<'a>(&self, builder: &'a mut HttpRequestBuilder) {
+        match self {
+            JenkinsCredentials::Anonymous => {}
+            JenkinsCredentials::Basic { username, token } => {
+                builder.basic_auth(username, Some(token));
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct JenkinsJob {
+    #[serde(rename = "_class", default)]
+    pub class: String,
+    pub name: String,
+    #[serde(rename = "fullName")]
+    pub full_name: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(rename = "fullDisplayName", default)]
+    pub full_display_name: Option<String>,
+    #[serde(rename = "lastBuild", default)]
+    pub last_build: Option<JenkinsBuild>,
+    #[serde(default)]
+    pub jobs: Vec<JenkinsJob>,
+}
+
+impl JenkinsJob {
+    pub fn is_multibranch(&self) -> bool {
+        self.class == MULTIBRANCH_CLASS
+    }
+
+    pub fn is_folder(&self) -> bool {
+        !self.is_multibranch() && !self.jobs.is_empty()
+    }
+
+    pub fn get_project_name(&self) -> &str {
+        match &self.full_display_name {
+            Some(name) => name,
+            None => &self.full_name,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct JenkinsBuild {
+    pub number: u64,
+    pub url: String,
+    pub result: Option<String>,
+    pub building: bool,
+    pub timestamp: i64,
+    pub duration: i64,
+}
+
+impl JenkinsBuild {
+    pub fn get_status(&self) -> BuildStatus {
+        if self.building {
+            return BuildStatus::Running;
+        }
+        match self.result.as_ref().map(|r| &r[..]) {
+            Some("SUCCESS") => BuildStatus::Success,
+            Some("FAILURE") | Some("UNSTABLE") => BuildStatus::Failed,
+            Some("ABORTED") => BuildStatus::Canceled,
+            Some("NOT_BUILT") => BuildStatus::Skipped,
+            None => BuildStatus::Queued,
+            Some(result) => {
+                warn!("Unknown build result: {}", result);
+                BuildStatus::Unknown
+            }
+        }
+    }
+
+    pub fn get_started_timestamp(&self) -> i64 {
+        self.timestamp / 1000
+    }
+
+    pub fn get_finished_timestamp(&self) -> Option<i64> {
+        if self.building || self.result.is_none() {
+            None
+        } else {
+            Some((self.timestamp + self.duration) / 1000)
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use std::time::Duration;
+
+use log::{trace, warn};
+use waithandle::WaitHandleListener;
+
+use crate::builds::{Build, BuildBuilder};
+use crate::config::JenkinsConfiguration;
+use crate::providers::collectors::{Collector, CollectorInfo, CollectorLoader};
+use crate::utils::http::{HttpClient, ReqwestClient};
+use crate::DuckResult;
+
+use self::client::{JenkinsBuild, JenkinsClient, JenkinsJob};
+
+mod client;
+mod validation;
+
+impl CollectorLoader for JenkinsConfiguration {
+    fn load(&self) -> DuckResult<Box<dyn Collector>> {
+        Ok(Box::new(JenkinsCollector::<ReqwestClient>::new(self)))
+    }
+}
+
+pub struct JenkinsCollector<T: HttpClient + Default> {
+    http: T,
+    client: JenkinsClient,
+    jobs: Vec<String>,
+    info: CollectorInfo,
+}
+
+impl<T: HttpClient + Default> JenkinsCollector<T> {
+    pub fn new(config: &JenkinsConfiguration) -> Self {
+        JenkinsCollector {
+            http: Default::default(),
+            client: JenkinsClient::new(config),
+            jobs: config.jobs.clone(),
+            info: CollectorInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    Option::None => true,
+                    Option::Some(e) => e,
+                },
+                provider: "Jenkins".to_owned(),
+                interval: config.interval,
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+
+    fn collect_job(
+        &self,
+        listener: &WaitHandleListener,
+        path: &str,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        trace!("Getting builds for {}...", path);
+        let job = self.client.get_job(&self.http, path)?;
+
+        if job.is_multibranch() {
+            // Every job in a multibranch job represents a branch.
+            // Builds are numbered per branch, so the branch job is used
+            // as the definition to keep builds from different branches apart.
+            for branch in job.jobs.iter() {
+                match &branch.last_build {
+                    None => trace!("No builds found for branch '{}'", branch.display_name),
+                    Some(build) => callback(self.create_build(
+                        &job,
+                        &branch.full_name,
+                        build,
+                        &branch.display_name,
+                    )),
+                };
+            }
+        } else if job.is_folder() {
+            // Collect all jobs in the folder.
+            for child in job.jobs.iter() {
+                if listener.check().unwrap() {
+                    return Ok(());
+                }
+                self.collect_job(listener, &format!("{}/{}", path, child.name), callback)?;
+            }
+            return Ok(());
+        } else {
+            match &job.last_build {
+                None => trace!("No builds found for job '{}'", job.full_name),
+                Some(build) => callback(self.create_build(&job, &job.full_name, build, "default")),
+            };
+        }
+
+        // Wait for a little time between calls.
+        listener.wait(Duration::from_millis(300)).unwrap();
+        Ok(())
+    }
+
+    fn create_build(
+        &self,
+        job: &JenkinsJob,
+        definition_id: &str,
+        build: &JenkinsBuild,
+        branch: &str,
+    ) -> Build {
+        BuildBuilder::new()
+            .build_id(build.number.to_string())
+            .provider("Jenkins")
+            .origin(self.client.get_origin())
+            .collector(&self.info.id)
+            .project_id(&job.full_name)
+            .project_name(job.get_project_name())
+            .definition_id(definition_id)
+            .definition_name(&job.display_name)
+            .build_number(build.number.to_string())
+            .status(build.get_status())
+            .url(&build.url)
+            .started_at(build.get_started_timestamp())
+            .finished_at(build.get_finished_timestamp())
+            .branch(branch)
+            .build()
+            .unwrap()
+    }
+}
+
+impl<T: HttpClient + Default> Collector for JenkinsCollector<T> {
+    fn info(&self) -> &CollectorInfo {
+        &self.info
+    }
+
+    fn collect(
+        &self,
+        listener: WaitHandleListener,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        for job in self.jobs.iter() {
+            if listener.check().unwrap() {
+                return Ok(());
+            }
+
+            if let Err(e) = self.collect_job(&listener, job.trim_matches('/'), callback) {
+                warn!("Could not get builds for Jenkins job '{}': {}", job, e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildStatus;
+    use crate::config::*;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+    use test_case::test_case;
+
+    fn create_collector(jobs: Vec<&str>) -> JenkinsCollector<MockHttpClient> {
+        JenkinsCollector::<MockHttpClient>::new(&JenkinsConfiguration {
+            id: "jenkins".to_owned(),
+            enabled: Some(true),
+            interval: None,
+            server_url: "https://ci.example.com/jenkins".to_owned(),
+            credentials: JenkinsCredentials::Basic {
+                username: "patrik".to_owned(),
+                token: "SECRET".to_owned(),
+            },
+            jobs: jobs.iter().map(|j| j.to_string()).collect(),
+        })
+    }
+
+    fn get_url(path: &str) -> String {
+        format!(
+            "https://ci.example.com/jenkins/{}api/json?tree=name,fullName,displayName,fullDisplayName,\
+             lastBuild[number,url,result,building,timestamp,duration],\
+             jobs[name,fullName,displayName,lastBuild[number,url,result,building,timestamp,duration]]",
+            path
+        )
+    }
+
+    fn collect(collector: &JenkinsCollector<MockHttpClient>) -> Vec<Build> {
+        let (_, listener) = waithandle::new();
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+        result
+    }
+
+    #[test]
+    fn should_return_correct_provider_name() {
+        // Given
+        let collector = create_collector(vec!["duck"]);
+        // When
+        let provider = &collector.info().provider;
+        // Then
+        assert_eq!("Jenkins", provider);
+    }
+
+    #[test]
+    fn should_get_correct_data_for_job() {
+        // Given
+        let collector = create_collector(vec!["duck"]);
+        let client = collector.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, &get_url("job/duck/"))
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/job.json")),
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("42", result[0].build_id);
+        assert_eq!("Jenkins", result[0].provider);
+        assert_eq!("jenkins", result[0].collector);
+        assert_eq!("https://ci.example.com/jenkins", result[0].origin);
+        assert_eq!("duck", result[0].project_id);
+        assert_eq!("Duck", result[0].project_name);
+        assert_eq!("duck", result[0].definition_id);
+        assert_eq!("Duck", result[0].definition_name);
+        assert_eq!("42", result[0].build_number);
+        assert_eq!(BuildStatus::Success, result[0].status);
+        assert_eq!("default", result[0].branch);
+        assert_eq!("https://ci.example.com/jenkins/job/duck/42/", result[0].url);
+        assert_eq!(1588580832, result[0].started_at);
+        assert_eq!(1588581177, result[0].finished_at.unwrap());
+    }
+
+    #[test]
+    fn should_get_all_branches_for_multibranch_job() {
+        // Given
+        let collector = create_collector(vec!["duck-pipeline"]);
+        let client = collector.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, &get_url("job/duck-pipeline/"))
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/multibranch.json")),
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("duck-pipeline", result[0].project_id);
+        assert_eq!("duck-pipeline/master", result[0].definition_id);
+        assert_eq!("Duck Pipeline", result[0].definition_name);
+        assert_eq!("master", result[0].branch);
+        assert_eq!(BuildStatus::Failed, result[0].status);
+        assert_eq!("feature/jenkins", result[1].branch);
+        assert_eq!(BuildStatus::Running, result[1].status);
+        assert_eq!(1588580832, result[1].started_at);
+        assert_eq!(None, result[1].finished_at);
+    }
+
+    #[test]
+    fn should_keep_builds_with_same_number_on_different_branches_apart() {
+        // Given
+        let collector = create_collector(vec!["duck-pipeline"]);
+        let client = collector.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, &get_url("job/duck-pipeline/"))
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/multibranch_same_number.json")),
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!(result[0].build_id, result[1].build_id);
+        assert_eq!("duck-pipeline/master", result[0].definition_id);
+        assert_eq!("duck-pipeline/develop", result[1].definition_id);
+        assert_ne!(result[0].id, result[1].id);
+        assert_ne!(result[0].partition, result[1].partition);
+    }
+
+    #[test]
+    fn should_get_all_jobs_in_folder() {
+        // Given
+        let collector = create_collector(vec!["team"]);
+        let client = collector.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, &get_url("job/team/"))
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/folder.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, &get_url("job/team/job/duck/"))
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/job.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, &get_url("job/team/job/duck-pipeline/"))
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/multibranch.json")),
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(3, result.len());
+        assert_eq!("default", result[0].branch);
+        assert_eq!("master", result[1].branch);
+        assert_eq!("feature/jenkins", result[2].branch);
+    }
+
+    #[test]
+    fn should_continue_with_next_job_if_a_job_could_not_be_retrieved() {
+        // Given
+        let collector = create_collector(vec!["missing", "duck"]);
+        let client = collector.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, &get_url("job/missing/"))
+                .returns_status(StatusCode::NOT_FOUND),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, &get_url("job/duck/"))
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/job.json")),
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("duck", result[0].definition_id);
+    }
+
+    #[test]
+    fn should_authenticate_using_basic_auth() {
+        // Given
+        let collector = create_collector(vec!["duck"]);
+        let client = collector.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, &get_url("job/duck/"))
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/job.json")),
+        );
+
+        // When
+        collect(&collector);
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "Basic cGF0cmlrOlNFQ1JFVA==",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+    }
+
+    #[test_case(Some("SUCCESS"), false, BuildStatus::Success ; "Success")]
+    #[test_case(Some("FAILURE"), false, BuildStatus::Failed ; "Failure")]
+    #[test_case(Some("UNSTABLE"), false, BuildStatus::Failed ; "Unstable")]
+    #[test_case(Some("ABORTED"), false, BuildStatus::Canceled ; "Aborted")]
+    #[test_case(Some("NOT_BUILT"), false, BuildStatus::Skipped ; "Not built")]
+    #[test_case(None, false, BuildStatus::Queued ; "Queued")]
+    #[test_case(None, true, BuildStatus::Running ; "Running")]
+    fn should_map_build_status(result: Option<&str>, building: bool, expected: BuildStatus) {
+        // Given
+        let build = JenkinsBuild {
+            number: 1,
+            url: "https://ci.example.com/jenkins/job/duck/1/".to_owned(),
+            result: result.map(|r| r.to_owned()),
+            building,
+            timestamp: 1588580832441,
+            duration: 0,
+        };
+        // When
+        let status = build.get_status();
+        // Then
+        assert_eq!(expected, status);
+    }
+}
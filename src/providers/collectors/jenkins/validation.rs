@@ -0,0 +1,212 @@
+use url::Url;
+
+use crate::config::{JenkinsConfiguration, JenkinsCredentials, Validate};
+use crate::DuckResult;
+
+impl Validate for JenkinsConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if let Err(e) = Url::parse(&self.server_url[..]) {
+            return Err(format_err!(
+                "[{}] Jenkins server URL is invalid: {}",
+                self.id,
+                e
+            ));
+        }
+        if self.jobs.is_empty() {
+            return Err(format_err!(
+                "[{}] Jenkins configuration have not specified any jobs",
+                self.id
+            ));
+        }
+        if self.jobs.iter().any(|j| j.trim_matches('/').is_empty()) {
+            return Err(format_err!("[{}] A Jenkins job is empty", self.id));
+        }
+
+        match &self.credentials {
+            JenkinsCredentials::Anonymous => (),
+            JenkinsCredentials::Basic { username, token } => {
+                if username.is_empty() {
+                    return Err(format_err!(
+                        "[{}] Jenkins username cannot be empty",
+                        self.id
+                    ));
+                }
+                if token.is_empty() {
+                    return Err(format_err!(
+                        "[{}] Jenkins API token cannot be empty",
+                        self.id
+                    ));
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "The id \\'\\' is invalid")]
+    fn should_return_error_if_id_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "jenkins": {
+                            "id": "",
+                            "serverUrl": "https://localhost:8080",
+                            "credentials": "anonymous",
+                            "jobs": [ "Foo" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[foo] Jenkins server URL is invalid: relative URL without a base")]
+    fn should_return_error_if_jenkins_server_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "jenkins": {
+                            "id": "foo",
+                            "serverUrl": "",
+                            "credentials": "anonymous",
+                            "jobs": [ "Foo" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[foo] Jenkins configuration have not specified any jobs")]
+    fn should_return_error_if_jenkins_jobs_are_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "jenkins": {
+                            "id": "foo",
+                            "serverUrl": "https://localhost:8080",
+                            "credentials": "anonymous",
+                            "jobs": [ ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[foo] A Jenkins job is empty")]
+    fn should_return_error_if_a_jenkins_job_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "jenkins": {
+                            "id": "foo",
+                            "serverUrl": "https://localhost:8080",
+                            "credentials": "anonymous",
+                            "jobs": [ "Foo", "/" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[foo] Jenkins username cannot be empty")]
+    fn should_return_error_if_jenkins_username_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "jenkins": {
+                            "id": "foo",
+                            "serverUrl": "https://localhost:8080",
+                            "credentials": {
+                                "basic": {
+                                    "username": "",
+                                    "token": "bar"
+                                }
+                            },
+                            "jobs": [ "Foo" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[foo] Jenkins API token cannot be empty")]
+    fn should_return_error_if_jenkins_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "jenkins": {
+                            "id": "foo",
+                            "serverUrl": "https://localhost:8080",
+                            "credentials": {
+                                "basic": {
+                                    "username": "john.doe",
+                                    "token": ""
+                                }
+                            },
+                            "jobs": [ "Foo" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+}
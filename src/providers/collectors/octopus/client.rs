@@ -1,15 +1,14 @@
 use log::trace;
-use reqwest::header::ACCEPT;
-use reqwest::{Client, ClientBuilder, RequestBuilder};
+use serde::de::DeserializeOwned;
 use url::Url;
 
 use crate::config::OctopusDeployCredentials;
+use crate::utils::http::*;
 use crate::DuckResult;
 
 pub struct OctopusDeployClient {
     url: Url,
     credentials: OctopusDeployCredentials,
-    client: Client,
 }
 
 impl OctopusDeployClient {
@@ -17,21 +16,28 @@ impl OctopusDeployClient {
         OctopusDeployClient {
             url: server_url,
             credentials,
-            client: ClientBuilder::new().build().unwrap(),
         }
     }
 
-    pub fn get_dashboard(&self) -> DuckResult<OctopusDashboard> {
-        let mut response =
-            self.send_get_request(&format!("{url}api/dashboard", url = self.url,)[..])?;
-        let result: OctopusDashboard = response.json()?;
-        Ok(result)
+    pub fn get_dashboard(&self, client: &impl HttpClient) -> DuckResult<OctopusDashboard> {
+        self.send_get_request(client, format!("{url}api/dashboard", url = self.url))
     }
 
-    fn send_get_request(&self, url: &str) -> DuckResult<reqwest::Response> {
+    pub fn get_channels(&self, client: &impl HttpClient) -> DuckResult<Vec<OctopusChannel>> {
+        self.send_get_request(client, format!("{url}api/channels/all", url = self.url))
+    }
+
+    fn send_get_request<T: DeserializeOwned>(
+        &self,
+        client: &impl HttpClient,
+        url: String,
+    ) -> DuckResult<T> {
         trace!("Sending request to: {}", url);
-        let response = self.client.get(url).header(ACCEPT, "application/json");
-        let response = self.credentials.authenticate(response).send()?;
+        let mut builder = HttpRequestBuilder::get(&url);
+        builder.add_header("Accept", "application/json");
+
+        self.credentials.authenticate(&mut builder);
+        let mut response = client.send(&builder)?;
 
         trace!("Received response: {}", response.status());
         if !response.status().is_success() {
@@ -41,15 +47,16 @@ impl OctopusDeployClient {
             ));
         }
 
-        Ok(response)
+        let body = response.body()?;
+        Ok(serde_json::from_str(&body[..])?)
     }
 }
 
 impl OctopusDeployCredentials {
-    fn authenticate(&self, builder: RequestBuilder) -> RequestBuilder {
-        return match self {
+    fn authenticate(&self, builder: &mut HttpRequestBuilder) {
+        match self {
             OctopusDeployCredentials::ApiKey(api_key) => {
-                builder.header("X-Octopus-ApiKey", api_key)
+                builder.add_header("X-Octopus-ApiKey", api_key);
             }
         };
     }
@@ -61,6 +68,9 @@ pub struct OctopusDashboard {
     pub projects: Vec<OctopusProject>,
     #[serde(rename = "Environments")]
     pub environments: Vec<OctopusEnvironment>,
+    #[serde(rename = "Tenants")]
+    #[serde(default)]
+    pub tenants: Vec<OctopusTenant>,
     #[serde(rename = "Items")]
     pub deployments: Vec<OctopusDeployment>,
 }
@@ -85,6 +95,22 @@ pub struct OctopusEnvironment {
     pub name: String,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct OctopusTenant {
+    #[serde(rename = "Id")]
+    pub id: String,
+    #[serde(rename = "Name")]
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OctopusChannel {
+    #[serde(rename = "Id")]
+    pub id: String,
+    #[serde(rename = "Name")]
+    pub name: String,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct OctopusDeployment {
     #[serde(rename = "Id")]
@@ -93,6 +119,12 @@ pub struct OctopusDeployment {
     pub project: String,
     #[serde(rename = "EnvironmentId")]
     pub environment: String,
+    #[serde(rename = "TenantId")]
+    #[serde(default)]
+    pub tenant: Option<String>,
+    #[serde(rename = "ChannelId")]
+    #[serde(default)]
+    pub channel: Option<String>,
     #[serde(rename = "ReleaseId")]
     pub release_id: String,
     #[serde(rename = "ReleaseVersion")]
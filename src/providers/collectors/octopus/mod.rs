@@ -5,6 +5,7 @@ use crate::builds::{BuildBuilder, BuildStatus};
 use crate::config::{OctopusDeployConfiguration, OctopusDeployProject};
 use crate::providers::collectors::*;
 use crate::utils::date;
+use crate::utils::http::{HttpClient, ReqwestClient};
 
 use self::client::*;
 
@@ -13,33 +14,47 @@ mod validation;
 
 impl CollectorLoader for OctopusDeployConfiguration {
     fn load(&self) -> DuckResult<Box<dyn Collector>> {
-        Ok(Box::new(OctopusDeployCollector {
-            server_url: Url::parse(&self.server_url[..]).unwrap(),
-            projects: self.projects.clone(),
+        Ok(Box::new(OctopusDeployCollector::<ReqwestClient>::new(self)))
+    }
+}
+
+pub struct OctopusDeployCollector<T: HttpClient + Default> {
+    http: T,
+    server_url: Url,
+    projects: Vec<OctopusDeployProject>,
+    client: OctopusDeployClient,
+    info: CollectorInfo,
+}
+
+impl<T: HttpClient + Default> OctopusDeployCollector<T> {
+    pub fn new(config: &OctopusDeployConfiguration) -> Self {
+        OctopusDeployCollector {
+            http: Default::default(),
+            server_url: Url::parse(&config.server_url[..]).unwrap(),
+            projects: config.projects.clone(),
             client: OctopusDeployClient::new(
-                Url::parse(&self.server_url[..]).unwrap(),
-                self.credentials.clone(),
+                Url::parse(&config.server_url[..]).unwrap(),
+                config.credentials.clone(),
             ),
             info: CollectorInfo {
-                id: self.id.clone(),
-                enabled: match self.enabled {
+                id: config.id.clone(),
+                enabled: match config.enabled {
                     Option::None => true,
                     Option::Some(e) => e,
                 },
                 provider: "OctopusDeploy".to_owned(),
+                interval: config.interval,
             },
-        }))
+        }
     }
-}
 
-pub struct OctopusDeployCollector {
-    server_url: Url,
-    projects: Vec<OctopusDeployProject>,
-    client: OctopusDeployClient,
-    info: CollectorInfo,
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
 }
 
-impl Collector for OctopusDeployCollector {
+impl<T: HttpClient + Default> Collector for OctopusDeployCollector<T> {
     fn info(&self) -> &CollectorInfo {
         &self.info
     }
@@ -49,7 +64,15 @@ impl Collector for OctopusDeployCollector {
         listener: WaitHandleListener,
         callback: &mut dyn FnMut(Build),
     ) -> DuckResult<()> {
-        let response = self.client.get_dashboard()?;
+        let response = self.client.get_dashboard(&self.http)?;
+
+        // Channels are not part of the dashboard, so we only
+        // fetch them if any project is filtering on channels.
+        let channels = if self.projects.iter().any(|p| p.channels.is_some()) {
+            self.client.get_channels(&self.http)?
+        } else {
+            Vec::new()
+        };
 
         for project in self.projects.iter() {
             if listener.check().unwrap() {
@@ -71,7 +94,7 @@ impl Collector for OctopusDeployCollector {
                 }
 
                 // Get the environment from the result.
-                let found_environment = match response.get_environment(environment) {
+                let found_environment = match response.find_environment(environment) {
                     Some(e) => e,
                     None => {
                         warn!("Environment '{}' does not exist.", environment);
@@ -87,52 +110,72 @@ impl Collector for OctopusDeployCollector {
                     );
                 }
 
-                // Get the deployment for the project and environment combination.
-                let deployment = match response.find_deployment(found_project, found_environment) {
-                    Some(d) => d,
-                    None => {
-                        warn!(
-                            "No deployment found for Environment '{}' in project '{}'",
-                            environment, found_project.name
-                        );
-                        continue;
-                    }
-                };
+                // Get the deployments for the project and environment combination.
+                // Multi-tenant projects have one deployment per tenant.
+                let deployments: Vec<&OctopusDeployment> = response
+                    .find_deployments(found_project, found_environment)
+                    .filter(|d| response.is_tenant_included(project, d))
+                    .filter(|d| is_channel_included(&channels, project, d))
+                    .collect();
 
-                callback(
-                    BuildBuilder::new()
-                        .build_id(&deployment.id)
-                        .provider("OctopusDeploy")
-                        .origin(self.server_url.as_str())
-                        .collector(&self.info.id)
-                        .project_id(&found_project.id)
-                        .project_name(&found_project.name)
-                        .definition_id(&found_environment.id)
-                        .definition_name(&found_environment.name)
-                        .build_number(&deployment.release_version)
-                        .status(deployment.get_status())
-                        .url(format!(
-                            "{}/app#/projects/{}/releases/{}/deployments/{}",
-                            self.server_url,
-                            found_project.slug,
-                            deployment.release_id,
-                            deployment.id
-                        ))
-                        .started_at(date::to_timestamp(
-                            &deployment.get_start_time()[..],
-                            date::OCTOPUS_DEPLOY_FORMAT,
-                        )?)
-                        .finished_at(match &deployment.finish_time {
-                            Option::None => None,
-                            Option::Some(value) => Option::Some(date::to_timestamp(
-                                &value[..],
+                if deployments.is_empty() {
+                    warn!(
+                        "No deployment found for Environment '{}' in project '{}'",
+                        environment, found_project.name
+                    );
+                    continue;
+                }
+
+                for deployment in deployments {
+                    // Every tenant gets its own definition, so that
+                    // the deployments end up in separate partitions.
+                    let (definition_id, definition_name) = match &deployment.tenant {
+                        Some(tenant) => (
+                            format!("{}/{}", found_environment.id, tenant),
+                            format!(
+                                "{} ({})",
+                                found_environment.name,
+                                response.get_tenant_name(tenant)
+                            ),
+                        ),
+                        None => (found_environment.id.clone(), found_environment.name.clone()),
+                    };
+
+                    callback(
+                        BuildBuilder::new()
+                            .build_id(&deployment.id)
+                            .provider("OctopusDeploy")
+                            .origin(self.server_url.as_str())
+                            .collector(&self.info.id)
+                            .project_id(&found_project.id)
+                            .project_name(&found_project.name)
+                            .definition_id(definition_id)
+                            .definition_name(definition_name)
+                            .build_number(&deployment.release_version)
+                            .status(deployment.get_status())
+                            .url(format!(
+                                "{}app#/projects/{}/releases/{}/deployments/{}",
+                                self.server_url,
+                                found_project.slug,
+                                deployment.release_id,
+                                deployment.id
+                            ))
+                            .started_at(date::to_timestamp(
+                                &deployment.get_start_time()[..],
                                 date::OCTOPUS_DEPLOY_FORMAT,
-                            )?),
-                        })
-                        .branch(&deployment.release_id)
-                        .build()
-                        .unwrap(),
-                );
+                            )?)
+                            .finished_at(match &deployment.finish_time {
+                                Option::None => None,
+                                Option::Some(value) => Option::Some(date::to_timestamp(
+                                    &value[..],
+                                    date::OCTOPUS_DEPLOY_FORMAT,
+                                )?),
+                            })
+                            .branch(&deployment.release_id)
+                            .build()
+                            .unwrap(),
+                    );
+                }
             }
         }
 
@@ -140,23 +183,80 @@ impl Collector for OctopusDeployCollector {
     }
 }
 
+fn is_channel_included(
+    channels: &[OctopusChannel],
+    project: &OctopusDeployProject,
+    deployment: &OctopusDeployment,
+) -> bool {
+    let filter = match &project.channels {
+        Some(filter) => filter,
+        None => return true,
+    };
+    let id = match &deployment.channel {
+        Some(id) => id,
+        None => return false,
+    };
+    let name = channels
+        .iter()
+        .find(|c| &c.id == id)
+        .map(|c| &c.name[..])
+        .unwrap_or("");
+    filter.iter().any(|f| is_match(f, id, name))
+}
+
+/// Matches a configured value against an ID or a name.
+/// Names are matched case insensitive.
+fn is_match(value: &str, id: &str, name: &str) -> bool {
+    value == id || value.eq_ignore_ascii_case(name)
+}
+
 impl OctopusDashboard {
-    pub fn find_project(&self, id: &str) -> Option<&OctopusProject> {
-        self.projects.iter().find(|&p| p.id == id)
+    pub fn find_project(&self, value: &str) -> Option<&OctopusProject> {
+        self.projects
+            .iter()
+            .find(|&p| is_match(value, &p.id, &p.name) || p.slug == value)
     }
 
-    pub fn get_environment(&self, name: &str) -> Option<&OctopusEnvironment> {
-        self.environments.iter().find(|&e| e.id == name)
+    pub fn find_environment(&self, value: &str) -> Option<&OctopusEnvironment> {
+        self.environments
+            .iter()
+            .find(|&e| is_match(value, &e.id, &e.name))
     }
 
-    pub fn find_deployment(
-        &self,
-        project: &OctopusProject,
-        environment: &OctopusEnvironment,
-    ) -> Option<&OctopusDeployment> {
+    pub fn find_deployments<'a>(
+        &'a self,
+        project: &'a OctopusProject,
+        environment: &'a OctopusEnvironment,
+    ) -> impl Iterator<Item = &'a OctopusDeployment> {
         self.deployments
             .iter()
-            .find(|&d| d.project == project.id && d.environment == environment.id)
+            .filter(move |&d| d.project == project.id && d.environment == environment.id)
+    }
+
+    pub fn get_tenant_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.tenants
+            .iter()
+            .find(|t| t.id == id)
+            .map(|t| &t.name[..])
+            .unwrap_or(id)
+    }
+
+    pub fn is_tenant_included(
+        &self,
+        project: &OctopusDeployProject,
+        deployment: &OctopusDeployment,
+    ) -> bool {
+        let filter = match &project.tenants {
+            Some(filter) => filter,
+            None => return true,
+        };
+        match &deployment.tenant {
+            Some(id) => {
+                let name = self.get_tenant_name(id);
+                filter.iter().any(|f| is_match(f, id, name))
+            }
+            None => false,
+        }
     }
 }
 
@@ -186,3 +286,180 @@ impl OctopusDeployment {
         .unwrap_or_else(|| self.created_time.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::*;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+
+    fn create_project(project: &str, environments: Vec<&str>) -> OctopusDeployProject {
+        OctopusDeployProject {
+            project_id: project.to_owned(),
+            environments: environments.iter().map(|e| (*e).to_owned()).collect(),
+            tenants: None,
+            channels: None,
+        }
+    }
+
+    fn create_collector(
+        projects: Vec<OctopusDeployProject>,
+    ) -> OctopusDeployCollector<MockHttpClient> {
+        let collector =
+            OctopusDeployCollector::<MockHttpClient>::new(&OctopusDeployConfiguration {
+                id: "octopus".to_owned(),
+                enabled: Some(true),
+                interval: None,
+                server_url: "http://localhost:9000".to_owned(),
+                credentials: OctopusDeployCredentials::ApiKey("SECRET".to_owned()),
+                projects,
+            });
+
+        let client = collector.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:9000/api/dashboard")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/dashboard.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "http://localhost:9000/api/channels/all")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/channels.json")),
+        );
+
+        collector
+    }
+
+    fn collect(collector: &OctopusDeployCollector<MockHttpClient>) -> Vec<Build> {
+        let (_, listener) = waithandle::new();
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+        result
+    }
+
+    #[test]
+    fn should_return_correct_provider_name() {
+        // Given
+        let collector = create_collector(vec![]);
+        // When
+        let provider = &collector.info().provider;
+        // Then
+        assert_eq!("OctopusDeploy", provider);
+    }
+
+    #[test]
+    fn should_get_correct_data() {
+        // Given
+        let collector =
+            create_collector(vec![create_project("Projects-1", vec!["Environments-1"])]);
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("Deployments-10", result[0].build_id);
+        assert_eq!("OctopusDeploy", result[0].provider);
+        assert_eq!("octopus", result[0].collector);
+        assert_eq!("http://localhost:9000/", result[0].origin);
+        assert_eq!("Projects-1", result[0].project_id);
+        assert_eq!("Duck", result[0].project_name);
+        assert_eq!("Environments-1", result[0].definition_id);
+        assert_eq!("Staging", result[0].definition_name);
+        assert_eq!("1.2.0", result[0].build_number);
+        assert_eq!(BuildStatus::Success, result[0].status);
+        assert_eq!("Releases-5", result[0].branch);
+        assert_eq!(
+            "http://localhost:9000/app#/projects/duck/releases/Releases-5/deployments/Deployments-10",
+            result[0].url
+        );
+        assert_eq!(1588586405, result[0].started_at);
+        assert_eq!(Some(1588586520), result[0].finished_at);
+    }
+
+    #[test]
+    fn should_find_project_and_environment_by_name() {
+        // Given
+        let collector = create_collector(vec![create_project("duck", vec!["staging"])]);
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("Projects-1", result[0].project_id);
+        assert_eq!("Environments-1", result[0].definition_id);
+    }
+
+    #[test]
+    fn should_return_one_build_per_tenant() {
+        // Given
+        let collector = create_collector(vec![create_project("Duck", vec!["Production"])]);
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("Environments-2/Tenants-1", result[0].definition_id);
+        assert_eq!("Production (Contoso)", result[0].definition_name);
+        assert_eq!("Environments-2/Tenants-2", result[1].definition_id);
+        assert_eq!("Production (Fabrikam)", result[1].definition_name);
+        assert_ne!(result[0].partition, result[1].partition);
+    }
+
+    #[test]
+    fn should_only_return_builds_for_configured_tenants() {
+        // Given
+        let collector = create_collector(vec![OctopusDeployProject {
+            tenants: Some(vec!["fabrikam".to_owned()]),
+            ..create_project("Duck", vec!["Staging", "Production"])
+        }]);
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("Deployments-12", result[0].build_id);
+    }
+
+    #[test]
+    fn should_only_return_builds_for_configured_channels() {
+        // Given
+        let collector = create_collector(vec![OctopusDeployProject {
+            channels: Some(vec!["Hotfix".to_owned()]),
+            ..create_project("Duck", vec!["Staging", "Production"])
+        }]);
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("Deployments-12", result[0].build_id);
+    }
+
+    #[test]
+    fn should_not_get_channels_if_not_filtering_on_channels() {
+        // Given
+        let collector = create_collector(vec![create_project("Duck", vec!["Staging"])]);
+
+        // When
+        collect(&collector);
+
+        // Then
+        let requests = collector.get_client().get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "SECRET",
+            requests[0].headers.get("X-Octopus-ApiKey").unwrap()
+        );
+    }
+}
@@ -43,6 +43,24 @@ impl Validate for OctopusDeployConfiguration {
                     ));
                 }
             }
+            if let Some(tenants) = &project.tenants {
+                if tenants.iter().any(|t| t.is_empty()) {
+                    return Err(format_err!(
+                        "[{}] An Octopus Deploy tenant in project '{}' is empty",
+                        self.id,
+                        project.project_id
+                    ));
+                }
+            }
+            if let Some(channels) = &project.channels {
+                if channels.iter().any(|c| c.is_empty()) {
+                    return Err(format_err!(
+                        "[{}] An Octopus Deploy channel in project '{}' is empty",
+                        self.id,
+                        project.project_id
+                    ));
+                }
+            }
         }
 
         match &self.credentials {
@@ -305,4 +323,70 @@ mod tests {
 
         providers::create_collectors(&config).unwrap();
     }
+
+    #[test]
+    #[should_panic(expected = "[foo] An Octopus Deploy tenant in project")]
+    fn should_return_error_if_tenant_name_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "octopus": {
+                            "id": "foo",
+                            "serverUrl": "http://localhost:9000",
+                            "credentials": {
+                                "apiKey": "MY-SECRET-API-KEY"
+                            },
+                            "projects": [
+                                {
+                                    "projectId": "bar",
+                                    "environments": [ "production" ],
+                                    "tenants": [ "" ]
+                                }
+                            ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[foo] An Octopus Deploy channel in project")]
+    fn should_return_error_if_channel_name_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "octopus": {
+                            "id": "foo",
+                            "serverUrl": "http://localhost:9000",
+                            "credentials": {
+                                "apiKey": "MY-SECRET-API-KEY"
+                            },
+                            "projects": [
+                                {
+                                    "projectId": "bar",
+                                    "environments": [ "production" ],
+                                    "channels": [ "" ]
+                                }
+                            ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
 }
@@ -0,0 +1,262 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use log::debug;
+use waithandle::WaitHandleListener;
+
+use crate::builds::Build;
+use crate::config::PushConfiguration;
+use crate::providers::collectors::{Collector, CollectorInfo, CollectorLoader};
+use crate::utils::secret;
+use crate::DuckResult;
+
+mod validation;
+
+impl CollectorLoader for PushConfiguration {
+    fn load(&self) -> DuckResult<Box<dyn Collector>> {
+        Ok(Box::new(PushCollector::new(self)))
+    }
+}
+
+pub struct PushCollector {
+    inbox: Arc<PushInbox>,
+    info: CollectorInfo,
+}
+
+impl PushCollector {
+    pub fn new(config: &PushConfiguration) -> Self {
+        PushCollector {
+            inbox: Arc::new(PushInbox::new(
+                &config.token,
+                Duration::from_secs(config.get_expiry().into()),
+            )),
+            info: CollectorInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    Option::None => true,
+                    Option::Some(e) => e,
+                },
+                provider: "Push".to_owned(),
+                interval: config.interval,
+            },
+        }
+    }
+}
+
+impl Collector for PushCollector {
+    fn info(&self) -> &CollectorInfo {
+        &self.info
+    }
+
+    fn collect(
+        &self,
+        _handle: WaitHandleListener,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        for build in self.inbox.get_builds() {
+            callback(build);
+        }
+        Ok(())
+    }
+
+    fn inbox(&self) -> Option<Arc<PushInbox>> {
+        Some(self.inbox.clone())
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Inbox
+
+/// Holds builds that have been pushed to a collector
+/// until they expire.
+pub struct PushInbox {
+    token: String,
+    expiry: Duration,
+    builds: Mutex<Vec<PushedBuild>>,
+}
+
+struct PushedBuild {
+    build: Build,
+    received: Instant,
+}
+
+impl PushInbox {
+    pub fn new(token: &str, expiry: Duration) -> Self {
+        Self {
+            token: token.to_owned(),
+            expiry,
+            builds: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn is_authorized(&self, token: &str) -> bool {
+        secret::is_match(&self.token, token)
+    }
+
+    /// Adds a build to the inbox.
+    /// Only the latest build for a partition is kept.
+    pub fn push(&self, build: Build) {
+        let mut builds = self.builds.lock().unwrap();
+        builds.retain(|b| b.build.partition != build.partition);
+        builds.push(PushedBuild {
+            build,
+            received: Instant::now(),
+        });
+    }
+
+    /// Moves all builds from another inbox into this one.
+    pub fn take_from(&self, other: &PushInbox) {
+        let mut other = other.builds.lock().unwrap();
+        let mut builds = self.builds.lock().unwrap();
+        builds.append(&mut other);
+    }
+
+    /// Gets all builds that have not expired.
+    pub fn get_builds(&self) -> Vec<Build> {
+        let mut builds = self.builds.lock().unwrap();
+        let count = builds.len();
+        builds.retain(|b| b.received.elapsed() < self.expiry);
+        if builds.len() != count {
+            debug!("Removed {} expired pushed build(s)", count - builds.len());
+        }
+        builds.iter().map(|b| b.build.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::IngestBuildModel;
+    use crate::builds::{BuildBuilder, BuildStatus};
+    use crate::engine::state::builds::BuildRepository;
+    use std::collections::HashSet;
+
+    fn create_collector() -> PushCollector {
+        PushCollector::new(&PushConfiguration {
+            id: "push".to_owned(),
+            enabled: Some(true),
+            interval: None,
+            token: "SECRET".to_owned(),
+            expiry: None,
+        })
+    }
+
+    fn collect(collector: &PushCollector) -> Vec<Build> {
+        let (_, listener) = waithandle::new();
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(listener, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+        result
+    }
+
+    #[test]
+    fn should_return_correct_provider_name() {
+        // Given
+        let collector = create_collector();
+        // When
+        let provider = &collector.info().provider;
+        // Then
+        assert_eq!("Push", provider);
+    }
+
+    #[test]
+    fn should_collect_pushed_builds() {
+        // Given
+        let collector = create_collector();
+        let inbox = collector.inbox().unwrap();
+        inbox.push(BuildBuilder::dummy().branch("master").unwrap());
+        inbox.push(BuildBuilder::dummy().branch("develop").unwrap());
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("master", result[0].branch);
+        assert_eq!("develop", result[1].branch);
+    }
+
+    #[test]
+    fn should_only_keep_latest_build_for_partition() {
+        // Given
+        let collector = create_collector();
+        let inbox = collector.inbox().unwrap();
+        inbox.push(BuildBuilder::dummy().build_id("1").unwrap());
+        inbox.push(
+            BuildBuilder::dummy()
+                .build_id("2")
+                .status(BuildStatus::Running)
+                .unwrap(),
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("2", result[0].build_id);
+        assert_eq!(BuildStatus::Running, result[0].status);
+    }
+
+    #[test]
+    fn should_not_return_expired_builds() {
+        // Given
+        let inbox = PushInbox::new("SECRET", Duration::from_secs(0));
+        inbox.push(BuildBuilder::dummy().unwrap());
+
+        // When
+        let result = inbox.get_builds();
+
+        // Then
+        assert_eq!(0, result.len());
+    }
+
+    #[test]
+    fn should_remove_expired_build_that_was_pushed_with_custom_provider() {
+        // Given
+        let collector = create_collector();
+        let model: IngestBuildModel = serde_json::from_str(
+            "{ \"buildId\": \"1\", \"provider\": \"Jenkins\", \"projectId\": \"duck\", \
+             \"definitionId\": \"ci\", \"buildNumber\": \"1\", \"status\": \"Success\", \
+             \"branch\": \"master\", \"url\": \"https://example.com\", \"started\": 1 }",
+        )
+        .unwrap();
+        let builds = BuildRepository::new();
+        builds.update(&model.to_build(&collector.info().id).unwrap());
+
+        // When
+        builds.retain_builds(collector.info(), HashSet::new());
+
+        // Then
+        assert!(builds.all().is_empty());
+    }
+
+    #[test]
+    fn should_take_builds_from_other_inbox() {
+        // Given
+        let inbox = PushInbox::new("SECRET", Duration::from_secs(60));
+        let other = PushInbox::new("SECRET", Duration::from_secs(60));
+        other.push(BuildBuilder::dummy().unwrap());
+
+        // When
+        inbox.take_from(&other);
+
+        // Then
+        assert_eq!(1, inbox.get_builds().len());
+        assert_eq!(0, other.get_builds().len());
+    }
+
+    #[test]
+    fn should_only_authorize_matching_token() {
+        // Given
+        let inbox = PushInbox::new("SECRET", Duration::from_secs(60));
+        // When, Then
+        assert!(inbox.is_authorized("SECRET"));
+        assert!(!inbox.is_authorized("secret"));
+        assert!(!inbox.is_authorized(""));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::config::{PushConfiguration, Validate};
+use crate::DuckResult;
+
+impl Validate for PushConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if self.token.is_empty() {
+            return Err(format_err!("[{}] Push token is empty", self.id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::*;
+    use crate::providers;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "[push] Push token is empty")]
+    fn should_return_error_if_push_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "push": {
+                            "id": "push",
+                            "token": ""
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
+}
@@ -1,16 +1,14 @@
 use log::trace;
-use reqwest::header::ACCEPT;
-use reqwest::{Client, ClientBuilder, RequestBuilder};
 use url::Url;
 
 use crate::config::{TeamCityAuth, TeamCityConfiguration};
 use crate::utils::date;
+use crate::utils::http::*;
 use crate::DuckResult;
 
 pub struct TeamCityClient {
     pub url: Url,
     credentials: TeamCityAuth,
-    client: Client,
 }
 
 impl TeamCityClient {
@@ -18,55 +16,70 @@ impl TeamCityClient {
         Self {
             url: Url::parse(&settings.server_url[..]).unwrap(),
             credentials: settings.credentials.clone(),
-            client: ClientBuilder::new().build().unwrap(),
         }
     }
 
-    pub fn is_online(&self) -> bool {
-        self.send_get_request(format!(
-            "{url}{authtype}/app/rest/server",
-            url = self.url,
-            authtype = self.credentials.get_auth_type()
-        ))
+    pub fn is_online(&self, client: &impl HttpClient) -> bool {
+        self.send_get_request(
+            client,
+            format!(
+                "{url}{authtype}app/rest/server",
+                url = self.url,
+                authtype = self.credentials.get_auth_type()
+            ),
+        )
         .is_ok()
     }
 
-    pub fn get_build_types(&self) -> DuckResult<Vec<TeamCityBuildTypeModel>> {
+    pub fn get_build_types(
+        &self,
+        client: &impl HttpClient,
+    ) -> DuckResult<Vec<TeamCityBuildTypeModel>> {
         // Get all branches for this build configuration.
-        let mut response = self.send_get_request(format!(
-            "{url}{authtype}/app/rest/buildTypes",
-            url = self.url,
-            authtype = self.credentials.get_auth_type()
-        ))?;
+        let mut response = self.send_get_request(
+            client,
+            format!(
+                "{url}{authtype}app/rest/buildTypes",
+                url = self.url,
+                authtype = self.credentials.get_auth_type()
+            ),
+        )?;
 
-        let result: TeamCityBuildTypeCollectionModel = response.json()?;
+        let result: TeamCityBuildTypeCollectionModel = response.deserialize_json()?;
 
         Ok(result.build_types)
     }
 
     pub fn get_builds(
         &self,
+        client: &impl HttpClient,
         build_type: &TeamCityBuildTypeModel,
     ) -> DuckResult<TeamCityBranchCollectionModel> {
         // Get all branches for this build configuration.
-        let mut response = self.send_get_request(format!(
-            "{url}{authtype}/app/rest/buildTypes/id:{id}/branches?locator=default:any\
-             &fields=count,branch(name,default,active,builds(build(id,number,running,status,\
-             branchName,webUrl,startDate,finishDate),count,$locator(running:any,canceled:any,count:1)))",
-            url = self.url,
-            authtype = self.credentials.get_auth_type(),
-            id = build_type.id
-        ))?;
-
-        let result: TeamCityBranchCollectionModel = response.json()?;
+        let mut response = self.send_get_request(
+            client,
+            format!(
+                "{url}{authtype}app/rest/buildTypes/id:{id}/branches?locator=default:any\
+                 &fields=count,branch(name,default,active,builds(build(id,number,running,status,\
+                 branchName,webUrl,startDate,finishDate),count,$locator(running:any,canceled:any,count:1)))",
+                url = self.url,
+                authtype = self.credentials.get_auth_type(),
+                id = build_type.id
+            ),
+        )?;
+
+        let result: TeamCityBranchCollectionModel = response.deserialize_json()?;
 
         Ok(result)
     }
 
-    fn send_get_request(&self, url: String) -> DuckResult<reqwest::Response> {
+    fn send_get_request<T: HttpClient>(&self, client: &T, url: String) -> DuckResult<T::Item> {
         trace!("Sending request to: {}", url);
-        let response = self.client.get(&url).header(ACCEPT, "application/json");
-        let response = self.credentials.authenticate(response).send()?;
+        let mut builder = HttpRequestBuilder::get(&url);
+        builder.add_header("Accept", "application/json");
+
+        self.credentials.authenticate(&mut builder);
+        let response = client.send(&builder)?;
 
         trace!("Received response: {}", response.status());
         if !response.status().is_success() {
@@ -78,17 +91,24 @@ impl TeamCityClient {
 }
 
 impl TeamCityAuth {
+    /// Gets the URL prefix for the authentication type.
+    /// Access tokens are used with the default REST API path.
     pub fn get_auth_type(&self) -> String {
         return match self {
-            TeamCityAuth::Guest => "guestAuth".to_string(),
-            TeamCityAuth::BasicAuth { .. } => "httpAuth".to_string(),
+            TeamCityAuth::Guest => "guestAuth/".to_string(),
+            TeamCityAuth::BasicAuth { .. } => "httpAuth/".to_string(),
+            TeamCityAuth::Token(_) => "".to_string(),
         };
     }
-    pub fn authenticate(&self, builder: RequestBuilder) -> RequestBuilder {
-        return match self {
-            TeamCityAuth::Guest => builder,
+
+    pub fn authenticate(&self, builder: &mut HttpRequestBuilder) {
+        match self {
+            TeamCityAuth::Guest => {}
             TeamCityAuth::BasicAuth { username, password } => {
-                builder.basic_auth(username, Some(password))
+                builder.basic_auth(username, Some(password));
+            }
+            TeamCityAuth::Token(token) => {
+                builder.bearer(token);
             }
         };
     }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use log::{error, trace, warn};
 use waithandle::WaitHandleListener;
 
@@ -5,6 +7,7 @@ use crate::builds::{Build, BuildBuilder, BuildStatus};
 use crate::config::TeamCityConfiguration;
 use crate::providers::collectors::{Collector, CollectorInfo};
 use crate::utils::date;
+use crate::utils::http::{HttpClient, ReqwestClient};
 use crate::DuckResult;
 
 use self::client::*;
@@ -15,28 +18,51 @@ mod validation;
 
 impl CollectorLoader for TeamCityConfiguration {
     fn load(&self) -> DuckResult<Box<dyn Collector>> {
-        Ok(Box::new(TeamCityCollector {
-            client: TeamCityClient::new(self),
-            build_types: self.builds.clone(),
+        Ok(Box::new(TeamCityCollector::<ReqwestClient>::new(self)))
+    }
+}
+
+pub struct TeamCityCollector<T: HttpClient + Default> {
+    http: T,
+    client: TeamCityClient,
+    build_types: Vec<String>,
+    branches: HashMap<String, Vec<String>>,
+    info: CollectorInfo,
+}
+
+impl<T: HttpClient + Default> TeamCityCollector<T> {
+    pub fn new(config: &TeamCityConfiguration) -> Self {
+        TeamCityCollector {
+            http: Default::default(),
+            client: TeamCityClient::new(config),
+            build_types: config.builds.clone(),
+            branches: config.branches.clone().unwrap_or_default(),
             info: CollectorInfo {
-                id: self.id.clone(),
-                enabled: match self.enabled {
+                id: config.id.clone(),
+                enabled: match config.enabled {
                     Option::None => true,
                     Option::Some(e) => e,
                 },
                 provider: "TeamCity".to_string(),
+                interval: config.interval,
             },
-        }))
+        }
     }
-}
 
-pub struct TeamCityCollector {
-    client: TeamCityClient,
-    build_types: Vec<String>,
-    info: CollectorInfo,
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+
+    fn is_branch_included(&self, build_type: &str, branch: &str) -> bool {
+        match self.branches.get(build_type) {
+            Some(branches) => branches.iter().any(|b| b == branch),
+            None => true,
+        }
+    }
 }
 
-impl Collector for TeamCityCollector {
+impl<T: HttpClient + Default> Collector for TeamCityCollector<T> {
     fn info(&self) -> &CollectorInfo {
         &self.info
     }
@@ -47,13 +73,13 @@ impl Collector for TeamCityCollector {
         callback: &mut dyn FnMut(Build),
     ) -> DuckResult<()> {
         // Make sure TeamCity is online.
-        if !self.client.is_online() {
+        if !self.client.is_online(&self.http) {
             error!("There was a problem contacting TeamCity.");
             return Err(format_err!("There was a problem contacting TeamCity."));
         }
 
         // Get all known build types from TeamCity.
-        let known_build_types = self.client.get_build_types()?;
+        let known_build_types = self.client.get_build_types(&self.http)?;
 
         // Get builds for all build types.
         for build_type in self.build_types.iter() {
@@ -74,7 +100,7 @@ impl Collector for TeamCityCollector {
             };
 
             trace!("Getting builds for {}...", build_type);
-            let result = self.client.get_builds(found)?;
+            let result = self.client.get_builds(&self.http, found)?;
             for branch in result.branches {
                 if listener.check().unwrap() {
                     return Ok(());
@@ -86,6 +112,11 @@ impl Collector for TeamCityCollector {
                     &branch.name
                 };
 
+                if !self.is_branch_included(build_type, branch_name) {
+                    trace!("Skipping branch '{}' for {}", branch_name, build_type);
+                    continue;
+                }
+
                 match branch.builds.builds.first() {
                     None => trace!("No builds found for branch '{}'", branch_name),
                     Some(build) => {
@@ -141,3 +172,207 @@ impl TeamCityBuildModel {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::*;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+
+    fn create_config(credentials: TeamCityAuth) -> TeamCityConfiguration {
+        TeamCityConfiguration {
+            id: "teamcity".to_owned(),
+            enabled: Some(true),
+            interval: None,
+            server_url: "http://localhost:8111".to_owned(),
+            credentials,
+            builds: vec!["Duck_Build".to_owned()],
+            branches: None,
+        }
+    }
+
+    fn create_collector(config: &TeamCityConfiguration) -> TeamCityCollector<MockHttpClient> {
+        let collector = TeamCityCollector::<MockHttpClient>::new(config);
+        let prefix = format!(
+            "http://localhost:8111/{}app/rest/",
+            config.credentials.get_auth_type()
+        );
+
+        let client = collector.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, format!("{}server", prefix))
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/server.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, format!("{}buildTypes", prefix))
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/buildTypes.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                format!(
+                    "{}buildTypes/id:Duck_Build/branches?locator=default:any\
+                     &fields=count,branch(name,default,active,builds(build(id,number,running,status,\
+                     branchName,webUrl,startDate,finishDate),count,$locator(running:any,canceled:any,count:1)))",
+                    prefix
+                ),
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/branches.json")),
+        );
+
+        collector
+    }
+
+    fn collect(collector: &TeamCityCollector<MockHttpClient>) -> DuckResult<Vec<Build>> {
+        let (_, listener) = waithandle::new();
+        let mut result = Vec::<Build>::new();
+        collector.collect(listener, &mut |build: Build| {
+            // Store the results
+            result.push(build);
+        })?;
+        Ok(result)
+    }
+
+    #[test]
+    fn should_return_correct_provider_name() {
+        // Given
+        let collector = create_collector(&create_config(TeamCityAuth::Guest));
+        // When
+        let provider = &collector.info().provider;
+        // Then
+        assert_eq!("TeamCity", provider);
+    }
+
+    #[test]
+    fn should_get_correct_data() {
+        // Given
+        let collector = create_collector(&create_config(TeamCityAuth::Guest));
+
+        // When
+        let result = collect(&collector).unwrap();
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("1842", result[0].build_id);
+        assert_eq!("TeamCity", result[0].provider);
+        assert_eq!("teamcity", result[0].collector);
+        assert_eq!("http://localhost:8111/", result[0].origin);
+        assert_eq!("Duck", result[0].project_id);
+        assert_eq!("Duck", result[0].project_name);
+        assert_eq!("Duck_Build", result[0].definition_id);
+        assert_eq!("Build", result[0].definition_name);
+        assert_eq!("117", result[0].build_number);
+        assert_eq!(BuildStatus::Success, result[0].status);
+        assert_eq!("default", result[0].branch);
+        assert_eq!(
+            "http://localhost:8111/viewLog.html?buildId=1842&buildTypeId=Duck_Build",
+            result[0].url
+        );
+        assert_eq!(1577693441, result[0].started_at);
+        assert_eq!(Some(1577693607), result[0].finished_at);
+        assert_eq!("feature/GH-12", result[1].branch);
+        assert_eq!(BuildStatus::Running, result[1].status);
+        assert_eq!(None, result[1].finished_at);
+    }
+
+    #[test]
+    fn should_only_get_builds_from_included_branches() {
+        // Given
+        let mut branches = HashMap::new();
+        branches.insert("Duck_Build".to_owned(), vec!["feature/GH-12".to_owned()]);
+        let collector = create_collector(&TeamCityConfiguration {
+            branches: Some(branches),
+            ..create_config(TeamCityAuth::Guest)
+        });
+
+        // When
+        let result = collect(&collector).unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("feature/GH-12", result[0].branch);
+    }
+
+    #[test]
+    fn should_skip_unknown_build_types() {
+        // Given
+        let collector = create_collector(&TeamCityConfiguration {
+            builds: vec!["Duck_Unknown".to_owned()],
+            ..create_config(TeamCityAuth::Guest)
+        });
+
+        // When
+        let result = collect(&collector).unwrap();
+
+        // Then
+        assert_eq!(0, result.len());
+        assert_eq!(2, collector.get_client().get_sent_requests().len());
+    }
+
+    #[test]
+    fn should_authenticate_using_basic_auth() {
+        // Given
+        let collector = create_collector(&create_config(TeamCityAuth::BasicAuth {
+            username: "patrik".to_owned(),
+            password: "SECRET".to_owned(),
+        }));
+
+        // When
+        collect(&collector).unwrap();
+
+        // Then
+        let requests = collector.get_client().get_sent_requests();
+        assert_eq!(3, requests.len());
+        assert!(requests[0]
+            .url
+            .starts_with("http://localhost:8111/httpAuth/"));
+        assert_eq!(
+            "Basic cGF0cmlrOlNFQ1JFVA==",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_authenticate_using_access_token() {
+        // Given
+        let collector = create_collector(&create_config(TeamCityAuth::Token("SECRET".to_owned())));
+
+        // When
+        collect(&collector).unwrap();
+
+        // Then
+        let requests = collector.get_client().get_sent_requests();
+        assert_eq!(3, requests.len());
+        assert!(requests[0]
+            .url
+            .starts_with("http://localhost:8111/app/rest/"));
+        assert_eq!(
+            "Bearer SECRET",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_return_error_if_teamcity_is_offline() {
+        // Given
+        let collector =
+            TeamCityCollector::<MockHttpClient>::new(&create_config(TeamCityAuth::Guest));
+        collector.get_client().add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "http://localhost:8111/guestAuth/app/rest/server",
+            )
+            .returns_status(StatusCode::SERVICE_UNAVAILABLE),
+        );
+
+        // When
+        let result = collect(&collector);
+
+        // Then
+        assert!(result.is_err());
+    }
+}
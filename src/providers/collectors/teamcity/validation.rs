@@ -29,6 +29,14 @@ impl Validate for TeamCityConfiguration {
                     ));
                 }
             }
+            TeamCityAuth::Token(token) => {
+                if token.is_empty() {
+                    return Err(format_err!(
+                        "[{}] TeamCity access token cannot be empty",
+                        self.id
+                    ));
+                }
+            }
         };
 
         Ok(())
@@ -150,4 +158,31 @@ mod tests {
 
         providers::create_collectors(&config).unwrap();
     }
+
+    #[test]
+    #[should_panic(expected = "[foo] TeamCity access token cannot be empty")]
+    fn should_return_error_if_teamcity_access_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [
+                    {
+                        "teamcity": {
+                            "id": "foo",
+                            "serverUrl": "https://localhost:5000",
+                            "credentials": {
+                                "token": ""
+                            },
+                            "builds": [ "Foo" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_collectors(&config).unwrap();
+    }
 }
@@ -7,6 +7,9 @@ use crate::DuckResult;
 mod hue;
 mod mattermost;
 mod slack;
+mod teams;
+mod template;
+mod webhook;
 
 pub trait ObserverLoader {
     fn load(&self) -> DuckResult<Box<dyn Observer>>;
@@ -27,7 +30,8 @@ pub struct ObserverInfo {
 pub enum Observation<'a> {
     DuckStatusChanged(BuildStatus),
     BuildUpdated(&'a Build),
-    BuildStatusChanged(&'a Build),
+    /// The absolute build status changed from the provided status.
+    BuildStatusChanged(&'a Build, BuildStatus),
     ShuttingDown,
 }
 
@@ -42,7 +46,7 @@ impl<'a> Observation<'a> {
         match self {
             Observation::DuckStatusChanged(_) => ObservationOrigin::System,
             Observation::BuildUpdated(build) => ObservationOrigin::Collector(&build.collector),
-            Observation::BuildStatusChanged(build) => {
+            Observation::BuildStatusChanged(build, _) => {
                 ObservationOrigin::Collector(&build.collector)
             }
             Observation::ShuttingDown => ObservationOrigin::System,
@@ -23,11 +23,16 @@ impl MattermostClient {
         }
     }
 
-    pub fn send(&self, client: &impl HttpClient, message: &str) -> DuckResult<()> {
+    pub fn send(
+        &self,
+        client: &impl HttpClient,
+        message: &str,
+        icon: Option<&str>,
+    ) -> DuckResult<()> {
         let mut builder = HttpRequestBuilder::post(self.credentials.get_url().to_string());
         builder.add_header("Content-Type", "application/json");
         builder.add_header("Accept", "application/json");
-        builder.set_body(self.get_payload(message).to_string());
+        builder.set_body(self.get_payload(message, icon).to_string());
 
         let response = client.send(&builder)?;
         if !response.status().is_success() {
@@ -40,13 +45,17 @@ impl MattermostClient {
         Ok(())
     }
 
-    fn get_payload(&self, message: &str) -> serde_json::Value {
-        match self.channel {
+    fn get_payload(&self, message: &str, icon: Option<&str>) -> serde_json::Value {
+        let mut payload = match self.channel {
             Option::None => json!({ "text": message }),
             Option::Some(_) => json!({
                 "channel_id": self.channel,
                 "text": message
             }),
+        };
+        if let Some(icon) = icon {
+            payload["icon_emoji"] = json!(icon);
         }
+        payload
     }
 }
@@ -4,8 +4,9 @@ use std::iter::FromIterator;
 use log::info;
 
 use crate::builds::BuildStatus;
-use crate::config::MattermostConfiguration;
+use crate::config::{MattermostConfiguration, StatusIcons};
 use crate::filters::BuildFilter;
+use crate::providers::observers::template::{Template, TemplateValues};
 use crate::providers::observers::{Observation, Observer, ObserverInfo, ObserverLoader};
 use crate::utils::http::{HttpClient, ReqwestClient};
 use crate::DuckResult;
@@ -15,6 +16,9 @@ use self::client::MattermostClient;
 mod client;
 mod validation;
 
+static DEFAULT_TEMPLATE: &str =
+    "{{provider}} build status for {{project}}::{{definition}} ({{branch}}) changed to *{{status}}*";
+
 impl ObserverLoader for MattermostConfiguration {
     fn load(&self) -> DuckResult<Box<dyn Observer>> {
         Ok(Box::new(MattermostObserver::<ReqwestClient>::new(self)?))
@@ -24,6 +28,8 @@ impl ObserverLoader for MattermostConfiguration {
 pub struct MattermostObserver<T: HttpClient + Default> {
     client: MattermostClient,
     http: T,
+    template: Template,
+    icons: StatusIcons,
     info: ObserverInfo,
 }
 
@@ -32,6 +38,11 @@ impl<T: HttpClient + Default> MattermostObserver<T> {
         Ok(MattermostObserver {
             client: MattermostClient::new(config),
             http: Default::default(),
+            template: Template::new(match &config.template {
+                Some(template) => template,
+                None => DEFAULT_TEMPLATE,
+            }),
+            icons: config.icons.clone().unwrap_or_default(),
             info: ObserverInfo {
                 id: config.id.clone(),
                 enabled: match config.enabled {
@@ -61,7 +72,7 @@ impl<T: HttpClient + Default> Observer for MattermostObserver<T> {
     }
 
     fn observe(&self, observation: Observation) -> DuckResult<()> {
-        if let Observation::BuildStatusChanged(build) = observation {
+        if let Observation::BuildStatusChanged(build, previous) = observation {
             if build.status != BuildStatus::Unknown {
                 info!(
                     "Sending Mattermost message since build status changed ({})...",
@@ -69,14 +80,12 @@ impl<T: HttpClient + Default> Observer for MattermostObserver<T> {
                 );
                 self.client.send(
                     &self.http,
-                    &format!(
-                        "{} build status for {}::{} ({}) changed to *{:?}*",
-                        build.provider,
-                        build.project_name,
-                        build.definition_name,
-                        build.branch,
-                        build.status
-                    )[..],
+                    &self.template.render(&TemplateValues::for_status_change(
+                        build,
+                        &previous,
+                        &self.icons,
+                    )),
+                    self.icons.get(&build.status),
                 )?;
             }
         };
@@ -102,6 +111,8 @@ mod tests {
             enabled: Some(true),
             collectors: None,
             channel: None,
+            template: None,
+            icons: None,
             filter: None,
             credentials: MattermostCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -119,6 +130,7 @@ mod tests {
         mattermost
             .observe(Observation::BuildStatusChanged(
                 &BuildBuilder::dummy().unwrap(),
+                BuildStatus::Unknown,
             ))
             .unwrap();
 
@@ -138,6 +150,8 @@ mod tests {
             enabled: Some(true),
             collectors: None,
             channel: None,
+            template: None,
+            icons: None,
             filter: None,
             credentials: MattermostCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -155,6 +169,7 @@ mod tests {
         mattermost
             .observe(Observation::BuildStatusChanged(
                 &BuildBuilder::dummy().status(status).unwrap(),
+                BuildStatus::Unknown,
             ))
             .unwrap();
 
@@ -174,6 +189,8 @@ mod tests {
             collectors: None,
             filter: None,
             channel: Some("foo".to_string()),
+            template: None,
+            icons: None,
             credentials: MattermostCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
             },
@@ -190,6 +207,7 @@ mod tests {
         mattermost
             .observe(Observation::BuildStatusChanged(
                 &BuildBuilder::dummy().status(BuildStatus::Success).unwrap(),
+                BuildStatus::Unknown,
             ))
             .unwrap();
 
@@ -202,4 +220,46 @@ mod tests {
             &requests[0].body.clone().unwrap()
         );
     }
+
+    #[test]
+    fn should_use_custom_template_and_icon_if_specified() {
+        // Given
+        let mattermost = MattermostObserver::<MockHttpClient>::new(&MattermostConfiguration {
+            id: "hue".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            filter: None,
+            channel: None,
+            template: Some("{{definition}} went from {{previousStatus}} to {{status}}".to_string()),
+            icons: Some(StatusIcons {
+                failed: Some(":fire:".to_string()),
+                ..Default::default()
+            }),
+            credentials: MattermostCredentials::Webhook {
+                url: "https://example.com/webhook".to_string(),
+            },
+        })
+        .unwrap();
+
+        let client = mattermost.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Put, "https://example.com/webhook")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        mattermost
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+                BuildStatus::Running,
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(
+            "{\"icon_emoji\":\":fire:\",\"text\":\"definition_name went from Running to Failed\"}",
+            &requests[0].body.clone().unwrap()
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use url::Url;
 
 use crate::config::{MattermostConfiguration, MattermostCredentials, Validate};
+use crate::providers::observers::template::{Template, BUILD_PLACEHOLDERS};
 use crate::DuckResult;
 
 impl Validate for MattermostConfiguration {
@@ -23,6 +24,16 @@ impl Validate for MattermostConfiguration {
             }
         };
 
+        if let Some(template) = &self.template {
+            if let Err(e) = Template::new(template).validate(BUILD_PLACEHOLDERS) {
+                return Err(format_err!(
+                    "[{}] Mattermost template is invalid: {}",
+                    self.id,
+                    e
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -4,8 +4,9 @@ use std::iter::FromIterator;
 use log::info;
 
 use crate::builds::BuildStatus;
-use crate::config::SlackConfiguration;
+use crate::config::{SlackConfiguration, StatusIcons};
 use crate::filters::BuildFilter;
+use crate::providers::observers::template::{Template, TemplateValues};
 use crate::providers::observers::{Observation, Observer, ObserverInfo, ObserverLoader};
 use crate::utils::http::{HttpClient, ReqwestClient};
 use crate::DuckResult;
@@ -15,6 +16,9 @@ use self::client::SlackClient;
 mod client;
 mod validation;
 
+static DEFAULT_TEMPLATE: &str =
+    "{{provider}} build status for {{project}}::{{definition}} ({{branch}}) changed to *{{status}}*";
+
 impl ObserverLoader for SlackConfiguration {
     fn load(&self) -> DuckResult<Box<dyn Observer>> {
         Ok(Box::new(SlackObserver::<ReqwestClient>::new(self)?))
@@ -24,14 +28,29 @@ impl ObserverLoader for SlackConfiguration {
 pub struct SlackObserver<T: HttpClient + Default> {
     client: SlackClient,
     http: T,
+    template: Template,
+    icons: StatusIcons,
     info: ObserverInfo,
 }
 
 impl<T: HttpClient + Default> SlackObserver<T> {
     pub fn new(config: &SlackConfiguration) -> DuckResult<Self> {
+        let mut icons = config.icons.clone().unwrap_or_default();
+        icons
+            .success
+            .get_or_insert_with(|| ":heavy_check_mark:".to_owned());
+        icons
+            .failed
+            .get_or_insert_with(|| ":heavy_multiplication_x:".to_owned());
+
         Ok(SlackObserver {
             client: SlackClient::new(config),
             http: Default::default(),
+            template: Template::new(match &config.template {
+                Some(template) => template,
+                None => DEFAULT_TEMPLATE,
+            }),
+            icons,
             info: ObserverInfo {
                 id: config.id.clone(),
                 enabled: match config.enabled {
@@ -61,7 +80,7 @@ impl<T: HttpClient + Default> Observer for SlackObserver<T> {
     }
 
     fn observe(&self, observation: Observation) -> DuckResult<()> {
-        if let Observation::BuildStatusChanged(build) = observation {
+        if let Observation::BuildStatusChanged(build, previous) = observation {
             if is_interesting_status(&build.status) {
                 info!(
                     "Sending Slack message since build status changed ({})...",
@@ -69,19 +88,12 @@ impl<T: HttpClient + Default> Observer for SlackObserver<T> {
                 );
                 self.client.send(
                     &self.http,
-                    &format!(
-                        "{} build status for {}::{} ({}) changed to *{}*",
-                        build.provider,
-                        build.project_name,
-                        build.definition_name,
-                        build.branch,
-                        build.status
-                    )[..],
-                    match build.status {
-                        BuildStatus::Success => ":heavy_check_mark:",
-                        BuildStatus::Failed => ":heavy_multiplication_x:",
-                        _ => ":question:",
-                    },
+                    &self.template.render(&TemplateValues::for_status_change(
+                        build,
+                        &previous,
+                        &self.icons,
+                    )),
+                    self.icons.get(&build.status).unwrap_or(":question:"),
                 )?;
             }
         };
@@ -114,6 +126,8 @@ mod tests {
             enabled: Some(true),
             collectors: None,
             channel: None,
+            template: None,
+            icons: None,
             filter: None,
             credentials: SlackCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -131,6 +145,7 @@ mod tests {
         slack
             .observe(Observation::BuildStatusChanged(
                 &BuildBuilder::dummy().unwrap(),
+                BuildStatus::Unknown,
             ))
             .unwrap();
 
@@ -150,6 +165,8 @@ mod tests {
             enabled: Some(true),
             collectors: None,
             channel: None,
+            template: None,
+            icons: None,
             filter: None,
             credentials: SlackCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -167,6 +184,7 @@ mod tests {
         slack
             .observe(Observation::BuildStatusChanged(
                 &BuildBuilder::dummy().status(status).unwrap(),
+                BuildStatus::Unknown,
             ))
             .unwrap();
 
@@ -177,6 +195,48 @@ mod tests {
         assert_eq!(expected, &requests[0].body.clone().unwrap());
     }
 
+    #[test]
+    fn should_use_custom_template_and_icon_if_specified() {
+        // Given
+        let slack = SlackObserver::<MockHttpClient>::new(&SlackConfiguration {
+            id: "hue".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            channel: None,
+            template: Some("{{icon}} {{definition}} is {{status}} after {{duration}}".to_string()),
+            icons: Some(StatusIcons {
+                success: Some(":tada:".to_string()),
+                ..Default::default()
+            }),
+            filter: None,
+            credentials: SlackCredentials::Webhook {
+                url: "https://example.com/webhook".to_string(),
+            },
+        })
+        .unwrap();
+
+        let client = slack.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Put, "https://example.com/webhook")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        slack
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Success).unwrap(),
+                BuildStatus::Unknown,
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(
+            "{\"icon_emoji\":\":tada:\",\"text\":\":tada: definition_name is Success after 16m 40s\",\"username\":\"Duck\"}",
+            &requests[0].body.clone().unwrap()
+        );
+    }
+
     #[test]
     #[should_panic(expected = "Could not send Slack message (502 Bad Gateway)")]
     fn should_return_error_if_server_return_non_successful_http_status_code() {
@@ -186,6 +246,8 @@ mod tests {
             enabled: Some(true),
             collectors: None,
             channel: None,
+            template: None,
+            icons: None,
             filter: None,
             credentials: SlackCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -203,6 +265,7 @@ mod tests {
         slack
             .observe(Observation::BuildStatusChanged(
                 &BuildBuilder::dummy().unwrap(),
+                BuildStatus::Unknown,
             ))
             .unwrap();
 
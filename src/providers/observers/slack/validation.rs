@@ -1,6 +1,7 @@
 use url::Url;
 
 use crate::config::{SlackConfiguration, SlackCredentials, Validate};
+use crate::providers::observers::template::{Template, BUILD_PLACEHOLDERS};
 use crate::DuckResult;
 
 impl Validate for SlackConfiguration {
@@ -16,6 +17,17 @@ impl Validate for SlackConfiguration {
                 }
             }
         };
+
+        if let Some(template) = &self.template {
+            if let Err(e) = Template::new(template).validate(BUILD_PLACEHOLDERS) {
+                return Err(format_err!(
+                    "[{}] Slack template is invalid: {}",
+                    self.id,
+                    e
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -81,4 +93,33 @@ mod tests {
 
         providers::create_observers(&config).unwrap();
     }
+
+    #[test]
+    #[should_panic(expected = "[foo] Slack template is invalid: Unknown placeholder")]
+    fn should_return_error_if_slack_template_contains_unknown_placeholder() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "slack": {
+                            "id": "foo",
+                            "template": "{{status}} {{foo}}",
+                            "credentials": {
+                                "webhook": {
+                                    "url": "https://slack.com/MY-WEBHOOK-URL"
+                                }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_observers(&config).unwrap();
+    }
 }
@@ -0,0 +1,40 @@
+use crate::config::{TeamsConfiguration, TeamsCredentials};
+use crate::utils::http::{HttpClient, HttpRequestBuilder, HttpResponse};
+use crate::DuckResult;
+
+pub struct TeamsClient {
+    credentials: TeamsCredentials,
+}
+
+impl TeamsCredentials {
+    pub fn get_url(&self) -> &str {
+        match self {
+            TeamsCredentials::Webhook { url } => &url[..],
+        }
+    }
+}
+
+impl TeamsClient {
+    pub fn new(config: &TeamsConfiguration) -> Self {
+        TeamsClient {
+            credentials: config.credentials.clone(),
+        }
+    }
+
+    pub fn send(&self, client: &impl HttpClient, card: &serde_json::Value) -> DuckResult<()> {
+        let mut builder = HttpRequestBuilder::post(self.credentials.get_url().to_string());
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+        builder.set_body(card.to_string());
+
+        let response = client.send(&builder)?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Could not send Microsoft Teams message ({})",
+                response.status()
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use log::info;
+
+use crate::builds::{Build, BuildStatus};
+use crate::config::TeamsConfiguration;
+use crate::filters::BuildFilter;
+use crate::providers::observers::{Observation, Observer, ObserverInfo, ObserverLoader};
+use crate::utils::http::{HttpClient, ReqwestClient};
+use crate::DuckResult;
+
+use self::client::TeamsClient;
+
+mod client;
+mod validation;
+
+impl ObserverLoader for TeamsConfiguration {
+    fn load(&self) -> DuckResult<Box<dyn Observer>> {
+        Ok(Box::new(TeamsObserver::<ReqwestClient>::new(self)?))
+    }
+}
+
+pub struct TeamsObserver<T: HttpClient + Default> {
+    client: TeamsClient,
+    http: T,
+    info: ObserverInfo,
+}
+
+impl<T: HttpClient + Default> TeamsObserver<T> {
+    pub fn new(config: &TeamsConfiguration) -> DuckResult<Self> {
+        Ok(TeamsObserver {
+            client: TeamsClient::new(config),
+            http: Default::default(),
+            info: ObserverInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    None => true,
+                    Some(e) => e,
+                },
+                filter: BuildFilter::new(config.filter.clone())?,
+                collectors: match &config.collectors {
+                    Option::None => Option::None,
+                    Option::Some(collectors) => {
+                        Some(HashSet::from_iter(collectors.iter().cloned()))
+                    }
+                },
+            },
+        })
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Observer for TeamsObserver<T> {
+    fn info(&self) -> &ObserverInfo {
+        &self.info
+    }
+
+    fn observe(&self, observation: Observation) -> DuckResult<()> {
+        if let Observation::BuildStatusChanged(build, _) = observation {
+            if is_interesting_status(&build.status) {
+                info!(
+                    "Sending Microsoft Teams message since build status changed ({})...",
+                    build.status
+                );
+                self.client.send(&self.http, &create_card(build))?;
+            }
+        };
+
+        Ok(())
+    }
+}
+
+fn is_interesting_status(status: &BuildStatus) -> bool {
+    match status {
+        BuildStatus::Success | BuildStatus::Failed => true,
+        _ => false,
+    }
+}
+
+fn create_card(build: &Build) -> serde_json::Value {
+    let title = format!(
+        "{} build status for {}::{} ({}) changed to **{}**",
+        build.provider, build.project_name, build.definition_name, build.branch, build.status
+    );
+
+    json!({
+        "@type": "MessageCard",
+        "@context": "https://schema.org/extensions",
+        "summary": title,
+        "themeColor": match build.status {
+            BuildStatus::Success => "2EB886",
+            BuildStatus::Failed => "D00000",
+            _ => "808080",
+        },
+        "sections": [
+            {
+                "activityTitle": title,
+                "facts": [
+                    { "name": "Project", "value": build.project_name },
+                    { "name": "Definition", "value": build.definition_name },
+                    { "name": "Branch", "value": build.branch },
+                    { "name": "Build", "value": build.build_number }
+                ],
+                "markdown": true
+            }
+        ],
+        "potentialAction": [
+            {
+                "@type": "OpenUri",
+                "name": "View build",
+                "targets": [ { "os": "default", "uri": build.url } ]
+            }
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+    use crate::config::TeamsCredentials;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+    use serde_json::Value;
+    use test_case::test_case;
+
+    fn create_observer(status: StatusCode) -> TeamsObserver<MockHttpClient> {
+        let teams = TeamsObserver::<MockHttpClient>::new(&TeamsConfiguration {
+            id: "teams".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            filter: None,
+            credentials: TeamsCredentials::Webhook {
+                url: "https://example.com/webhook".to_string(),
+            },
+        })
+        .unwrap();
+
+        teams.get_client().add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/webhook")
+                .returns_status(status),
+        );
+
+        teams
+    }
+
+    #[test]
+    fn should_post_to_webhook_url() {
+        // Given
+        let teams = create_observer(StatusCode::OK);
+
+        // When
+        teams
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().unwrap(),
+                BuildStatus::Unknown,
+            ))
+            .unwrap();
+
+        // Then
+        let requests = teams.get_client().get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(HttpMethod::Post, requests[0].method);
+        assert_eq!("https://example.com/webhook", &requests[0].url);
+    }
+
+    #[test_case(BuildStatus::Success, "2EB886" ; "Success")]
+    #[test_case(BuildStatus::Failed, "D00000" ; "Failed")]
+    fn should_send_correct_message_card(status: BuildStatus, color: &str) {
+        // Given
+        let teams = create_observer(StatusCode::OK);
+
+        // When
+        teams
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(status.clone()).unwrap(),
+                BuildStatus::Unknown,
+            ))
+            .unwrap();
+
+        // Then
+        let requests = teams.get_client().get_sent_requests();
+        let card: Value = serde_json::from_str(requests[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!("MessageCard", card["@type"]);
+        assert_eq!(color, card["themeColor"]);
+        assert_eq!(
+            format!(
+                "TeamCity build status for project_name::definition_name (branch) changed to **{}**",
+                status
+            ),
+            card["sections"][0]["activityTitle"]
+        );
+        assert_eq!("project_name", card["sections"][0]["facts"][0]["value"]);
+        assert_eq!("definition_name", card["sections"][0]["facts"][1]["value"]);
+        assert_eq!("branch", card["sections"][0]["facts"][2]["value"]);
+        assert_eq!(
+            "https://dummy",
+            card["potentialAction"][0]["targets"][0]["uri"]
+        );
+    }
+
+    #[test]
+    fn should_ignore_builds_that_are_not_absolute() {
+        // Given
+        let teams = create_observer(StatusCode::OK);
+
+        // When
+        teams
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Running).unwrap(),
+                BuildStatus::Unknown,
+            ))
+            .unwrap();
+
+        // Then
+        assert_eq!(0, teams.get_client().get_sent_requests().len());
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not send Microsoft Teams message (502 Bad Gateway)")]
+    fn should_return_error_if_server_return_non_successful_http_status_code() {
+        // Given
+        let teams = create_observer(StatusCode::BAD_GATEWAY);
+
+        // When, Then
+        teams
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().unwrap(),
+                BuildStatus::Unknown,
+            ))
+            .unwrap();
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use crate::builds::{Build, BuildStatus};
+use crate::config::StatusIcons;
+use crate::DuckResult;
+
+/// The placeholders that are available for builds.
+pub static BUILD_PLACEHOLDERS: &[&str] = &[
+    "provider",
+    "collector",
+    "origin",
+    "project",
+    "projectId",
+    "definition",
+    "definitionId",
+    "branch",
+    "buildId",
+    "buildNumber",
+    "status",
+    "previousStatus",
+    "url",
+    "started",
+    "finished",
+    "duration",
+    "icon",
+];
+
+/// A message template where {{placeholder}} expressions
+/// are replaced with values when the template is rendered.
+pub struct Template {
+    parts: Vec<TemplatePart>,
+}
+
+enum TemplatePart {
+    Text(String),
+    Placeholder(String),
+}
+
+impl Template {
+    pub fn new(text: &str) -> Self {
+        let mut parts = Vec::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            let end = match rest[start..].find("}}") {
+                Some(end) => start + end,
+                None => break,
+            };
+            if start > 0 {
+                parts.push(TemplatePart::Text(rest[..start].to_owned()));
+            }
+            parts.push(TemplatePart::Placeholder(
+                rest[start + 2..end].trim().to_owned(),
+            ));
+            rest = &rest[end + 2..];
+        }
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Text(rest.to_owned()));
+        }
+        Self { parts }
+    }
+
+    /// Makes sure that the template only contains known placeholders.
+    pub fn validate(&self, known: &[&str]) -> DuckResult<()> {
+        for part in self.parts.iter() {
+            if let TemplatePart::Placeholder(name) = part {
+                if !known.contains(&&name[..]) {
+                    return Err(format_err!("Unknown placeholder '{{{{{}}}}}'", name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Renders the template. Unknown placeholders are left as they are.
+    pub fn render(&self, values: &TemplateValues) -> String {
+        let mut result = String::new();
+        for part in self.parts.iter() {
+            match part {
+                TemplatePart::Text(text) => result.push_str(text),
+                TemplatePart::Placeholder(name) => match values.get(name) {
+                    Some(value) => result.push_str(value),
+                    None => result.push_str(&format!("{{{{{}}}}}", name)),
+                },
+            }
+        }
+        result
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Values
+
+pub struct TemplateValues {
+    values: HashMap<&'static str, String>,
+}
+
+impl TemplateValues {
+    /// Creates template values where all build placeholders are empty.
+    pub fn new() -> Self {
+        let mut result = Self {
+            values: HashMap::new(),
+        };
+        for name in BUILD_PLACEHOLDERS.iter() {
+            result.add(name, "");
+        }
+        result
+    }
+
+    pub fn for_build(build: &Build, icons: &StatusIcons) -> Self {
+        let mut result = Self::new();
+        result.add("provider", &build.provider);
+        result.add("collector", &build.collector);
+        result.add("origin", &build.origin);
+        result.add("project", &build.project_name);
+        result.add("projectId", &build.project_id);
+        result.add("definition", &build.definition_name);
+        result.add("definitionId", &build.definition_id);
+        result.add("branch", &build.branch);
+        result.add("buildId", &build.build_id);
+        result.add("buildNumber", &build.build_number);
+        result.add("status", &build.status.to_string());
+        result.add("url", &build.url);
+        result.add("started", &build.started_at.to_string());
+        result.add(
+            "finished",
+            &build.finished_at.map(|f| f.to_string()).unwrap_or_default(),
+        );
+        result.add("duration", &get_duration(build));
+        result.add("icon", icons.get(&build.status).unwrap_or(""));
+        result
+    }
+
+    /// Creates template values for a build where
+    /// the absolute build status changed.
+    pub fn for_status_change(build: &Build, previous: &BuildStatus, icons: &StatusIcons) -> Self {
+        let mut result = Self::for_build(build, icons);
+        result.add("previousStatus", &previous.to_string());
+        result
+    }
+
+    pub fn add(&mut self, name: &'static str, value: &str) {
+        self.values.insert(name, value.to_owned());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(|v| &v[..])
+    }
+}
+
+fn get_duration(build: &Build) -> String {
+    let seconds = match build.finished_at {
+        Some(finished_at) => finished_at - build.started_at,
+        None => return "".to_owned(),
+    };
+    if seconds < 0 {
+        return "".to_owned();
+    }
+    match (seconds / 3600, (seconds % 3600) / 60, seconds % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m {}s", m, s),
+        (h, m, s) => format!("{}h {}m {}s", h, m, s),
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Icons
+
+impl StatusIcons {
+    pub fn get(&self, status: &BuildStatus) -> Option<&str> {
+        let icon = match status {
+            BuildStatus::Success => &self.success,
+            BuildStatus::Failed => &self.failed,
+            BuildStatus::Running => &self.running,
+            BuildStatus::Canceled => &self.canceled,
+            BuildStatus::Queued => &self.queued,
+            BuildStatus::Skipped => &self.skipped,
+            BuildStatus::Unknown => &None,
+        };
+        icon.as_ref().map(|i| &i[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+    use test_case::test_case;
+
+    #[test]
+    fn should_render_build_placeholders() {
+        // Given
+        let template = Template::new("{{ project }}::{{definition}} is {{status}} ({{duration}})");
+        let build = BuildBuilder::dummy()
+            .status(BuildStatus::Failed)
+            .started_at(1000)
+            .finished_at(Some(1090))
+            .unwrap();
+
+        // When
+        let result = template.render(&TemplateValues::for_build(&build, &Default::default()));
+
+        // Then
+        assert_eq!("project_name::definition_name is Failed (1m 30s)", result);
+    }
+
+    #[test_case(BuildStatus::Failed, "Failed" ; "Failed")]
+    #[test_case(BuildStatus::Running, "Running" ; "Running")]
+    fn should_render_previous_status(previous: BuildStatus, expected: &str) {
+        // Given
+        let template = Template::new("{{previousStatus}}");
+        let build = BuildBuilder::dummy().status(BuildStatus::Success).unwrap();
+
+        // When
+        let result = template.render(&TemplateValues::for_status_change(
+            &build,
+            &previous,
+            &Default::default(),
+        ));
+
+        // Then
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn should_render_icon_for_status() {
+        // Given
+        let template = Template::new("{{icon}} {{status}}");
+        let icons = StatusIcons {
+            failed: Some(":fire:".to_owned()),
+            ..Default::default()
+        };
+        let build = BuildBuilder::dummy().status(BuildStatus::Failed).unwrap();
+
+        // When
+        let result = template.render(&TemplateValues::for_build(&build, &icons));
+
+        // Then
+        assert_eq!(":fire: Failed", result);
+    }
+
+    #[test]
+    fn should_leave_unknown_and_unterminated_placeholders() {
+        // Given
+        let template = Template::new("{{foo}} {{status");
+        let mut values = TemplateValues::new();
+        values.add("status", "Success");
+
+        // When
+        let result = template.render(&values);
+
+        // Then
+        assert_eq!("{{foo}} {{status", result);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown placeholder '{{foo}}'")]
+    fn should_return_error_if_template_contains_unknown_placeholder() {
+        Template::new("{{status}} {{foo}}")
+            .validate(BUILD_PLACEHOLDERS)
+            .unwrap();
+    }
+}
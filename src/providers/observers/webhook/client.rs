@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::config::WebhookConfiguration;
+use crate::utils::http::{HttpClient, HttpRequestBuilder, HttpResponse};
+use crate::DuckResult;
+
+pub struct WebhookClient {
+    url: String,
+    headers: HashMap<String, String>,
+    secret: Option<String>,
+}
+
+impl WebhookClient {
+    pub fn new(config: &WebhookConfiguration) -> Self {
+        WebhookClient {
+            url: config.url.clone(),
+            headers: config.headers.clone().unwrap_or_default(),
+            secret: config.secret.clone(),
+        }
+    }
+
+    pub fn send(&self, client: &impl HttpClient, payload: &str) -> DuckResult<()> {
+        let mut builder = HttpRequestBuilder::post(self.url.clone());
+        builder.add_header("Content-Type", "application/json");
+        for (name, value) in self.headers.iter() {
+            builder.add_header(name, value);
+        }
+
+        // Sign the payload?
+        if let Some(secret) = &self.secret {
+            builder.add_header(
+                "X-Duck-Signature",
+                &format!("sha256={}", sign(secret, payload)?),
+            );
+        }
+
+        builder.set_body(payload.to_owned());
+
+        let response = client.send(&builder)?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Could not send webhook payload ({})",
+                response.status()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+fn sign(secret: &str, payload: &str) -> DuckResult<String> {
+    let mut mac = Hmac::<Sha256>::new_varkey(secret.as_bytes())
+        .map_err(|_| format_err!("Invalid webhook secret"))?;
+    mac.input(payload.as_bytes());
+    Ok(hex::encode(mac.result().code()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_sign_payload_using_hmac_sha256() {
+        // Given, When
+        let signature = sign("key", "The quick brown fox jumps over the lazy dog").unwrap();
+
+        // Then
+        assert_eq!(
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+            signature
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use log::info;
+use serde_json::Value;
+
+use crate::builds::{Build, BuildStatus};
+use crate::config::WebhookConfiguration;
+use crate::filters::BuildFilter;
+use crate::providers::observers::template::{Template, TemplateValues};
+use crate::providers::observers::{Observation, Observer, ObserverInfo, ObserverLoader};
+use crate::utils::http::{HttpClient, ReqwestClient};
+use crate::DuckResult;
+
+use self::client::WebhookClient;
+
+mod client;
+mod validation;
+
+impl ObserverLoader for WebhookConfiguration {
+    fn load(&self) -> DuckResult<Box<dyn Observer>> {
+        Ok(Box::new(WebhookObserver::<ReqwestClient>::new(self)?))
+    }
+}
+
+pub struct WebhookObserver<T: HttpClient + Default> {
+    client: WebhookClient,
+    http: T,
+    payload: Option<Value>,
+    info: ObserverInfo,
+}
+
+impl<T: HttpClient + Default> WebhookObserver<T> {
+    pub fn new(config: &WebhookConfiguration) -> DuckResult<Self> {
+        Ok(WebhookObserver {
+            client: WebhookClient::new(config),
+            http: Default::default(),
+            payload: config.payload.clone(),
+            info: ObserverInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    None => true,
+                    Some(e) => e,
+                },
+                filter: BuildFilter::new(config.filter.clone())?,
+                collectors: match &config.collectors {
+                    Option::None => Option::None,
+                    Option::Some(collectors) => {
+                        Some(HashSet::from_iter(collectors.iter().cloned()))
+                    }
+                },
+            },
+        })
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+
+    fn send(&self, event: &str, status: &BuildStatus, build: Option<&Build>) -> DuckResult<()> {
+        let payload = match &self.payload {
+            Some(template) => expand_template(template, &get_placeholders(event, status, build)),
+            None => get_default_payload(event, status, build),
+        };
+
+        info!("Sending webhook payload ({})...", event);
+        self.client.send(&self.http, &payload.to_string())
+    }
+}
+
+impl<T: HttpClient + Default> Observer for WebhookObserver<T> {
+    fn info(&self) -> &ObserverInfo {
+        &self.info
+    }
+
+    fn observe(&self, observation: Observation) -> DuckResult<()> {
+        match observation {
+            Observation::DuckStatusChanged(status) => {
+                self.send("duckStatusChanged", &status, None)?
+            }
+            Observation::BuildUpdated(build) => {
+                self.send("buildUpdated", &build.status, Some(build))?
+            }
+            Observation::BuildStatusChanged(build, _) => {
+                self.send("buildStatusChanged", &build.status, Some(build))?
+            }
+            Observation::ShuttingDown => {}
+        };
+
+        Ok(())
+    }
+}
+
+fn get_default_payload(event: &str, status: &BuildStatus, build: Option<&Build>) -> Value {
+    match build {
+        None => json!({
+            "event": event,
+            "status": status.to_string()
+        }),
+        Some(build) => json!({
+            "event": event,
+            "build": {
+                "provider": build.provider,
+                "collector": build.collector,
+                "project": build.project_name,
+                "definition": build.definition_name,
+                "branch": build.branch,
+                "buildId": build.build_id,
+                "buildNumber": build.build_number,
+                "status": status.to_string(),
+                "url": build.url,
+                "started": build.started_at,
+                "finished": build.finished_at
+            }
+        }),
+    }
+}
+
+fn get_placeholders(event: &str, status: &BuildStatus, build: Option<&Build>) -> TemplateValues {
+    let mut result = match build {
+        Some(build) => TemplateValues::for_build(build, &Default::default()),
+        None => TemplateValues::new(),
+    };
+    result.add("event", event);
+    result.add("status", &status.to_string());
+    result
+}
+
+fn expand_template(template: &Value, placeholders: &TemplateValues) -> Value {
+    match template {
+        Value::String(text) => Value::String(Template::new(text).render(placeholders)),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|i| expand_template(i, placeholders))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), expand_template(v, placeholders)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+    use std::collections::HashMap;
+
+    fn create_observer(
+        payload: Option<Value>,
+        secret: Option<&str>,
+    ) -> WebhookObserver<MockHttpClient> {
+        let mut headers = HashMap::new();
+        headers.insert("X-Team".to_owned(), "Ducks".to_owned());
+        let observer = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            url: "https://example.com/webhook".to_string(),
+            headers: Some(headers),
+            secret: secret.map(|s| s.to_owned()),
+            payload,
+            filter: None,
+        })
+        .unwrap();
+
+        observer.get_client().add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/webhook")
+                .returns_status(StatusCode::OK),
+        );
+
+        observer
+    }
+
+    #[test]
+    fn should_post_default_payload_to_url() {
+        // Given
+        let webhook = create_observer(None, None);
+
+        // When
+        webhook
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+                BuildStatus::Unknown,
+            ))
+            .unwrap();
+
+        // Then
+        let requests = webhook.get_client().get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(HttpMethod::Post, requests[0].method);
+        assert_eq!("https://example.com/webhook", &requests[0].url);
+        assert_eq!("Ducks", requests[0].headers.get("X-Team").unwrap());
+        let body: Value = serde_json::from_str(requests[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!("buildStatusChanged", body["event"]);
+        assert_eq!("Failed", body["build"]["status"]);
+        assert_eq!("definition_name", body["build"]["definition"]);
+    }
+
+    #[test]
+    fn should_post_duck_status_changes() {
+        // Given
+        let webhook = create_observer(None, None);
+
+        // When
+        webhook
+            .observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+
+        // Then
+        let requests = webhook.get_client().get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "{\"event\":\"duckStatusChanged\",\"status\":\"Success\"}",
+            requests[0].body.as_ref().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_expand_placeholders_in_custom_payload() {
+        // Given
+        let webhook = create_observer(
+            Some(json!({
+                "text": "{{project}}::{{definition}} ({{branch}}) is {{status}}",
+                "tags": [ "{{event}}", 42 ]
+            })),
+            None,
+        );
+
+        // When
+        webhook
+            .observe(Observation::BuildUpdated(&BuildBuilder::dummy().unwrap()))
+            .unwrap();
+
+        // Then
+        let requests = webhook.get_client().get_sent_requests();
+        assert_eq!(
+            "{\"tags\":[\"buildUpdated\",42],\"text\":\"project_name::definition_name (branch) is Success\"}",
+            requests[0].body.as_ref().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_sign_payload_if_secret_has_been_specified() {
+        // Given
+        let webhook = create_observer(Some(json!({ "text": "{{status}}" })), Some("key"));
+
+        // When
+        webhook
+            .observe(Observation::DuckStatusChanged(BuildStatus::Failed))
+            .unwrap();
+
+        // Then
+        let requests = webhook.get_client().get_sent_requests();
+        assert_eq!(
+            "sha256=3aecfdb512762c6df2107e02a9db8bd5ae277f6108274af498ac7864e20315a3",
+            requests[0].headers.get("X-Duck-Signature").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_not_send_anything_when_shutting_down() {
+        // Given
+        let webhook = create_observer(None, None);
+
+        // When
+        webhook.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        assert_eq!(0, webhook.get_client().get_sent_requests().len());
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not send webhook payload (502 Bad Gateway)")]
+    fn should_return_error_if_server_return_non_successful_http_status_code() {
+        // Given
+        let webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            url: "https://example.com/webhook".to_string(),
+            headers: None,
+            secret: None,
+            payload: None,
+            filter: None,
+        })
+        .unwrap();
+
+        webhook.get_client().add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/webhook")
+                .returns_status(StatusCode::BAD_GATEWAY),
+        );
+
+        // When, Then
+        webhook
+            .observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+    }
+}
@@ -0,0 +1,109 @@
+use url::Url;
+
+use crate::config::{Validate, WebhookConfiguration};
+use crate::DuckResult;
+
+impl Validate for WebhookConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if let Err(e) = Url::parse(&self.url) {
+            return Err(format_err!("[{}] Webhook URL is invalid: {}", self.id, e));
+        }
+
+        if let Some(headers) = &self.headers {
+            if headers.keys().any(|name| name.trim().is_empty()) {
+                return Err(format_err!("[{}] Webhook header name is empty", self.id));
+            }
+        }
+
+        if let Some(secret) = &self.secret {
+            if secret.is_empty() {
+                return Err(format_err!("[{}] Webhook secret is empty", self.id));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "[foo] Webhook URL is invalid: relative URL without a base")]
+    fn should_return_error_if_webhook_url_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "webhook": {
+                            "id": "foo",
+                            "url": ""
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[foo] Webhook header name is empty")]
+    fn should_return_error_if_webhook_header_name_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "webhook": {
+                            "id": "foo",
+                            "url": "https://example.com/webhook",
+                            "headers": {
+                                "": "bar"
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "[foo] Webhook secret is empty")]
+    fn should_return_error_if_webhook_secret_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "webhook": {
+                            "id": "foo",
+                            "url": "https://example.com/webhook",
+                            "secret": ""
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        providers::create_observers(&config).unwrap();
+    }
+}
@@ -2,19 +2,40 @@ use crate::builds::BuildStatus;
 use crate::DuckResult;
 use std::fmt::Display;
 
+use self::diagnostics::Diagnostic;
+
+mod diagnostics;
 mod lexer;
 mod parser;
 
 pub fn parse<T: Into<String>>(expression: T) -> DuckResult<Expression> {
-    match parse_expression(expression) {
+    let expression = expression.into();
+    match parse_expression(&expression) {
         Ok(expression) => Ok(expression),
-        Err(e) => Err(format_err!("Error parsing expression: {}", e)),
+        Err(e) => match e.downcast_ref::<Diagnostic>() {
+            Some(diagnostic) => Err(format_err!(
+                "Error parsing expression: {}",
+                diagnostic.render(&expression)
+            )),
+            None => Err(format_err!("Error parsing expression: {}", e)),
+        },
     }
 }
 
-fn parse_expression<T: Into<String>>(expression: T) -> DuckResult<Expression> {
-    let expression = expression.into();
-    parser::parse(&mut lexer::tokenize(&expression[..])?)
+fn parse_expression(expression: &str) -> DuckResult<Expression> {
+    let stream = &mut lexer::tokenize(expression)?;
+    let result = parser::parse(stream)?;
+
+    // Make sure that we've consumed the whole expression.
+    if let Some(token) = stream.current() {
+        return Err(Diagnostic::new(
+            format!("Unexpected token '{}'", token),
+            stream.current_span(),
+        )
+        .into());
+    }
+
+    Ok(result)
 }
 
 ///////////////////////////////////////////////////////////
@@ -27,6 +48,12 @@ pub trait Visitor<TContext, TResult> {
     fn constant(&self, ctx: &TContext, constant: &Constant) -> DuckResult<TResult>;
     fn property(&self, ctx: &TContext, property: &Property) -> DuckResult<TResult>;
     fn scope(&self, ctx: &TContext, exp: &Expression) -> DuckResult<TResult>;
+    fn function(
+        &self,
+        ctx: &TContext,
+        function: &Function,
+        args: &[Expression],
+    ) -> DuckResult<TResult>;
     fn relational(
         &self,
         ctx: &TContext,
@@ -48,6 +75,7 @@ pub enum Expression {
     Property(Property),
     Relational(Box<Expression>, Box<Expression>, Operator),
     Scope(Box<Expression>),
+    Function(Function, Vec<Expression>),
 }
 
 impl Expression {
@@ -64,6 +92,25 @@ impl Expression {
             Expression::Property(property) => visitor.property(ctx, property),
             Expression::Relational(lhs, rhs, op) => visitor.relational(ctx, lhs, rhs, op),
             Expression::Scope(expression) => visitor.scope(ctx, expression),
+            Expression::Function(function, args) => visitor.function(ctx, function, args),
+        }
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expression::And(lhs, rhs) => write!(f, "{} and {}", lhs, rhs),
+            Expression::Or(lhs, rhs) => write!(f, "{} or {}", lhs, rhs),
+            Expression::Not(expression) => write!(f, "not {}", expression),
+            Expression::Constant(constant) => write!(f, "{}", constant),
+            Expression::Property(property) => write!(f, "{}", property),
+            Expression::Relational(lhs, rhs, op) => write!(f, "{} {} {}", lhs, op, rhs),
+            Expression::Scope(expression) => write!(f, "({})", expression),
+            Expression::Function(function, args) => {
+                let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+                write!(f, "{}({})", function, args.join(", "))
+            }
         }
     }
 }
@@ -74,6 +121,24 @@ pub enum Constant {
     Integer(i64),
     String(String),
     Status(BuildStatus),
+    List(Vec<Constant>),
+    Null,
+}
+
+impl Display for Constant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Constant::Boolean(value) => write!(f, "{}", value),
+            Constant::Integer(value) => write!(f, "{}", value),
+            Constant::String(value) => write!(f, "'{}'", value),
+            Constant::Status(status) => write!(f, "'{}'", status.to_string().to_lowercase()),
+            Constant::List(items) => {
+                let items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Constant::Null => write!(f, "null"),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -84,6 +149,11 @@ pub enum Operator {
     GreaterThanOrEqualTo,
     LessThan,
     LessThanOrEqualTo,
+    Like,
+    Matches,
+    In,
+    StartsWith,
+    Contains,
 }
 
 impl Display for Operator {
@@ -95,6 +165,11 @@ impl Display for Operator {
             Operator::GreaterThanOrEqualTo => write!(f, ">="),
             Operator::LessThan => write!(f, "<"),
             Operator::LessThanOrEqualTo => write!(f, "<="),
+            Operator::Like => write!(f, "like"),
+            Operator::Matches => write!(f, "matches"),
+            Operator::In => write!(f, "in"),
+            Operator::StartsWith => write!(f, "startswith"),
+            Operator::Contains => write!(f, "contains"),
         }
     }
 }
@@ -108,4 +183,126 @@ pub enum Property {
     Build,
     Collector,
     Provider,
+    ProjectName,
+    DefinitionName,
+    BuildNumber,
+    Origin,
+    Url,
+    StartedAt,
+    FinishedAt,
+    Duration,
+}
+
+impl Display for Property {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Property::Branch => write!(f, "branch"),
+            Property::Status => write!(f, "status"),
+            Property::Project => write!(f, "project"),
+            Property::Definition => write!(f, "definition"),
+            Property::Build => write!(f, "build"),
+            Property::Collector => write!(f, "collector"),
+            Property::Provider => write!(f, "provider"),
+            Property::ProjectName => write!(f, "project_name"),
+            Property::DefinitionName => write!(f, "definition_name"),
+            Property::BuildNumber => write!(f, "build_number"),
+            Property::Origin => write!(f, "origin"),
+            Property::Url => write!(f, "url"),
+            Property::StartedAt => write!(f, "started_at"),
+            Property::FinishedAt => write!(f, "finished_at"),
+            Property::Duration => write!(f, "duration"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Function {
+    Age,
+    Now,
+}
+
+impl Function {
+    /// Gets the number of arguments the function expects.
+    pub fn get_arity(&self) -> usize {
+        match self {
+            Function::Age => 1,
+            Function::Now => 0,
+        }
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Function::Age => write!(f, "age"),
+            Function::Now => write!(f, "now"),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_format_expression() {
+        // Given
+        let expression =
+            parse("!(status in ['failed', 'running']) && age(finished_at) < 1h || true").unwrap();
+
+        // When
+        let result = expression.to_string();
+
+        // Then
+        assert_eq!(
+            "not (status in ['failed', 'running']) and age(finished_at) < 3600 or true",
+            result
+        );
+    }
+
+    #[test]
+    fn should_point_at_unknown_property() {
+        // Given, When
+        let result = parse("status == 'failed' and brnch == 'master'");
+
+        // Then
+        assert_eq!(
+            "Error parsing expression: Unknown property 'brnch'. Did you mean 'branch'?\n\
+             \x20--> line 1, column 24\n\
+             \x20 |\n\
+             1 | status == 'failed' and brnch == 'master'\n\
+             \x20 |                        ^^^^^",
+            format!("{}", result.unwrap_err())
+        );
+    }
+
+    #[test]
+    fn should_point_at_end_of_expression() {
+        // Given, When
+        let result = parse("branch ==");
+
+        // Then
+        assert_eq!(
+            "Error parsing expression: Unexpected end of expression\n\
+             \x20--> line 1, column 10\n\
+             \x20 |\n\
+             1 | branch ==\n\
+             \x20 |          ^",
+            format!("{}", result.unwrap_err())
+        );
+    }
+
+    #[test]
+    fn should_return_error_if_expression_has_trailing_tokens() {
+        // Given, When
+        let result = parse("branch == 'master' 'develop'");
+
+        // Then
+        let error = format!("{}", result.unwrap_err());
+        assert!(error.starts_with("Error parsing expression: Unexpected token 'develop'\n"));
+        assert!(error.ends_with("|                    ^^^^^^^^^"));
+    }
 }
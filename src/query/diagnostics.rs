@@ -0,0 +1,180 @@
+use std::fmt::Display;
+
+/// The location of a token in an expression,
+/// measured in characters from the start of the expression.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+/// An error that points at a specific location in an expression.
+#[derive(Debug)]
+pub struct Diagnostic {
+    message: String,
+    span: Span,
+    suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new<T: Into<String>>(message: T, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+            suggestion: None,
+        }
+    }
+
+    /// Suggests the candidate that is closest to the provided value, if any.
+    pub fn with_suggestion(mut self, value: &str, candidates: &[&str]) -> Self {
+        self.suggestion = suggest(value, candidates).map(|s| s.to_owned());
+        self
+    }
+
+    /// Renders the diagnostic together with the line
+    /// of the expression that the diagnostic points at.
+    pub fn render(&self, source: &str) -> String {
+        // Find the line and column for the start of the span.
+        let mut line = 1;
+        let mut column = 1;
+        let mut line_start = 0;
+        for (index, character) in source.chars().enumerate() {
+            if index >= self.span.start {
+                break;
+            }
+            if character == '\n' {
+                line += 1;
+                column = 1;
+                line_start = index + 1;
+            } else {
+                column += 1;
+            }
+        }
+
+        let text: String = source
+            .chars()
+            .skip(line_start)
+            .take_while(|c| *c != '\n')
+            .collect();
+        let text = text.trim_end_matches('\r');
+        let length = self.span.end.saturating_sub(self.span.start);
+        let length = length.min(text.chars().count().saturating_sub(column - 1));
+
+        let gutter = " ".repeat(line.to_string().len());
+        format!(
+            "{}\n{}--> line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+            self,
+            gutter,
+            line,
+            column,
+            gutter,
+            line,
+            text,
+            gutter,
+            " ".repeat(column - 1),
+            "^".repeat(length.max(1))
+        )
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.suggestion {
+            Some(suggestion) => write!(f, "{}. Did you mean '{}'?", self.message, suggestion),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// Gets the candidate that is closest to the provided value,
+/// as long as it is close enough to be a likely typo.
+pub fn suggest<'a>(value: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let value = value.to_lowercase();
+    candidates
+        .iter()
+        .map(|candidate| (candidate, get_distance(&value, &candidate.to_lowercase())))
+        .filter(|(candidate, distance)| *distance <= 2.max(candidate.len() / 3))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| *candidate)
+}
+
+/// Calculates the Levenshtein distance between two strings.
+fn get_distance(first: &str, second: &str) -> usize {
+    let second: Vec<char> = second.chars().collect();
+    let mut previous: Vec<usize> = (0..=second.len()).collect();
+    for (i, a) in first.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in second.iter().enumerate() {
+            let cost = if a == *b { 0 } else { 1 };
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[second.len()]
+}
+
+///////////////////////////////////////////////////////////
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("brnch", Some("branch") ; "missing_character")]
+    #[test_case("Staus", Some("status") ; "different_casing")]
+    #[test_case("definitoin", Some("definition") ; "swapped_characters")]
+    #[test_case("foo", None ; "no_match")]
+    fn should_suggest_closest_candidate(value: &str, expected: Option<&str>) {
+        // Given, When
+        let result = suggest(value, &["branch", "status", "definition", "build"]);
+
+        // Then
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn should_render_diagnostic_with_caret() {
+        // Given
+        let diagnostic = Diagnostic::new("Unknown property 'brnch'", Span::new(23, 28))
+            .with_suggestion("brnch", &["branch"]);
+
+        // When
+        let result = diagnostic.render("status == 'failed' and brnch == 'master'");
+
+        // Then
+        assert_eq!(
+            "Unknown property 'brnch'. Did you mean 'branch'?\n\
+             \x20--> line 1, column 24\n\
+             \x20 |\n\
+             1 | status == 'failed' and brnch == 'master'\n\
+             \x20 |                        ^^^^^",
+            result
+        );
+    }
+
+    #[test]
+    fn should_render_diagnostic_on_correct_line() {
+        // Given
+        let diagnostic = Diagnostic::new("Unexpected token '$'", Span::new(22, 23));
+
+        // When
+        let result = diagnostic.render("branch == 'master'\nor $");
+
+        // Then
+        assert!(result.contains("--> line 2, column 4\n"));
+        assert!(result.ends_with("2 | or $\n  |    ^"));
+    }
+}
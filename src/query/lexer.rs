@@ -3,18 +3,49 @@ use std::iter::Peekable;
 use std::{fmt::Display, str::Chars};
 
 use crate::builds::BuildStatus;
+use crate::query::diagnostics::{Diagnostic, Span};
 use crate::query::Operator;
 use crate::DuckResult;
 
+/// The names of all build statuses.
+pub static STATUSES: &[&str] = &[
+    "success",
+    "failed",
+    "canceled",
+    "cancelled",
+    "running",
+    "skipped",
+    "queued",
+];
+
+/// Gets the build status with the provided name.
+pub fn parse_status(name: &str) -> Option<BuildStatus> {
+    match name {
+        "success" => Some(BuildStatus::Success),
+        "canceled" => Some(BuildStatus::Canceled),
+        "cancelled" => Some(BuildStatus::Canceled),
+        "failed" => Some(BuildStatus::Failed),
+        "running" => Some(BuildStatus::Running),
+        "skipped" => Some(BuildStatus::Skipped),
+        "queued" => Some(BuildStatus::Queued),
+        _ => None,
+    }
+}
+
 pub struct TokenStream {
     tokens: Vec<Token>,
+    spans: Vec<Span>,
+    length: usize,
     position: usize,
 }
 
 impl TokenStream {
-    pub fn new(tokens: Vec<Token>) -> TokenStream {
+    pub fn new(tokens: Vec<(Token, Span)>, length: usize) -> TokenStream {
+        let (tokens, spans) = tokens.into_iter().unzip();
         Self {
             tokens,
+            spans,
+            length,
             position: 0,
         }
     }
@@ -34,23 +65,39 @@ impl TokenStream {
 
     pub fn expect(&mut self, expected: Token) -> DuckResult<()> {
         match self.current() {
-            None => Err(format_err!(
-                "Expected token '{}' but found end of stream",
-                expected
-            )),
+            None => Err(Diagnostic::new(
+                format!("Expected token '{}' but found end of stream", expected),
+                self.current_span(),
+            )
+            .into()),
             Some(token) => {
                 if token == &expected {
                     return Ok(());
                 }
-                return Err(format_err!(
-                    "Expected token '{}' but found '{}'",
-                    expected,
-                    token
-                ));
+                return Err(Diagnostic::new(
+                    format!("Expected token '{}' but found '{}'", expected, token),
+                    self.current_span(),
+                )
+                .into());
             }
         }
     }
 
+    /// Gets the span of the current token, or an empty
+    /// span at the end of the expression if there is none.
+    pub fn current_span(&self) -> Span {
+        if self.position >= self.spans.len() {
+            return Span::new(self.length, self.length);
+        }
+        self.spans[self.position].clone()
+    }
+
+    /// Gets a span from the start of the provided
+    /// span to the end of the current token.
+    pub fn span_from(&self, start: &Span) -> Span {
+        Span::new(start.start, self.current_span().end.max(start.end))
+    }
+
     pub fn current(&self) -> Option<&Token> {
         if self.position >= self.tokens.len() {
             return None;
@@ -58,6 +105,13 @@ impl TokenStream {
         Some(&self.tokens[self.position])
     }
 
+    pub fn peek(&self) -> Option<&Token> {
+        if self.position + 1 >= self.tokens.len() {
+            return None;
+        }
+        Some(&self.tokens[self.position + 1])
+    }
+
     pub fn move_next(&mut self) -> bool {
         if self.position >= self.tokens.len() {
             return false;
@@ -81,6 +135,9 @@ pub enum Token {
     False,
     LParen,
     RParen,
+    LBracket,
+    RBracket,
+    Comma,
 }
 
 impl Display for Token {
@@ -88,7 +145,8 @@ impl Display for Token {
         match self {
             Token::False => write!(f, "false"),
             Token::Word(word) => write!(f, "{}", word),
-            Token::Literal(literal) => write!(f, "'{}'", literal),
+            // Messages quote tokens themselves.
+            Token::Literal(literal) => write!(f, "{}", literal),
             Token::Integer(i) => write!(f, "{}", i),
             Token::Status(s) => write!(f, "{}", s),
             Token::Operator(op) => write!(f, "{}", op),
@@ -98,6 +156,9 @@ impl Display for Token {
             Token::True => write!(f, "TRUE"),
             Token::LParen => write!(f, "("),
             Token::RParen => write!(f, ")"),
+            Token::LBracket => write!(f, "["),
+            Token::RBracket => write!(f, "]"),
+            Token::Comma => write!(f, ","),
         }
     }
 }
@@ -122,81 +183,131 @@ impl Token {
 // Lexer
 
 pub fn tokenize(text: &str) -> DuckResult<TokenStream> {
-    let mut result: Vec<Token> = Vec::new();
-    let mut stream = text.chars().peekable();
+    let mut result: Vec<(Token, Span)> = Vec::new();
+    let mut stream = Reader::new(text);
     loop {
-        match stream.peek() {
+        let start = stream.position;
+        let token = match stream.peek() {
             None => break,
             Some(&character) => match character {
-                ' ' => {
+                ' ' | '\t' | '\r' | '\n' => {
                     stream.next();
+                    continue;
                 }
                 'a'..='z' | 'A'..='Z' => {
                     let word = read_word(&mut stream)?;
                     match &word.to_lowercase()[..] {
-                        "or" => result.push(Token::Or),
-                        "and" => result.push(Token::And),
-                        "not" => result.push(Token::Not),
-                        "true" => result.push(Token::True),
-                        "false" => result.push(Token::False),
-                        _ => result.push(Token::Word(word)),
+                        "or" => Token::Or,
+                        "and" => Token::And,
+                        "not" => Token::Not,
+                        "true" => Token::True,
+                        "false" => Token::False,
+                        "like" => Token::Operator(Operator::Like),
+                        "matches" => Token::Operator(Operator::Matches),
+                        "in" => Token::Operator(Operator::In),
+                        "startswith" => Token::Operator(Operator::StartsWith),
+                        "contains" => Token::Operator(Operator::Contains),
+                        lowercase => match parse_status(lowercase) {
+                            Some(status) => Token::Status(status),
+                            None => Token::Word(word),
+                        },
                     }
                 }
-                '0'..='9' => {
-                    let integer = read_integer(&mut stream)?;
-                    result.push(Token::Integer(integer))
-                }
+                '0'..='9' => Token::Integer(read_integer(&mut stream)?),
                 '=' | '!' | '>' | '<' | '&' | '|' => {
                     let symbols = read_symbols(&mut stream)?;
                     match &symbols[..] {
-                        "!" => result.push(Token::Not),
-                        "&&" => result.push(Token::And),
-                        "||" => result.push(Token::Or),
-                        "==" => result.push(Token::Operator(Operator::EqualTo)),
-                        "!=" => result.push(Token::Operator(Operator::NotEqualTo)),
-                        ">" => result.push(Token::Operator(Operator::GreaterThan)),
-                        ">=" => result.push(Token::Operator(Operator::GreaterThanOrEqualTo)),
-                        "<" => result.push(Token::Operator(Operator::LessThan)),
-                        "<=" => result.push(Token::Operator(Operator::LessThanOrEqualTo)),
-                        _ => return Err(format_err!("Unexpected operator '{}'.", symbols)),
+                        "!" => Token::Not,
+                        "&&" => Token::And,
+                        "||" => Token::Or,
+                        "==" => Token::Operator(Operator::EqualTo),
+                        "!=" => Token::Operator(Operator::NotEqualTo),
+                        ">" => Token::Operator(Operator::GreaterThan),
+                        ">=" => Token::Operator(Operator::GreaterThanOrEqualTo),
+                        "<" => Token::Operator(Operator::LessThan),
+                        "<=" => Token::Operator(Operator::LessThanOrEqualTo),
+                        _ => {
+                            return Err(Diagnostic::new(
+                                format!("Unexpected operator '{}'", symbols),
+                                Span::new(start, stream.position),
+                            )
+                            .with_suggestion(&symbols, &["==", "!=", ">=", "<=", "&&", "||"])
+                            .into())
+                        }
                     }
                 }
                 '\'' => {
                     stream.next();
-                    let literal = read_literal(&mut stream)?;
-                    match &literal[..] {
-                        "success" => result.push(Token::Status(BuildStatus::Success)),
-                        "canceled" => result.push(Token::Status(BuildStatus::Canceled)),
-                        "cancelled" => result.push(Token::Status(BuildStatus::Canceled)),
-                        "failed" => result.push(Token::Status(BuildStatus::Failed)),
-                        "running" => result.push(Token::Status(BuildStatus::Running)),
-                        "skipped" => result.push(Token::Status(BuildStatus::Skipped)),
-                        "queued" => result.push(Token::Status(BuildStatus::Queued)),
-                        _ => result.push(Token::Literal(literal)),
-                    }
+                    Token::Literal(read_literal(&mut stream)?)
                 }
                 '(' => {
-                    result.push(Token::LParen);
                     stream.next();
+                    Token::LParen
                 }
                 ')' => {
-                    result.push(Token::RParen);
                     stream.next();
+                    Token::RParen
+                }
+                '[' => {
+                    stream.next();
+                    Token::LBracket
+                }
+                ']' => {
+                    stream.next();
+                    Token::RBracket
+                }
+                ',' => {
+                    stream.next();
+                    Token::Comma
+                }
+                _ => {
+                    return Err(Diagnostic::new(
+                        format!("Unexpected token '{}'", character),
+                        Span::new(start, start + 1),
+                    )
+                    .into())
                 }
-                _ => return Err(format_err!("Unexpected token '{}'.", character)),
             },
+        };
+        result.push((token, Span::new(start, stream.position)));
+    }
+    Ok(TokenStream::new(result, stream.position))
+}
+
+/// Reads characters while keeping track of the position in the expression.
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            position: 0,
         }
     }
-    Ok(TokenStream::new(result))
+
+    fn peek(&mut self) -> Option<&char> {
+        self.chars.peek()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.chars.next();
+        if character.is_some() {
+            self.position += 1;
+        }
+        character
+    }
 }
 
-fn read_word(stream: &mut Peekable<Chars>) -> DuckResult<String> {
+fn read_word(stream: &mut Reader) -> DuckResult<String> {
     let mut accumulator: Vec<char> = Vec::new();
     loop {
         match stream.peek() {
             None => break,
             Some(&character) => match character {
-                'a'..='z' | 'A'..='Z' => {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => {
                     accumulator.push(character);
                     stream.next();
                 }
@@ -207,11 +318,19 @@ fn read_word(stream: &mut Peekable<Chars>) -> DuckResult<String> {
     Ok(accumulator.into_iter().collect())
 }
 
-fn read_literal(stream: &mut Peekable<Chars>) -> DuckResult<String> {
+fn read_literal(stream: &mut Reader) -> DuckResult<String> {
+    // The opening quote have already been consumed.
+    let start = stream.position - 1;
     let mut accumulator: Vec<char> = Vec::new();
     loop {
         match stream.peek() {
-            None => return Err(format_err!("Unexpected end of string.")),
+            None => {
+                return Err(Diagnostic::new(
+                    "Unexpected end of string",
+                    Span::new(start, stream.position),
+                )
+                .into())
+            }
             Some(&character) => match character {
                 '\'' => {
                     stream.next();
@@ -227,7 +346,8 @@ fn read_literal(stream: &mut Peekable<Chars>) -> DuckResult<String> {
     Ok(accumulator.into_iter().collect())
 }
 
-fn read_integer(stream: &mut Peekable<Chars>) -> DuckResult<i64> {
+fn read_integer(stream: &mut Reader) -> DuckResult<i64> {
+    let start = stream.position;
     let mut accumulator: Vec<char> = Vec::new();
     loop {
         match stream.peek() {
@@ -241,11 +361,50 @@ fn read_integer(stream: &mut Peekable<Chars>) -> DuckResult<i64> {
             },
         }
     }
-    let result: String = accumulator.into_iter().collect();
-    Ok(result.parse::<i64>()?)
+    let literal: String = accumulator.into_iter().collect();
+    let result = match literal.parse::<i64>() {
+        Ok(result) => result,
+        Err(_) => {
+            return Err(Diagnostic::new(
+                format!("The integer '{}' is too large", literal),
+                Span::new(start, stream.position),
+            )
+            .into())
+        }
+    };
+
+    // Is this a duration such as 30m or 2h?
+    let multiplier = match stream.peek() {
+        Some('s') => 1,
+        Some('m') => 60,
+        Some('h') => 60 * 60,
+        Some('d') => 60 * 60 * 24,
+        _ => return Ok(result),
+    };
+    let unit = stream.next().unwrap();
+    if let Some(&character) = stream.peek() {
+        if character.is_ascii_alphanumeric() || character == '_' {
+            return Err(Diagnostic::new(
+                format!(
+                    "Unexpected character '{}' after '{}{}'",
+                    character, result, unit
+                ),
+                Span::new(start, stream.position + 1),
+            )
+            .into());
+        }
+    }
+    match result.checked_mul(multiplier) {
+        Some(result) => Ok(result),
+        None => Err(Diagnostic::new(
+            format!("The duration '{}{}' is too large", result, unit),
+            Span::new(start, stream.position),
+        )
+        .into()),
+    }
 }
 
-fn read_symbols(stream: &mut Peekable<Chars>) -> DuckResult<String> {
+fn read_symbols(stream: &mut Reader) -> DuckResult<String> {
     let mut accumulator: Vec<char> = Vec::new();
     loop {
         match stream.peek() {
@@ -306,6 +465,67 @@ mod tests {
         assert_eq!(tokens[2], Token::Integer(89));
     }
 
+    #[test]
+    fn should_recognize_words_with_underscores_and_digits() {
+        // Given, When
+        let tokens = tokenize("project_name foo2").unwrap().get_tokens();
+
+        // Then
+        assert_eq!(2, tokens.len());
+        assert_eq!(tokens[0], Token::Word("project_name".to_owned()));
+        assert_eq!(tokens[1], Token::Word("foo2".to_owned()));
+    }
+
+    #[test_case("45s", 45 ; "seconds")]
+    #[test_case("30m", 1800 ; "minutes")]
+    #[test_case("2h", 7200 ; "hours")]
+    #[test_case("1d", 86400 ; "days")]
+    fn should_recognize_durations(expression: &str, expected: i64) {
+        // Given, When
+        let tokens = tokenize(expression).unwrap().get_tokens();
+
+        // Then
+        assert_eq!(1, tokens.len());
+        assert_eq!(tokens[0], Token::Integer(expected));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unexpected character")]
+    fn should_return_error_if_duration_has_unknown_unit() {
+        // Given, When, Then
+        tokenize("30min").unwrap();
+    }
+
+    #[test]
+    fn should_return_error_if_duration_is_too_large() {
+        // Given, When
+        let result = tokenize("duration > 9999999999999999d");
+
+        // Then
+        let error = result.err().unwrap();
+        let diagnostic = error.downcast_ref::<Diagnostic>().unwrap();
+        assert_eq!(
+            "The duration '9999999999999999d' is too large\n\
+             \x20--> line 1, column 12\n\
+             \x20 |\n\
+             1 | duration > 9999999999999999d\n\
+             \x20 |            ^^^^^^^^^^^^^^^^^",
+            diagnostic.render("duration > 9999999999999999d")
+        );
+    }
+
+    #[test]
+    fn should_return_error_if_integer_is_too_large() {
+        // Given, When
+        let result = tokenize("99999999999999999999");
+
+        // Then
+        assert_eq!(
+            "The integer '99999999999999999999' is too large",
+            format!("{}", result.err().unwrap())
+        );
+    }
+
     #[test]
     fn should_recognize_keywords() {
         // Given, When
@@ -340,13 +560,14 @@ mod tests {
         assert_eq!(tokens[5], Token::RParen);
     }
 
-    #[test_case("'success'", Token::Status(BuildStatus::Success) ; "success_status")]
-    #[test_case("'failed'", Token::Status(BuildStatus::Failed) ; "failed_status")]
-    #[test_case("'canceled'", Token::Status(BuildStatus::Canceled) ; "canceled_status")]
-    #[test_case("'cancelled'", Token::Status(BuildStatus::Canceled) ; "cancelled_status")]
-    #[test_case("'queued'", Token::Status(BuildStatus::Queued) ; "queued_status")]
-    #[test_case("'running'", Token::Status(BuildStatus::Running) ; "running_status")]
-    #[test_case("'skipped'", Token::Status(BuildStatus::Skipped) ; "skipped_status")]
+    #[test_case("success", Token::Status(BuildStatus::Success) ; "success_status")]
+    #[test_case("failed", Token::Status(BuildStatus::Failed) ; "failed_status")]
+    #[test_case("canceled", Token::Status(BuildStatus::Canceled) ; "canceled_status")]
+    #[test_case("cancelled", Token::Status(BuildStatus::Canceled) ; "cancelled_status")]
+    #[test_case("queued", Token::Status(BuildStatus::Queued) ; "queued_status")]
+    #[test_case("running", Token::Status(BuildStatus::Running) ; "running_status")]
+    #[test_case("Skipped", Token::Status(BuildStatus::Skipped) ; "skipped_status")]
+    #[test_case("'failed'", Token::Literal("failed".to_owned()) ; "quoted_status")]
     fn should_recognize_statuses(expression: &str, expected: Token) {
         // Given, When
         let tokens = tokenize(expression).unwrap().get_tokens();
@@ -371,6 +592,36 @@ mod tests {
         assert_eq!(tokens[5], Token::Operator(Operator::LessThanOrEqualTo));
     }
 
+    #[test]
+    fn should_recognize_pattern_operators() {
+        // Given, When
+        let tokens = tokenize("like MATCHES in StartsWith contains")
+            .unwrap()
+            .get_tokens();
+
+        // Then
+        assert_eq!(5, tokens.len());
+        assert_eq!(tokens[0], Token::Operator(Operator::Like));
+        assert_eq!(tokens[1], Token::Operator(Operator::Matches));
+        assert_eq!(tokens[2], Token::Operator(Operator::In));
+        assert_eq!(tokens[3], Token::Operator(Operator::StartsWith));
+        assert_eq!(tokens[4], Token::Operator(Operator::Contains));
+    }
+
+    #[test]
+    fn should_recognize_lists() {
+        // Given, When
+        let tokens = tokenize("['foo', 3]").unwrap().get_tokens();
+
+        // Then
+        assert_eq!(5, tokens.len());
+        assert_eq!(tokens[0], Token::LBracket);
+        assert_eq!(tokens[1], Token::Literal("foo".to_owned()));
+        assert_eq!(tokens[2], Token::Comma);
+        assert_eq!(tokens[3], Token::Integer(3));
+        assert_eq!(tokens[4], Token::RBracket);
+    }
+
     #[test]
     fn should_tokenize_expression_correctly() {
         // Given, When
@@ -386,6 +637,6 @@ mod tests {
         assert_eq!(tokens[3], Token::And);
         assert_eq!(tokens[4], Token::Word("status".to_owned()));
         assert_eq!(tokens[5], Token::Operator(Operator::NotEqualTo));
-        assert_eq!(tokens[6], Token::Status(BuildStatus::Skipped));
+        assert_eq!(tokens[6], Token::Literal("skipped".to_owned()));
     }
 }
@@ -1,14 +1,40 @@
-use crate::query::lexer::{Token, TokenStream};
-use crate::query::{Constant, Expression, Property};
+use regex::Regex;
+
+use crate::builds::BuildStatus;
+use crate::query::diagnostics::{Diagnostic, Span};
+use crate::query::lexer::{self, Token, TokenStream, STATUSES};
+use crate::query::{Constant, Expression, Function, Operator, Property};
 use crate::DuckResult;
 
+/// The names of all properties.
+static PROPERTIES: &[&str] = &[
+    "branch",
+    "status",
+    "project",
+    "definition",
+    "build",
+    "collector",
+    "provider",
+    "project_name",
+    "definition_name",
+    "build_number",
+    "origin",
+    "url",
+    "started_at",
+    "finished_at",
+    "duration",
+];
+
+/// The names of all functions.
+static FUNCTIONS: &[&str] = &["age", "now"];
+
 pub fn parse(stream: &mut TokenStream) -> DuckResult<Expression> {
     parse_or(stream)
 }
 
 fn parse_or(stream: &mut TokenStream) -> DuckResult<Expression> {
     if stream.current().is_none() {
-        return Err(format_err!("Unexpected end of token stream"));
+        return Err(unexpected_end(stream));
     }
 
     let mut expression = parse_and(stream)?;
@@ -26,7 +52,7 @@ fn parse_or(stream: &mut TokenStream) -> DuckResult<Expression> {
 
 fn parse_and(stream: &mut TokenStream) -> DuckResult<Expression> {
     if stream.current().is_none() {
-        return Err(format_err!("Unexpected end of token stream"));
+        return Err(unexpected_end(stream));
     }
 
     let mut expression = parse_predicate(stream)?;
@@ -44,7 +70,7 @@ fn parse_and(stream: &mut TokenStream) -> DuckResult<Expression> {
 
 fn parse_predicate(stream: &mut TokenStream) -> DuckResult<Expression> {
     if stream.current().is_none() {
-        return Err(format_err!("Unexpected end of token stream"));
+        return Err(unexpected_end(stream));
     }
 
     if let Some(token) = stream.current() {
@@ -59,10 +85,12 @@ fn parse_predicate(stream: &mut TokenStream) -> DuckResult<Expression> {
 
 fn parse_relation(stream: &mut TokenStream) -> DuckResult<Expression> {
     if stream.current().is_none() {
-        return Err(format_err!("Unexpected end of token stream"));
+        return Err(unexpected_end(stream));
     }
 
+    let start = stream.current_span();
     let expression = parse_literal(stream)?;
+    let left_span = stream.span_from(&start);
     stream.move_next();
 
     if let Some(token) = stream.current() {
@@ -70,9 +98,24 @@ fn parse_relation(stream: &mut TokenStream) -> DuckResult<Expression> {
             stream.move_next();
 
             let left = expression;
+            let start = stream.current_span();
             let right = parse_literal(stream)?;
+            let right_span = stream.span_from(&start);
             stream.move_next();
 
+            let (left, right) = match op {
+                Operator::EqualTo | Operator::NotEqualTo | Operator::In => {
+                    let right = coerce_status(&left, right, &right_span)?;
+                    let left = coerce_status(&right, left, &left_span)?;
+                    (left, right)
+                }
+                Operator::Matches => {
+                    check_pattern(&right, right_span)?;
+                    (left, right)
+                }
+                _ => (left, right),
+            };
+
             return Ok(Expression::Relational(Box::new(left), Box::new(right), op));
         }
     }
@@ -80,10 +123,64 @@ fn parse_relation(stream: &mut TokenStream) -> DuckResult<Expression> {
     Ok(expression)
 }
 
+/// Makes sure that regular expressions given as constants compile,
+/// so that invalid patterns are reported when the expression is parsed.
+fn check_pattern(constant: &Expression, span: Span) -> DuckResult<()> {
+    if let Expression::Constant(Constant::String(pattern)) = constant {
+        if Regex::new(pattern).is_err() {
+            return Err(
+                Diagnostic::new(format!("Invalid regular expression '{}'", pattern), span).into(),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Converts strings compared to a build status into statuses, so that
+/// quoted statuses (such as `'failed'`) work just like bare ones.
+/// Strings that aren't known statuses would otherwise result in a
+/// less helpful type error.
+fn coerce_status(
+    property: &Expression,
+    constant: Expression,
+    span: &Span,
+) -> DuckResult<Expression> {
+    if property != &Expression::Property(Property::Status) {
+        return Ok(constant);
+    }
+    Ok(match constant {
+        Expression::Constant(Constant::String(value)) => {
+            Expression::Constant(Constant::Status(get_status(&value, span)?))
+        }
+        Expression::Constant(Constant::List(items)) => Expression::Constant(Constant::List(
+            items
+                .into_iter()
+                .map(|item| match item {
+                    Constant::String(value) => Ok(Constant::Status(get_status(&value, span)?)),
+                    item => Ok(item),
+                })
+                .collect::<DuckResult<Vec<Constant>>>()?,
+        )),
+        constant => constant,
+    })
+}
+
+fn get_status(value: &str, span: &Span) -> DuckResult<BuildStatus> {
+    match lexer::parse_status(value) {
+        Some(status) => Ok(status),
+        None => Err(
+            Diagnostic::new(format!("Unknown status '{}'", value), span.clone())
+                .with_suggestion(value, STATUSES)
+                .into(),
+        ),
+    }
+}
+
 fn parse_literal(stream: &mut TokenStream) -> DuckResult<Expression> {
     match stream.current() {
-        None => Err(format_err!("Unexpected end of token stream")),
+        None => Err(unexpected_end(stream)),
         Some(token) => match token {
+            Token::Word(_) if stream.peek() == Some(&Token::LParen) => parse_function(stream),
             Token::Word(word) => match &word[..] {
                 "branch" => Ok(Expression::Property(Property::Branch)),
                 "status" => Ok(Expression::Property(Property::Status)),
@@ -92,7 +189,20 @@ fn parse_literal(stream: &mut TokenStream) -> DuckResult<Expression> {
                 "build" => Ok(Expression::Property(Property::Build)),
                 "collector" => Ok(Expression::Property(Property::Collector)),
                 "provider" => Ok(Expression::Property(Property::Provider)),
-                _ => Err(format_err!("Unknown property '{}'", word)),
+                "project_name" => Ok(Expression::Property(Property::ProjectName)),
+                "definition_name" => Ok(Expression::Property(Property::DefinitionName)),
+                "build_number" => Ok(Expression::Property(Property::BuildNumber)),
+                "origin" => Ok(Expression::Property(Property::Origin)),
+                "url" => Ok(Expression::Property(Property::Url)),
+                "started_at" => Ok(Expression::Property(Property::StartedAt)),
+                "finished_at" => Ok(Expression::Property(Property::FinishedAt)),
+                "duration" => Ok(Expression::Property(Property::Duration)),
+                _ => Err(Diagnostic::new(
+                    format!("Unknown property '{}'", word),
+                    stream.current_span(),
+                )
+                .with_suggestion(word, PROPERTIES)
+                .into()),
             },
             Token::Literal(literal) => Ok(Expression::Constant(Constant::String(literal.clone()))),
             Token::Integer(number) => Ok(Expression::Constant(Constant::Integer(*number))),
@@ -100,7 +210,12 @@ fn parse_literal(stream: &mut TokenStream) -> DuckResult<Expression> {
             Token::True => Ok(Expression::Constant(Constant::Boolean(true))),
             Token::False => Ok(Expression::Constant(Constant::Boolean(false))),
             Token::LParen => parse_scope(stream),
-            _ => Err(format_err!("Could not parse literal expression")),
+            Token::LBracket => parse_list(stream),
+            _ => Err(Diagnostic::new(
+                format!("Expected a property or a value but found '{}'", token),
+                stream.current_span(),
+            )
+            .into()),
         },
     }
 }
@@ -111,6 +226,76 @@ fn parse_scope(stream: &mut TokenStream) -> DuckResult<Expression> {
     Ok(Expression::Scope(Box::new(expression)))
 }
 
+fn parse_function(stream: &mut TokenStream) -> DuckResult<Expression> {
+    let span = stream.current_span();
+    let function = match stream.current() {
+        Some(Token::Word(word)) => match &word[..] {
+            "age" => Function::Age,
+            "now" => Function::Now,
+            _ => {
+                return Err(
+                    Diagnostic::new(format!("Unknown function '{}'", word), span)
+                        .with_suggestion(word, FUNCTIONS)
+                        .into(),
+                )
+            }
+        },
+        _ => return Err(Diagnostic::new("Could not parse function", span).into()),
+    };
+    stream.move_next();
+    stream.consume(Token::LParen)?;
+
+    let mut args = Vec::new();
+    while stream.expect(Token::RParen).is_err() {
+        if !args.is_empty() {
+            stream.consume(Token::Comma)?;
+        }
+        args.push(parse(stream)?);
+    }
+
+    if args.len() != function.get_arity() {
+        return Err(Diagnostic::new(
+            format!(
+                "Function '{}' expects {} argument(s) but got {}",
+                function,
+                function.get_arity(),
+                args.len()
+            ),
+            stream.span_from(&span),
+        )
+        .into());
+    }
+
+    Ok(Expression::Function(function, args))
+}
+
+fn parse_list(stream: &mut TokenStream) -> DuckResult<Expression> {
+    stream.consume(Token::LBracket)?;
+    let mut items = Vec::new();
+    while stream.expect(Token::RBracket).is_err() {
+        if !items.is_empty() {
+            stream.consume(Token::Comma)?;
+        }
+        let start = stream.current_span();
+        match parse_literal(stream)? {
+            Expression::Constant(constant) => items.push(constant),
+            _ => {
+                return Err(Diagnostic::new(
+                    "Lists can only contain constants",
+                    stream.span_from(&start),
+                )
+                .into())
+            }
+        }
+        stream.move_next();
+    }
+    Ok(Expression::Constant(Constant::List(items)))
+}
+
+fn unexpected_end(stream: &TokenStream) -> failure::Error {
+    Diagnostic::new("Unexpected end of expression", stream.current_span()).into()
+}
+
 ///////////////////////////////////////////////////////////
 // Tests
 
@@ -119,7 +304,6 @@ mod tests {
     use super::*;
     use crate::builds::BuildStatus;
     use crate::query::lexer;
-    use crate::query::Operator;
 
     #[test]
     fn should_parse_expression() {
@@ -154,4 +338,179 @@ mod tests {
             )
         )
     }
+
+    #[test]
+    fn should_parse_list_membership() {
+        // Given
+        let query = "branch in ['master', 'develop']";
+        let tokens = &mut lexer::tokenize(&query[..]).unwrap();
+
+        // When
+        let expression = parse(tokens).unwrap();
+
+        // Then
+        assert_eq!(
+            expression,
+            Expression::Relational(
+                Box::new(Expression::Property(Property::Branch)),
+                Box::new(Expression::Constant(Constant::List(vec![
+                    Constant::String("master".to_owned()),
+                    Constant::String("develop".to_owned())
+                ]))),
+                Operator::In
+            )
+        )
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected token ',' but found")]
+    fn should_return_error_if_list_items_are_not_separated() {
+        // Given
+        let query = "branch in ['master' 'develop']";
+        let tokens = &mut lexer::tokenize(&query[..]).unwrap();
+
+        // When, Then
+        parse(tokens).unwrap();
+    }
+
+    #[test]
+    fn should_parse_function() {
+        // Given
+        let query = "age(finished_at) < 1h";
+        let tokens = &mut lexer::tokenize(&query[..]).unwrap();
+
+        // When
+        let expression = parse(tokens).unwrap();
+
+        // Then
+        assert_eq!(
+            expression,
+            Expression::Relational(
+                Box::new(Expression::Function(
+                    Function::Age,
+                    vec![Expression::Property(Property::FinishedAt)]
+                )),
+                Box::new(Expression::Constant(Constant::Integer(3600))),
+                Operator::LessThan
+            )
+        )
+    }
+
+    #[test]
+    #[should_panic(expected = "Function 'age' expects 1 argument(s) but got 0")]
+    fn should_return_error_if_function_has_wrong_number_of_arguments() {
+        // Given
+        let tokens = &mut lexer::tokenize("age() > 3").unwrap();
+
+        // When, Then
+        parse(tokens).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown function")]
+    fn should_return_error_if_function_is_unknown() {
+        // Given
+        let tokens = &mut lexer::tokenize("foo(3) > 3").unwrap();
+
+        // When, Then
+        parse(tokens).unwrap();
+    }
+
+    #[test]
+    fn should_return_error_if_regular_expression_is_invalid() {
+        // Given
+        let tokens = &mut lexer::tokenize("branch matches '['").unwrap();
+
+        // When
+        let result = parse(tokens);
+
+        // Then
+        assert_eq!(
+            "Invalid regular expression '['",
+            format!("{}", result.unwrap_err())
+        );
+    }
+
+    #[test]
+    fn should_keep_quoted_statuses_as_strings_unless_compared_to_status() {
+        // Given
+        let tokens = &mut lexer::tokenize(
+            "branch contains 'failed' and definition like 'success*' and status == 'failed'",
+        )
+        .unwrap();
+
+        // When
+        let result = parse(tokens).unwrap();
+
+        // Then
+        assert_eq!(
+            Expression::And(
+                Box::new(Expression::And(
+                    Box::new(Expression::Relational(
+                        Box::new(Expression::Property(Property::Branch)),
+                        Box::new(Expression::Constant(Constant::String("failed".to_owned()))),
+                        Operator::Contains
+                    )),
+                    Box::new(Expression::Relational(
+                        Box::new(Expression::Property(Property::Definition)),
+                        Box::new(Expression::Constant(Constant::String(
+                            "success*".to_owned()
+                        ))),
+                        Operator::Like
+                    )),
+                )),
+                Box::new(Expression::Relational(
+                    Box::new(Expression::Property(Property::Status)),
+                    Box::new(Expression::Constant(Constant::Status(BuildStatus::Failed))),
+                    Operator::EqualTo
+                )),
+            ),
+            result
+        );
+    }
+
+    #[test]
+    fn should_suggest_property_if_property_is_unknown() {
+        // Given
+        let tokens = &mut lexer::tokenize("brnch == 'master'").unwrap();
+
+        // When
+        let result = parse(tokens);
+
+        // Then
+        assert_eq!(
+            "Unknown property 'brnch'. Did you mean 'branch'?",
+            format!("{}", result.unwrap_err())
+        );
+    }
+
+    #[test]
+    fn should_suggest_status_if_status_is_unknown() {
+        // Given
+        let tokens = &mut lexer::tokenize("status != 'faild'").unwrap();
+
+        // When
+        let result = parse(tokens);
+
+        // Then
+        assert_eq!(
+            "Unknown status 'faild'. Did you mean 'failed'?",
+            format!("{}", result.unwrap_err())
+        );
+    }
+
+    #[test]
+    fn should_suggest_status_if_status_in_list_is_unknown() {
+        // Given
+        let tokens = &mut lexer::tokenize("status in ['failed', 'runing']").unwrap();
+
+        // When
+        let result = parse(tokens);
+
+        // Then
+        assert_eq!(
+            "Unknown status 'runing'. Did you mean 'running'?",
+            format!("{}", result.unwrap_err())
+        );
+    }
 }
@@ -6,10 +6,18 @@ use crate::DuckResult;
 pub mod colors;
 pub mod date;
 pub mod http;
+pub mod secret;
 pub mod switch;
 pub mod text;
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// The version of the HTTP API contract (`/api/v1/...`).
+/// Only bump this when making breaking changes to the API.
+pub const API_VERSION: u16 = 1;
+/// All versions of the HTTP API contract that this server supports.
+pub const API_VERSIONS: &[u16] = &[1];
+/// The capabilities that this server's API supports.
+pub const API_CAPABILITIES: &[&str] = &["builds", "views", "ingest", "webhooks"];
 pub const UI_TITLE: &str = "Duck";
 
 /// A super naive implementation of a message bus
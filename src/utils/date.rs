@@ -5,6 +5,9 @@ use crate::DuckResult;
 pub static TEAMCITY_FORMAT: &str = "%Y%m%dT%H%M%S%z";
 pub static AZURE_DEVOPS_FORMAT: &str = "%+";
 pub static GITHUB_FORMAT: &str = "%+";
+pub static BITBUCKET_FORMAT: &str = "%+";
+pub static CIRCLECI_FORMAT: &str = "%+";
+pub static GITLAB_FORMAT: &str = "%+";
 pub static OCTOPUS_DEPLOY_FORMAT: &str = "%+";
 pub static APPVEYOR_FORMAT: &str = "%+";
 pub static DEBUGGER_FORMAT: &str = "%+";
@@ -44,6 +47,12 @@ mod tests {
         assert_eq!(1580589796, result);
     }
 
+    #[test]
+    fn should_parse_gitlab_format() {
+        let result = to_timestamp("2020-05-04T08:27:12.441Z", GITLAB_FORMAT).unwrap();
+        assert_eq!(1588580832, result);
+    }
+
     #[test]
     fn should_parse_appveyor_format() {
         let result = to_timestamp("2020-03-11T12:09:48.1638791+00:00", APPVEYOR_FORMAT).unwrap();
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fmt;
+use std::time::Duration;
 
 use base64::encode;
 use reqwest::{Client, Response, StatusCode};
@@ -94,9 +95,14 @@ impl Default for ReqwestClient {
 
 impl ReqwestClient {
     pub fn new() -> Self {
-        Self {
-            client: Client::new(),
-        }
+        // Use timeouts so a hanging server can't
+        // block a collector (and shutdown) forever.
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(60))
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        Self { client }
     }
 }
 
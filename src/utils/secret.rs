@@ -0,0 +1,33 @@
+use sha2::{Digest, Sha256};
+
+/// Compares a secret with a provided value in constant time,
+/// so the comparison doesn't reveal how much of the value was correct.
+pub fn is_match(secret: &str, value: &str) -> bool {
+    if secret.is_empty() {
+        return false;
+    }
+    // Comparing digests means that the compared
+    // values always have the same length.
+    let secret = Sha256::digest(secret.as_bytes());
+    let value = Sha256::digest(value.as_bytes());
+    secret
+        .iter()
+        .zip(value.iter())
+        .fold(0, |result, (a, b)| result | (a ^ b))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("SECRET", "SECRET", true ; "same")]
+    #[test_case("SECRET", "secret", false ; "different_casing")]
+    #[test_case("SECRET", "SECRET2", false ; "different_length")]
+    #[test_case("SECRET", "", false ; "empty_value")]
+    #[test_case("", "", false ; "empty_secret")]
+    fn should_match_secret(secret: &str, value: &str, expected: bool) {
+        assert_eq!(expected, is_match(secret, value));
+    }
+}